@@ -1,6 +1,37 @@
+use std::{path::Path, process::Command};
+
+use duchess_build_rs::Configuration;
+
 fn main() {
+    compile_support_classes();
+
     duchess_build_rs::DuchessBuildRs::new()
         .with_src_path("src/".into())
         .execute()
         .unwrap();
 }
+
+/// Compiles the Java classes under `java/` that duchess defines in the JVM at runtime
+/// (see `src/callback.rs`). They are written to `$OUT_DIR/java` and embedded with `include_bytes!`.
+fn compile_support_classes() {
+    println!("cargo:rerun-if-changed=java");
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let class_dir = Path::new(&out_dir).join("java");
+
+    let output = Command::new(Configuration::default().bin_path("javac"))
+        // Oldest release with `java.lang.ref.Cleaner`
+        .args(["--release", "9", "-d"])
+        .arg(&class_dir)
+        .arg(Path::new("java").join("duchess").join("RustCallback.java"))
+        .output()
+        .expect("failed to execute `javac`");
+
+    if !output.status.success() {
+        panic!(
+            "failed to compile duchess support classes: {}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr),
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use duchess_reflect::{
     argument::MethodSelector,
+    class_info::{DotId, Id},
     parse::{Parse, Parser},
 };
 use proc_macro2::Span;
@@ -10,7 +11,7 @@ use crate::{log, re};
 /// Process a file and reflect any `#[java(...)]` attributes that were found
 pub(crate) fn process_file(
     rs_file: &crate::files::File,
-    reflector: &mut duchess_reflect::reflect::JavapReflector,
+    reflector: &mut dyn duchess_reflect::reflect::Reflect,
 ) -> anyhow::Result<bool> {
     let mut watch_file = false;
     for capture in re::java_derive().captures_iter(&rs_file.contents) {
@@ -28,12 +29,22 @@ pub(crate) fn process_file(
                 return Ok(true);
             }
         };
-        reflector.reflect_and_cache(
+        reflector.reflect(
             &derive_java_attr.method_selector.class_name(),
             Span::call_site(),
         )?;
         watch_file = true;
     }
+    for capture in re::java_derive_builder().captures_iter(&rs_file.contents) {
+        log!(
+            "Found derive(java) builder `{}` in {}",
+            &capture[1],
+            rs_file.path.display(),
+        );
+        let class_name: DotId = capture[1].split('.').map(Id::from).collect();
+        reflector.reflect(&class_name, Span::call_site())?;
+        watch_file = true;
+    }
     Ok(watch_file)
 }
 
@@ -1,15 +1,17 @@
-use duchess_reflect::{
-    class_info::ClassRef,
-    reflect::{JavapReflector, Reflect},
-};
+use duchess_reflect::{class_info::ClassRef, reflect::Reflect};
 use proc_macro2::{Span, TokenStream};
 use syn::spanned::Spanned;
 
 use crate::{files::File, java_compiler::JavaCompiler, log, shim_writer::ShimWriter};
 
-pub fn process_impl(compiler: &JavaCompiler, file: &File, offset: usize) -> anyhow::Result<()> {
+pub fn process_impl(
+    compiler: &JavaCompiler,
+    reflector: &mut dyn Reflect,
+    file: &File,
+    offset: usize,
+) -> anyhow::Result<()> {
     let the_impl: JavaInterfaceImpl = syn::parse_str(file.rust_slice_from(offset))?;
-    the_impl.generate_shim(compiler)?;
+    the_impl.generate_shim(compiler, reflector)?;
     Ok(())
 }
 
@@ -31,8 +33,11 @@ impl syn::parse::Parse for JavaInterfaceImpl {
 }
 
 impl JavaInterfaceImpl {
-    fn generate_shim(&self, compiler: &JavaCompiler) -> anyhow::Result<()> {
-        let mut reflector = JavapReflector::new(compiler.configuration());
+    fn generate_shim(
+        &self,
+        compiler: &JavaCompiler,
+        reflector: &mut dyn Reflect,
+    ) -> anyhow::Result<()> {
         let (java_interface_ref, java_interface_span) = self.java_interface()?;
         let java_interface_info =
             reflector.reflect(&java_interface_ref.name, java_interface_span)?;
@@ -50,10 +50,6 @@ impl JavaCompiler {
         })
     }
 
-    pub fn configuration(&self) -> &Configuration {
-        &self.configuration
-    }
-
     fn src_dir(&self) -> PathBuf {
         self.temp_dir_path.join("src")
     }
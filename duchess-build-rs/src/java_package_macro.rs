@@ -1,12 +1,12 @@
 use std::time::Instant;
 
 use anyhow::Context;
-use duchess_reflect::{argument::DuchessDeclaration, parse::Parser, reflect::JavapReflector};
+use duchess_reflect::{argument::DuchessDeclaration, parse::Parser, reflect::Reflect};
 use proc_macro2::{Span, TokenStream};
 
 use crate::{files::File, java_package_macro, log, re};
 
-pub fn process_file(rs_file: &File, reflector: &mut JavapReflector) -> anyhow::Result<bool> {
+pub fn process_file(rs_file: &File, reflector: &mut dyn Reflect) -> anyhow::Result<bool> {
     let mut watch_file = false;
     for capture in re::java_package().captures_iter(&rs_file.contents) {
         let std::ops::Range { start, end: _ } = capture.get(0).unwrap().range();
@@ -22,7 +22,7 @@ pub fn process_file(rs_file: &File, reflector: &mut JavapReflector) -> anyhow::R
     Ok(watch_file)
 }
 
-fn process_macro(reflector: &mut JavapReflector, file: &File, offset: usize) -> anyhow::Result<()> {
+fn process_macro(reflector: &mut dyn Reflect, file: &File, offset: usize) -> anyhow::Result<()> {
     let the_impl: JavaPackageMacro = match syn::parse_str(file.rust_slice_from(offset))
         .with_context(|| {
             format!(
@@ -82,15 +82,12 @@ impl JavaPackageMacro {
     }
 }
 
-fn cache_all_classes(
-    decl: DuchessDeclaration,
-    reflector: &mut JavapReflector,
-) -> anyhow::Result<()> {
+fn cache_all_classes(decl: DuchessDeclaration, reflector: &mut dyn Reflect) -> anyhow::Result<()> {
     let _root_map = decl.to_root_map(reflector)?;
     for class in _root_map.class_names() {
         // forcibly reflect every class
         let now = Instant::now();
-        reflector.reflect_and_cache(&class, Span::call_site())?;
+        reflector.reflect(&class, Span::call_site())?;
         log!("Reflecting {} took {:?}", class, now.elapsed());
     }
     Ok(())
@@ -4,8 +4,9 @@ use std::{
 };
 
 use anyhow::Context;
-use duchess_reflect::reflect::JavapReflector;
+use duchess_reflect::reflect::Reflect;
 use java_compiler::JavaCompiler;
+use reflector::PackageReflector;
 
 mod code_writer;
 mod derive_java;
@@ -15,10 +16,23 @@ mod java_compiler;
 mod java_package_macro;
 mod log;
 mod re;
+mod reflector;
 mod shim_writer;
 
 pub use duchess_reflect::config::Configuration;
 
+/// How duchess learns the signatures of the Java classes it is asked to mirror.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Reflection {
+    /// Run `javap` on each class.
+    #[default]
+    Javap,
+
+    /// Read `.class` files directly from the JDK and the classpath.
+    /// This avoids spawning a process per class, but requires a JDK with a `jmods` directory (or an `rt.jar`).
+    Bytecode,
+}
+
 /// Build Rs configuration for duchess.
 /// To use duchess you must invoke [`DuchessBuildRs::execute`][].
 ///
@@ -35,6 +49,8 @@ pub use duchess_reflect::config::Configuration;
 /// ```
 pub struct DuchessBuildRs {
     configuration: Configuration,
+    reflection: Reflection,
+    package_reflections: Vec<(String, Reflection)>,
     src_path: PathBuf,
     in_cargo: bool,
     temporary_dir: Option<PathBuf>,
@@ -44,6 +60,8 @@ impl Default for DuchessBuildRs {
     fn default() -> Self {
         DuchessBuildRs {
             configuration: Configuration::default(),
+            reflection: Reflection::default(),
+            package_reflections: Vec::new(),
             src_path: PathBuf::from("."),
             in_cargo: std::env::var("CARGO").is_ok() && std::env::var("OUT_DIR").is_ok(),
             temporary_dir: None,
@@ -64,6 +82,25 @@ impl DuchessBuildRs {
         self
     }
 
+    /// Configure how Java classes are reflected.
+    /// The default is [`Reflection::Javap`].
+    pub fn with_reflection(mut self, reflection: Reflection) -> Self {
+        self.reflection = reflection;
+        self
+    }
+
+    /// Configure how the classes of `package` (e.g. `java.util`) and its subpackages are reflected,
+    /// overriding [`with_reflection`](Self::with_reflection) for them.
+    /// When several packages match a class, the most specific one wins.
+    pub fn with_package_reflection(
+        mut self,
+        package: impl Into<String>,
+        reflection: Reflection,
+    ) -> Self {
+        self.package_reflections.push((package.into(), reflection));
+        self
+    }
+
     /// Configure the path where Rust sources are found.
     /// The default is `.`.
     /// We will automatically search all subdirectories for `.rs` files.
@@ -100,18 +137,42 @@ impl DuchessBuildRs {
             self.src_path,
             files::rs_files(&self.src_path).count()
         );
-        let mut reflector = JavapReflector::new(&self.configuration);
+        let out_dir = std::env::var("OUT_DIR").unwrap();
+        let mut reflector = PackageReflector::new(
+            &self.configuration,
+            self.reflection,
+            &self.package_reflections,
+        );
+        self.process_files(compiler, &mut reflector)?;
+        log!("dumping {} classes to {out_dir}", reflector.len());
+        reflector.dump_to(Path::new(&out_dir))?;
+        println!("cargo::rustc-env=DUCHESS_OUT_DIR={}", out_dir);
+        if let Some(classpath) = self.configuration.classpath() {
+            println!("cargo::rustc-env=CLASSPATH={}", classpath);
+        } else {
+            println!("cargo::rustc-env=CLASSPATH={}", out_dir);
+        }
+        let elapsed = start_time.elapsed();
+        log!("duchess build-rs complete in {:?}", elapsed);
+        Ok(())
+    }
+
+    fn process_files(
+        &self,
+        compiler: &JavaCompiler,
+        reflector: &mut dyn Reflect,
+    ) -> anyhow::Result<()> {
         for rs_file in files::rs_files(&self.src_path) {
             let rs_file = rs_file?;
             let mut watch_file = false;
 
             eprintln!("looking for java macros in {:?}", rs_file.path);
-            watch_file |= java_package_macro::process_file(&rs_file, &mut reflector)?;
-            watch_file |= derive_java::process_file(&rs_file, &mut reflector)?;
+            watch_file |= java_package_macro::process_file(&rs_file, reflector)?;
+            watch_file |= derive_java::process_file(&rs_file, reflector)?;
 
             for capture in re::impl_java_interface().captures_iter(&rs_file.contents) {
                 let std::ops::Range { start, end: _ } = capture.get(0).unwrap().range();
-                impl_java_trait::process_impl(compiler, &rs_file, start)
+                impl_java_trait::process_impl(compiler, reflector, &rs_file, start)
                     .with_context(|| "failed to parse impl")?;
                 watch_file = true;
             }
@@ -120,17 +181,81 @@ impl DuchessBuildRs {
                 println!("cargo:rerun-if-changed={}", rs_file.path.display());
             }
         }
-        let out_dir = std::env::var("OUT_DIR").unwrap();
-        log!("dumping {} classes to {out_dir}", reflector.len());
-        reflector.dump_to(Path::new(&out_dir))?;
-        println!("cargo::rustc-env=DUCHESS_OUT_DIR={}", out_dir);
-        if let Some(classpath) = self.configuration.classpath() {
-            println!("cargo::rustc-env=CLASSPATH={}", classpath);
-        } else {
-            println!("cargo::rustc-env=CLASSPATH={}", out_dir);
-        }
-        let elapsed = start_time.elapsed();
-        log!("duchess build-rs complete in {:?}", elapsed);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{path::Path, process::Command};
+
+    use duchess_reflect::reflect::{BytecodeReflector, JavapReflector};
+    use walkdir::WalkDir;
+
+    use crate::{java_compiler::JavaCompiler, Configuration, DuchessBuildRs};
+
+    const TEST_CRATE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../test-crates/duchess-java-tests"
+    );
+
+    /// Reads a dumped reflection cache, with the flags of member classes removed
+    /// since `javap` does not show them.
+    fn reflection_cache(out_dir: &Path) -> serde_json::Value {
+        let json = std::fs::read(out_dir.join("reflection-cache.json")).unwrap();
+        let mut classes: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        for (name, class) in classes.as_object_mut().unwrap() {
+            if name.contains('$') {
+                class.as_object_mut().unwrap().remove("flags");
+            }
+        }
+        classes
+    }
+
+    /// Reflects on the classes used by the `rust-to-java` tests of the test crate with both
+    /// reflectors, which must agree.
+    #[test]
+    fn javap_reflection_matches_bytecode_reflection() {
+        let tmp = tempfile::tempdir().unwrap();
+        let classes = tmp.path().join("classes");
+        let java_dir = Path::new(TEST_CRATE).join("java");
+        let sources = WalkDir::new(&java_dir)
+            .into_iter()
+            .map(|entry| entry.unwrap().into_path())
+            .filter(|path| path.extension().is_some_and(|e| e == "java"));
+        let configuration = Configuration::default();
+        let status = Command::new(configuration.bin_path("javac"))
+            .arg("-d")
+            .arg(&classes)
+            .arg("-sourcepath")
+            .arg(&java_dir)
+            .args(sources)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let configuration = configuration.with_classpath(classes.display());
+        let build = DuchessBuildRs::new()
+            .with_configuration(configuration.clone())
+            .with_src_path(Path::new(TEST_CRATE).join("tests/rust-to-java"));
+        let compiler = JavaCompiler::new(&configuration, Some(&tmp.path().join("build"))).unwrap();
+
+        let javap_dir = tmp.path().join("javap");
+        let mut javap = JavapReflector::new(&configuration);
+        build.process_files(&compiler, &mut javap).unwrap();
+        std::fs::create_dir(&javap_dir).unwrap();
+        javap.dump_to(&javap_dir).unwrap();
+
+        let bytecode_dir = tmp.path().join("bytecode");
+        let mut bytecode = BytecodeReflector::new(&configuration);
+        build.process_files(&compiler, &mut bytecode).unwrap();
+        std::fs::create_dir(&bytecode_dir).unwrap();
+        bytecode.dump_to(&bytecode_dir).unwrap();
+
+        assert!(!javap.is_empty());
+        assert_eq!(
+            reflection_cache(&bytecode_dir),
+            reflection_cache(&javap_dir)
+        );
+    }
+}
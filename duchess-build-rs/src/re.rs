@@ -14,7 +14,10 @@ declare_regex!(impl_java_interface() = r"#\[duchess::impl_java_interface\]");
 
 declare_regex!(java_package() = r"(?m)^\s*(duchess|duchess_macro)::java_package! *\{");
 
-declare_regex!(java_derive() = r"#\[java\(([\w.]+)(?:::\w+)?\)\]");
+// Class names in derives are qualified, which distinguishes them from field attributes like `#[java(skip)]`
+declare_regex!(java_derive() = r"#\[java\((\w+(?:\.\w+)+)(?:::\w+)?\)\]");
+
+declare_regex!(java_derive_builder() = r"#\[java\(builder\s*=\s*(\w+(?:\.\w+)+)::\w+");
 
 #[cfg(test)]
 mod test {
@@ -37,9 +40,20 @@ mod test {
         assert!(super::java_package().is_match(java_file));
     }
 
+    #[test]
+    fn test_java_derive_builder() {
+        let captures = super::java_derive_builder()
+            .captures("#[java(builder = log.Event::builder, build = build)]")
+            .unwrap();
+        assert_eq!(&captures[1], "log.Event");
+    }
+
     #[test]
     fn test_java_derive() {
         assert!(super::java_derive().is_match("#[java(java.lang.Long::decode)]"));
         assert!(super::java_derive().is_match("#[java(java.lang.Throwable)]"));
+        assert!(!super::java_derive().is_match("#[java(skip)]"));
+        assert!(!super::java_derive().is_match("#[java(builder = log.Event::builder)]"));
+        assert!(!super::java_derive().is_match("#[java(rename_all = \"camelCase\")]"));
     }
 }
@@ -0,0 +1,92 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use duchess_reflect::{
+    class_info::DotId,
+    config::Configuration,
+    reflect::{dump_classes, BytecodeReflector, JavapClassInfo, JavapReflector, Reflect},
+};
+use proc_macro2::Span;
+
+use crate::Reflection;
+
+/// Reflects each class with the [`Reflection`] configured for its package,
+/// falling back to the default one.
+pub(crate) struct PackageReflector<'b> {
+    reflection: Reflection,
+    package_reflections: &'b [(String, Reflection)],
+    javap: JavapReflector,
+    bytecode: BytecodeReflector,
+    classes: BTreeMap<DotId, Arc<JavapClassInfo>>,
+}
+
+impl<'b> PackageReflector<'b> {
+    pub(crate) fn new(
+        configuration: &Configuration,
+        reflection: Reflection,
+        package_reflections: &'b [(String, Reflection)],
+    ) -> Self {
+        Self {
+            reflection,
+            package_reflections,
+            javap: JavapReflector::new(configuration),
+            bytecode: BytecodeReflector::new(configuration),
+            classes: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub(crate) fn dump_to(&self, out_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        dump_classes(&self.classes, out_dir)
+    }
+
+    /// The reflection for the most specific package configured to contain `dot_id`.
+    fn reflection_for(&self, dot_id: &DotId) -> Reflection {
+        let (package, _) = dot_id.split();
+        self.package_reflections
+            .iter()
+            .filter(|(name, _)| {
+                let name: Vec<&str> = name.split('.').collect();
+                name.len() <= package.len() && name.iter().zip(package).all(|(n, id)| **n == id[..])
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map_or(self.reflection, |&(_, reflection)| reflection)
+    }
+}
+
+impl Reflect for PackageReflector<'_> {
+    fn reflect(&mut self, dot_id: &DotId, span: Span) -> syn::Result<Arc<JavapClassInfo>> {
+        let info = match self.reflection_for(dot_id) {
+            Reflection::Javap => self.javap.reflect_and_cache(dot_id, span)?,
+            Reflection::Bytecode => self.bytecode.reflect_and_cache(dot_id, span)?,
+        };
+        self.classes.insert(dot_id.clone(), Arc::clone(&info));
+        Ok(info)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use duchess_reflect::{class_info::DotId, config::Configuration};
+
+    use super::PackageReflector;
+    use crate::Reflection;
+
+    #[test]
+    fn most_specific_package_wins() {
+        let packages = [
+            ("java".to_string(), Reflection::Javap),
+            ("java.util".to_string(), Reflection::Bytecode),
+        ];
+        let reflector =
+            PackageReflector::new(&Configuration::default(), Reflection::Bytecode, &packages);
+        let reflection = |name: &str| reflector.reflection_for(&DotId::parse(name));
+
+        assert_eq!(reflection("java.lang.Object"), Reflection::Javap);
+        assert_eq!(reflection("java.util.ArrayList"), Reflection::Bytecode);
+        assert_eq!(reflection("javax.net.SocketFactory"), Reflection::Bytecode);
+        assert_eq!(reflection("log.Logger"), Reflection::Bytecode);
+    }
+}
@@ -235,14 +235,24 @@ impl Ident {
 
 impl Parse for Ident {
     fn parse(p: &mut Parser) -> syn::Result<Option<Self>> {
-        let Some(text) = p.eat_ident() else {
+        let Some(mut text) = p.eat_ident() else {
             return Ok(None);
         };
+        let mut span = p.last_span().unwrap();
 
-        Ok(Some(Ident {
-            text,
-            span: p.last_span().unwrap(),
-        }))
+        // Nested classes are named like `Map$Entry`, which Rust tokenizes as `Map`, `$`, `Entry`
+        while let Some(_) = p.eat_punct('$') {
+            let Some(next) = p.eat_ident() else {
+                return Err(syn::Error::new(
+                    p.last_span().unwrap(),
+                    format!("expected identifier after `$`"),
+                ));
+            };
+            text = format!("{text}${next}");
+            span = span.join(p.last_span().unwrap()).unwrap_or(span);
+        }
+
+        Ok(Some(Ident { text, span }))
     }
 
     fn description() -> String {
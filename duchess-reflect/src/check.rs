@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 
 use crate::{
-    class_info::{ClassInfo, ClassRef, Constructor, Flags, Method, RefType, RootMap, Type},
+    class_info::{
+        ClassInfo, ClassKind, ClassRef, Constructor, Field, Flags, Method, RefType, RootMap, Type,
+    },
     reflect::{JavapClassInfo, PrecomputedReflector},
 };
 
@@ -40,7 +42,7 @@ impl ClassInfo {
             ));
         };
 
-        if self.kind != info.kind {
+        if !self.kind.matches_reflected(info.kind) {
             push_error_message(format!(
                 "class `{}` should be type `{}`",
                 self.name,
@@ -116,6 +118,39 @@ impl ClassInfo {
             &mut push_error_message,
         );
 
+        // Records are declared by their components, so any instance fields
+        // must match the reflected components exactly.
+        if info.kind == ClassKind::Record {
+            let reflected = info.to_class_info(self.span);
+            let components: Vec<&Field> = reflected.record_components().collect();
+            let declared: Vec<&Field> = self.fields.iter().filter(|f| !f.flags.is_static).collect();
+
+            for f in &declared {
+                match components.iter().find(|c| c.name == f.name) {
+                    Some(c) if c.ty != f.ty => push_error_message(format!(
+                        "record component `{}` has type `{}`, not `{}`",
+                        f.name, c.ty, f.ty,
+                    )),
+                    Some(_) => {}
+                    None => push_error_message(format!(
+                        "no component named `{}` in the reflected record",
+                        f.name,
+                    )),
+                }
+            }
+
+            if !declared.is_empty() {
+                for c in &components {
+                    if !declared.iter().any(|f| f.name == c.name) {
+                        push_error_message(format!(
+                            "record component `{}` is not declared",
+                            c.name,
+                        ));
+                    }
+                }
+            }
+        }
+
         let refl = JavapClassInfo::from(self.clone());
 
         for c in &self.constructors {
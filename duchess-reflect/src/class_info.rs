@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     parse::{Parse, TextAccum},
-    reflect::JavapClassInfo,
+    reflect::{ConstructorIndex, JavapClassInfo},
     upcasts::Upcasts,
 };
 
@@ -219,6 +219,12 @@ impl ClassInfo {
         javap::parse_class_info(span, &text)
     }
 
+    /// Parses the contents of a `.class` file, yielding the same result as [`ClassInfo::parse`]
+    /// on the output of `javap -p`.
+    pub fn parse_class_file(bytes: &[u8], span: Span) -> anyhow::Result<ClassInfo> {
+        bytecode::parse_class_file(bytes, span)
+    }
+
     /// Indicates whether a member with the given privacy level should be reflected in Rust.
     /// We always mirror things declared as public.
     /// In classes, the default privacy indicates "package level" visibility and we do not mirror.
@@ -229,10 +235,52 @@ impl ClassInfo {
 
             (Privacy::Protected, _)
             | (Privacy::Private, _)
-            | (Privacy::Default, ClassKind::Class) => false,
+            | (Privacy::Default, ClassKind::Class | ClassKind::Enum | ClassKind::Record) => false,
         }
     }
 
+    /// Returns the constants of an enum, in declaration order.
+    ///
+    /// `javap` does not distinguish enum constants from other fields, so we consider
+    /// every public static final field whose type is the enum itself to be a constant.
+    pub fn enum_constants(&self) -> impl Iterator<Item = &Field> {
+        let this_ty = Type::Ref(RefType::Class(ClassRef {
+            name: self.name.clone(),
+            generics: vec![],
+        }));
+        self.fields.iter().filter(move |f| {
+            self.kind == ClassKind::Enum
+                && f.flags.privacy == Privacy::Public
+                && f.flags.is_static
+                && f.flags.is_final
+                && f.ty == this_ty
+        })
+    }
+
+    /// Returns the components of a record, in declaration order.
+    ///
+    /// Records cannot declare instance fields other than their components,
+    /// so these are the (private final) instance fields of the record.
+    pub fn record_components(&self) -> impl Iterator<Item = &Field> {
+        self.fields
+            .iter()
+            .filter(move |f| self.kind == ClassKind::Record && !f.flags.is_static)
+    }
+
+    /// Returns the index of the canonical constructor of a record,
+    /// whose arguments are the record components.
+    pub fn canonical_constructor(&self) -> Option<ConstructorIndex> {
+        let component_tys: Vec<&Type> = self.record_components().map(|f| &f.ty).collect();
+        self.constructors.iter().position(|c| {
+            self.kind == ClassKind::Record
+                && c.argument_tys.len() == component_tys.len()
+                && c.argument_tys
+                    .iter()
+                    .zip(&component_tys)
+                    .all(|(a, c)| a == *c)
+        })
+    }
+
     pub fn generics_scope(&self) -> GenericsScope<'_> {
         GenericsScope::Generics(&self.generics, &GenericsScope::Empty)
     }
@@ -241,7 +289,8 @@ impl ClassInfo {
 #[derive(Eq, Ord, PartialEq, PartialOrd, Clone, Debug, Deserialize, Serialize)]
 pub struct Generic {
     pub id: Id,
-    pub extends: Vec<ClassRef>,
+    /// Either classes and interfaces, or a single other type parameter (e.g., `T extends U`).
+    pub extends: Vec<RefType>,
 }
 
 impl Generic {
@@ -267,6 +316,36 @@ impl std::fmt::Display for Generic {
 pub enum ClassKind {
     Class,
     Interface,
+    Enum,
+    Record,
+}
+
+impl ClassKind {
+    /// Returns the kind of a class declared with the keyword `kind` that extends `extends`.
+    ///
+    /// `javap` prints enums as `final class Foo extends java.lang.Enum<Foo>`
+    /// and records as `final class Foo extends java.lang.Record`,
+    /// so we recognize them by their superclass (which no other class can extend).
+    pub fn from_declaration(kind: ClassKind, extends: &[ClassRef]) -> ClassKind {
+        if kind != ClassKind::Class {
+            return kind;
+        }
+        if extends.iter().any(|c| c.name == DotId::java_lang_enum()) {
+            ClassKind::Enum
+        } else if extends.iter().any(|c| c.name == DotId::java_lang_record()) {
+            ClassKind::Record
+        } else {
+            kind
+        }
+    }
+
+    /// True if a class declared as `self` can be the class `reflected`.
+    /// Since `javap` shows enums and records as classes, a `class` declaration may be either.
+    pub fn matches_reflected(self, reflected: ClassKind) -> bool {
+        self == reflected
+            || (self == ClassKind::Class
+                && matches!(reflected, ClassKind::Enum | ClassKind::Record))
+    }
 }
 
 #[derive(Eq, Ord, PartialEq, PartialOrd, Copy, Clone, Debug, Serialize, Deserialize)]
@@ -361,6 +440,73 @@ impl Constructor {
     }
 }
 
+/// The generic signature of a constructor of an inner class omits the instance of the
+/// enclosing class that is passed as the first argument, but its descriptor includes it.
+/// Returns the type of that argument if it is missing from `argument_tys`.
+pub(crate) fn missing_outer_instance(
+    class_name: &DotId,
+    descriptor: &str,
+    argument_tys: &[Type],
+) -> Option<Type> {
+    let outer = class_name.outer_class()?;
+    let arguments = descriptor_arguments(descriptor);
+    if arguments.len() != argument_tys.len() + 1
+        || arguments[0] != format!("L{};", outer.to_jni_name())
+    {
+        return None;
+    }
+    Some(Type::Ref(RefType::Class(ClassRef {
+        name: outer,
+        generics: vec![],
+    })))
+}
+
+/// Splits the arguments of a method descriptor like `(I[Ljava/lang/String;)V`
+/// into `["I", "[Ljava/lang/String;"]`.
+fn descriptor_arguments(descriptor: &str) -> Vec<&str> {
+    let Some(arguments) = descriptor
+        .strip_prefix('(')
+        .and_then(|d| d.split(')').next())
+    else {
+        return vec![];
+    };
+    let mut result = vec![];
+    let mut rest = arguments;
+    while !rest.is_empty() {
+        let dims = rest.len() - rest.trim_start_matches('[').len();
+        let len = match rest[dims..].chars().next() {
+            Some('L') => rest.find(';').map_or(rest.len(), |i| i + 1),
+            _ => dims + 1,
+        };
+        let len = len.min(rest.len());
+        result.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    result
+}
+
+/// Erases a thrown type to a class: a type parameter (e.g., `X` in `<X extends Throwable> ... throws X`)
+/// is replaced by the erasure of its first bound, falling back to `java.lang.Throwable`.
+fn erase_throws(ty: &RefType, method_generics: &[Generic], class_generics: &[Generic]) -> ClassRef {
+    match ty {
+        RefType::Class(c) => c.clone(),
+        RefType::TypeParameter(id) => method_generics
+            .iter()
+            .chain(class_generics)
+            .find(|g| g.id == *id)
+            .and_then(|g| g.extends.first())
+            .map(|bound| erase_throws(bound, method_generics, class_generics))
+            .unwrap_or_else(|| ClassRef {
+                name: DotId::throwable(),
+                generics: vec![],
+            }),
+        _ => ClassRef {
+            name: DotId::throwable(),
+            generics: vec![],
+        },
+    }
+}
+
 #[derive(Eq, Ord, PartialEq, PartialOrd, Clone, Debug, Serialize, Deserialize)]
 pub struct Field {
     pub flags: Flags,
@@ -572,7 +718,7 @@ impl NonRepeatingType {
                 RefType::TypeParameter(id) => {
                     let generic = ctx.find(id).expect("generic did not exist.");
                     match generic.extends.get(0) {
-                        Some(c) => format!("L{};", c.name.to_jni_name()),
+                        Some(bound) => NonRepeatingType::Ref(bound.clone()).descriptor(ctx),
                         _ => format!("Ljava/lang/Object;"),
                     }
                 }
@@ -714,6 +860,21 @@ impl Id {
             data: self.data.to_snake_case(),
         }
     }
+
+    /// Splits the name of a nested class (e.g., `Map$Entry`) into the names of
+    /// its enclosing classes (e.g., `[Map]`) and its own simple name (e.g., `Entry`).
+    /// Other names (including those with a leading or trailing `$`) are not split.
+    pub fn split_nested(&self) -> (Vec<Id>, Id) {
+        let names: Vec<&str> = self.data.split('$').collect();
+        if names.iter().any(|n| n.is_empty()) {
+            return (vec![], self.clone());
+        }
+        let (name, outer) = names.split_last().unwrap();
+        (
+            outer.iter().map(|&n| Id::from(n)).collect(),
+            Id::from(*name),
+        )
+    }
 }
 
 impl std::fmt::Display for Id {
@@ -789,6 +950,14 @@ impl DotId {
         Self::parse("java.lang.Object")
     }
 
+    pub fn java_lang_enum() -> Self {
+        Self::parse("java.lang.Enum")
+    }
+
+    pub fn java_lang_record() -> Self {
+        Self::parse("java.lang.Record")
+    }
+
     pub fn exception() -> Self {
         Self::parse("java.lang.Exception")
     }
@@ -818,15 +987,32 @@ impl DotId {
     }
 
     /// returns the class name in JNI format with _'s escaped with _1
+    /// (and the `$` of nested classes escaped with _00024)
     /// https://docs.oracle.com/en/java/javase/17/docs/specs/jni/design.html
     pub fn to_jni_class_name(&self) -> Id {
-        self.split().1.data.replace("_", "_1").into()
+        self.split()
+            .1
+            .data
+            .replace("_", "_1")
+            .replace("$", "_00024")
+            .into()
     }
 
     pub fn class_name(&self) -> &Id {
         self.split().1
     }
 
+    /// Returns the class enclosing a nested class (e.g., `java.util.Map` for `java.util.Map$Entry`).
+    pub fn outer_class(&self) -> Option<DotId> {
+        let (package, class) = self.split();
+        let (outer, _) = class.split_nested();
+        if outer.is_empty() {
+            return None;
+        }
+        let outer: Vec<&str> = outer.iter().map(|id| &id[..]).collect();
+        Some(DotId::new(package, &Id::from(outer.join("$"))))
+    }
+
     /// returns the package in JNI format with _'s escaped with _1
     /// https://docs.oracle.com/en/java/javase/17/docs/specs/jni/design.html
     pub fn to_jni_package(&self) -> String {
@@ -863,12 +1049,20 @@ impl DotId {
         self.with_sep("$")
     }
 
-    /// Returns a token stream like `java::lang::Object`
+    /// Returns a token stream like `java::lang::Object`.
+    ///
+    /// Nested classes are found in a module named after their enclosing class,
+    /// so `java.util.Map$Entry` becomes `java::util::map::Entry`.
     pub fn to_module_name(&self, span: Span) -> TokenStream {
-        let (package_names, struct_name) = self.split();
+        let (package_names, class_name) = self.split();
+        let (outer_names, struct_name) = class_name.split_nested();
         let struct_ident = struct_name.to_ident(span);
         let package_idents: Vec<Ident> = package_names.iter().map(|n| n.to_ident(span)).collect();
-        quote_spanned!(span => #(#package_idents ::)* #struct_ident)
+        let outer_idents: Vec<Ident> = outer_names
+            .iter()
+            .map(|n| n.to_snake_case().to_ident(span))
+            .collect();
+        quote_spanned!(span => #(#package_idents ::)* #(#outer_idents ::)* #struct_ident)
     }
 }
 
@@ -891,5 +1085,6 @@ impl std::fmt::Display for DotId {
     }
 }
 
+mod bytecode;
 mod from_syn;
 mod javap;
@@ -0,0 +1,752 @@
+//! Parses the [class file format][cf] into a [`ClassInfo`].
+//!
+//! The result is meant to be identical to what we get by parsing the output of `javap -p`
+//! (see `javap_parser.lalrpop`), so the quirks of `javap` are reproduced here: for example,
+//! an `extends java.lang.Object` bound is omitted. The one deliberate difference is that member
+//! classes take their access flags from the `InnerClasses` attribute, which records whether they
+//! are `static` and their declared privacy, while `javap` only shows those of the class header.
+//!
+//! [cf]: https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html
+
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use proc_macro2::Span;
+
+use super::{
+    erase_throws, missing_outer_instance, ClassInfo, ClassKind, ClassRef, Constructor, DotId,
+    Field, Flags, Generic, Id, Method, Privacy, RefType, ScalarType, Type,
+};
+
+const ACC_PUBLIC: u16 = 0x0001;
+const ACC_PRIVATE: u16 = 0x0002;
+const ACC_PROTECTED: u16 = 0x0004;
+const ACC_STATIC: u16 = 0x0008;
+const ACC_FINAL: u16 = 0x0010;
+const ACC_SYNCHRONIZED: u16 = 0x0020;
+const ACC_VOLATILE: u16 = 0x0040;
+const ACC_TRANSIENT: u16 = 0x0080;
+const ACC_VARARGS: u16 = 0x0080;
+const ACC_NATIVE: u16 = 0x0100;
+const ACC_INTERFACE: u16 = 0x0200;
+const ACC_ABSTRACT: u16 = 0x0400;
+
+pub(super) fn parse_class_file(bytes: &[u8], span: Span) -> anyhow::Result<ClassInfo> {
+    let mut r = Reader { bytes, offset: 0 };
+
+    if r.u4()? != 0xCAFEBABE {
+        bail!("not a class file (bad magic number)");
+    }
+    let _minor_version = r.u2()?;
+    let _major_version = r.u2()?;
+    let pool = ConstantPool::parse(&mut r)?;
+
+    let access_flags = r.u2()?;
+    let this_class = pool.class_name(r.u2()?)?;
+    let super_class = match r.u2()? {
+        0 => None,
+        index => Some(pool.class_name(index)?),
+    };
+    let mut interfaces = vec![];
+    for _ in 0..r.u2()? {
+        interfaces.push(pool.class_name(r.u2()?)?);
+    }
+    let fields = Member::parse_all(&mut r, &pool)?;
+    let methods = Member::parse_all(&mut r, &pool)?;
+    let attributes = Attributes::parse(&mut r, &pool)?;
+
+    let name = class_dot_id(this_class);
+
+    // The class header of a member class has no `static`, `private` or `protected` flags;
+    // its declared flags are recorded in the `InnerClasses` attribute instead.
+    let member_class = attributes
+        .inner_classes
+        .iter()
+        .find(|c| c.inner == this_class && c.outer.is_some());
+    let access_flags = member_class.map_or(access_flags, |c| c.access_flags);
+    let is_interface = access_flags & ACC_INTERFACE != 0;
+    let is_inner = member_class.is_some() && access_flags & ACC_STATIC == 0;
+
+    let (generics, superclass, interfaces) = match attributes.signature {
+        Some(signature) => SignatureParser::new(signature)
+            .class_signature()
+            .with_context(|| format!("invalid signature for `{name}`: `{signature}`"))?,
+        None => (
+            vec![],
+            super_class.map(class_ref),
+            interfaces.into_iter().map(class_ref).collect(),
+        ),
+    };
+
+    // Like `javap`, we list the superinterfaces of an interface as `extends`
+    // and never list `java.lang.Object` as a superclass.
+    let (extends, implements) = if is_interface {
+        (interfaces, vec![])
+    } else {
+        (
+            superclass.into_iter().filter(|c| !is_object(c)).collect(),
+            interfaces,
+        )
+    };
+
+    let flags = Flags {
+        is_final: access_flags & ACC_FINAL != 0,
+        is_abstract: !is_interface && access_flags & ACC_ABSTRACT != 0,
+        is_static: member_class.is_some() && !is_inner,
+        ..Flags::new(privacy(access_flags))
+    };
+
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let ty = SignatureParser::new(field.signature.unwrap_or(field.descriptor))
+                .field_type()
+                .with_context(|| format!("invalid type for field `{name}.{}`", field.name))?;
+            Ok(Field {
+                flags: Flags {
+                    is_static: field.access_flags & ACC_STATIC != 0,
+                    is_final: field.access_flags & ACC_FINAL != 0,
+                    is_volatile: field.access_flags & ACC_VOLATILE != 0,
+                    is_transient: field.access_flags & ACC_TRANSIENT != 0,
+                    ..Flags::new(privacy(field.access_flags))
+                },
+                name: Id::from(field.name),
+                ty,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut constructors = vec![];
+    let mut class_methods = vec![];
+    for method in &methods {
+        if method.name == "<clinit>" {
+            continue;
+        }
+
+        let signature = method.signature.unwrap_or(method.descriptor);
+        let mut ty = SignatureParser::new(signature)
+            .method_signature()
+            .with_context(|| {
+                format!(
+                    "invalid signature for method `{name}.{}`: `{signature}`",
+                    method.name
+                )
+            })?;
+
+        if method.name == "<init>" && is_inner && method.signature.is_some() {
+            if let Some(outer) = missing_outer_instance(&name, method.descriptor, &ty.argument_tys)
+            {
+                ty.argument_tys.insert(0, outer);
+            }
+        }
+
+        if method.access_flags & ACC_VARARGS != 0 {
+            if let Some(last) = ty.argument_tys.pop() {
+                ty.argument_tys.push(match last {
+                    Type::Ref(RefType::Array(element)) => Type::Repeat(element),
+                    ty => ty,
+                });
+            }
+        }
+
+        // The signature only lists thrown types if one of them is a type parameter.
+        let throws = if ty.throws.is_empty() {
+            method.exceptions.iter().copied().map(class_ref).collect()
+        } else {
+            ty.throws
+                .iter()
+                .map(|t| erase_throws(t, &ty.generics, &generics))
+                .collect()
+        };
+
+        let is_abstract = method.access_flags & ACC_ABSTRACT != 0;
+        let is_static = method.access_flags & ACC_STATIC != 0;
+        let is_private = method.access_flags & ACC_PRIVATE != 0;
+        let flags = Flags {
+            is_final: method.access_flags & ACC_FINAL != 0,
+            is_synchronized: method.access_flags & ACC_SYNCHRONIZED != 0,
+            is_native: method.access_flags & ACC_NATIVE != 0,
+            is_abstract,
+            is_static,
+            is_default: is_interface && !is_abstract && !is_static && !is_private,
+            ..Flags::new(privacy(method.access_flags))
+        };
+
+        if method.name == "<init>" {
+            constructors.push(Constructor {
+                flags,
+                generics: ty.generics,
+                argument_tys: ty.argument_tys,
+                throws,
+            });
+        } else {
+            class_methods.push(Method {
+                flags,
+                name: Id::from(method.name),
+                generics: ty.generics,
+                argument_tys: ty.argument_tys,
+                return_ty: ty.return_ty,
+                throws,
+            });
+        }
+    }
+
+    Ok(ClassInfo {
+        span,
+        flags,
+        name,
+        kind: if is_interface {
+            ClassKind::Interface
+        } else {
+            ClassKind::from_declaration(ClassKind::Class, &extends)
+        },
+        generics,
+        extends,
+        implements,
+        constructors,
+        fields,
+        methods: class_methods,
+    })
+}
+
+fn privacy(access_flags: u16) -> Privacy {
+    if access_flags & ACC_PUBLIC != 0 {
+        Privacy::Public
+    } else if access_flags & ACC_PROTECTED != 0 {
+        Privacy::Protected
+    } else if access_flags & ACC_PRIVATE != 0 {
+        Privacy::Private
+    } else {
+        Privacy::Default
+    }
+}
+
+/// Converts an internal name like `java/util/Map$Entry` into a [`DotId`].
+fn class_dot_id(internal_name: &str) -> DotId {
+    internal_name.split('/').map(Id::from).collect()
+}
+
+fn class_ref(internal_name: &str) -> ClassRef {
+    ClassRef {
+        name: class_dot_id(internal_name),
+        generics: vec![],
+    }
+}
+
+fn is_object(c: &ClassRef) -> bool {
+    c.generics.is_empty() && c.name == DotId::object()
+}
+
+struct Reader<'b> {
+    bytes: &'b [u8],
+    offset: usize,
+}
+
+impl<'b> Reader<'b> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&'b [u8]> {
+        let Some(bytes) = self.bytes.get(self.offset..self.offset + len) else {
+            bail!("unexpected end of class file at offset {}", self.offset)
+        };
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u1(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u2(&mut self) -> anyhow::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u4(&mut self) -> anyhow::Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+enum Constant {
+    Utf8(String),
+    Class(u16),
+    /// Constants we never need to look at, as well as the unusable entries
+    /// at index 0 and after each `long` or `double`.
+    Other,
+}
+
+struct ConstantPool {
+    constants: Vec<Constant>,
+}
+
+impl ConstantPool {
+    fn parse(r: &mut Reader<'_>) -> anyhow::Result<Self> {
+        let count = r.u2()? as usize;
+        let mut constants = Vec::with_capacity(count);
+        constants.push(Constant::Other);
+        while constants.len() < count {
+            let tag = r.u1()?;
+            let constant = match tag {
+                // Utf8
+                1 => {
+                    let len = r.u2()? as usize;
+                    Constant::Utf8(decode_modified_utf8(r.take(len)?)?)
+                }
+                // Class
+                7 => Constant::Class(r.u2()?),
+                // String, MethodType, Module, Package
+                8 | 16 | 19 | 20 => {
+                    r.take(2)?;
+                    Constant::Other
+                }
+                // MethodHandle
+                15 => {
+                    r.take(3)?;
+                    Constant::Other
+                }
+                // Integer, Float, Fieldref, Methodref, InterfaceMethodref, NameAndType, Dynamic, InvokeDynamic
+                3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => {
+                    r.take(4)?;
+                    Constant::Other
+                }
+                // Long, Double (which take up two entries)
+                5 | 6 => {
+                    r.take(8)?;
+                    constants.push(Constant::Other);
+                    Constant::Other
+                }
+                _ => bail!("unknown constant pool tag {tag} at offset {}", r.offset - 1),
+            };
+            constants.push(constant);
+        }
+        Ok(ConstantPool { constants })
+    }
+
+    fn utf8(&self, index: u16) -> anyhow::Result<&str> {
+        match self.constants.get(index as usize) {
+            Some(Constant::Utf8(s)) => Ok(s),
+            _ => bail!("constant pool entry {index} is not a utf8 constant"),
+        }
+    }
+
+    fn class_name(&self, index: u16) -> anyhow::Result<&str> {
+        match self.constants.get(index as usize) {
+            Some(Constant::Class(name_index)) => self.utf8(*name_index),
+            _ => bail!("constant pool entry {index} is not a class constant"),
+        }
+    }
+}
+
+/// Decodes the ["modified UTF-8"][mutf8] used by class files.
+///
+/// [mutf8]: https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4.7
+fn decode_modified_utf8(bytes: &[u8]) -> anyhow::Result<String> {
+    // Modified UTF-8 only differs from UTF-8 in ways that make it invalid UTF-8
+    // (encoded nul bytes and surrogate pairs), so the common case is just a validity check.
+    if let Ok(s) = std::str::from_utf8(bytes) {
+        return Ok(s.to_string());
+    }
+
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter().copied();
+    fn continuation(iter: &mut impl Iterator<Item = u8>) -> anyhow::Result<u16> {
+        match iter.next() {
+            Some(b) if b & 0xC0 == 0x80 => Ok(u16::from(b & 0x3F)),
+            _ => bail!("invalid modified utf-8 string"),
+        }
+    }
+    while let Some(b) = iter.next() {
+        let unit = if b & 0x80 == 0 {
+            u16::from(b)
+        } else if b & 0xE0 == 0xC0 {
+            (u16::from(b & 0x1F) << 6) | continuation(&mut iter)?
+        } else if b & 0xF0 == 0xE0 {
+            let high = continuation(&mut iter)?;
+            let low = continuation(&mut iter)?;
+            (u16::from(b & 0x0F) << 12) | (high << 6) | low
+        } else {
+            bail!("invalid modified utf-8 string");
+        };
+        units.push(unit);
+    }
+    Ok(String::from_utf16(&units)?)
+}
+
+/// A field or method.
+struct Member<'p> {
+    access_flags: u16,
+    name: &'p str,
+    descriptor: &'p str,
+    signature: Option<&'p str>,
+    exceptions: Vec<&'p str>,
+}
+
+impl<'p> Member<'p> {
+    fn parse_all(r: &mut Reader<'_>, pool: &'p ConstantPool) -> anyhow::Result<Vec<Self>> {
+        let mut members = vec![];
+        for _ in 0..r.u2()? {
+            let access_flags = r.u2()?;
+            let name = pool.utf8(r.u2()?)?;
+            let descriptor = pool.utf8(r.u2()?)?;
+            let attributes = Attributes::parse(r, pool)?;
+            members.push(Member {
+                access_flags,
+                name,
+                descriptor,
+                signature: attributes.signature,
+                exceptions: attributes.exceptions,
+            });
+        }
+        Ok(members)
+    }
+}
+
+/// The attributes that are relevant to reflection; all others are skipped.
+struct Attributes<'p> {
+    signature: Option<&'p str>,
+    exceptions: Vec<&'p str>,
+    inner_classes: Vec<InnerClass<'p>>,
+}
+
+/// An entry of the `InnerClasses` attribute.
+struct InnerClass<'p> {
+    inner: &'p str,
+    /// `None` for local and anonymous classes
+    outer: Option<&'p str>,
+    access_flags: u16,
+}
+
+impl<'p> Attributes<'p> {
+    fn parse(r: &mut Reader<'_>, pool: &'p ConstantPool) -> anyhow::Result<Self> {
+        let mut attributes = Attributes {
+            signature: None,
+            exceptions: vec![],
+            inner_classes: vec![],
+        };
+        for _ in 0..r.u2()? {
+            let name = pool.utf8(r.u2()?)?;
+            let len = r.u4()? as usize;
+            let mut info = Reader {
+                bytes: r.take(len)?,
+                offset: 0,
+            };
+            match name {
+                "Signature" => attributes.signature = Some(pool.utf8(info.u2()?)?),
+                "Exceptions" => {
+                    for _ in 0..info.u2()? {
+                        attributes.exceptions.push(pool.class_name(info.u2()?)?);
+                    }
+                }
+                "InnerClasses" => {
+                    for _ in 0..info.u2()? {
+                        let inner = pool.class_name(info.u2()?)?;
+                        let outer = match info.u2()? {
+                            0 => None,
+                            index => Some(pool.class_name(index)?),
+                        };
+                        let _inner_name = info.u2()?;
+                        let access_flags = info.u2()?;
+                        attributes.inner_classes.push(InnerClass {
+                            inner,
+                            outer,
+                            access_flags,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+struct MethodType {
+    generics: Vec<Generic>,
+    argument_tys: Vec<Type>,
+    return_ty: Option<Type>,
+    throws: Vec<RefType>,
+}
+
+/// Parses [signatures][sig], as well as descriptors (which are signatures without any generics).
+///
+/// [sig]: https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1
+struct SignatureParser<'s> {
+    text: &'s str,
+    offset: usize,
+}
+
+impl<'s> SignatureParser<'s> {
+    fn new(text: &'s str) -> Self {
+        SignatureParser { text, offset: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.offset).copied()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.offset += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> anyhow::Result<()> {
+        if !self.eat(b) {
+            bail!("expected `{}` at offset {}", b as char, self.offset);
+        }
+        Ok(())
+    }
+
+    fn expect_end(&self) -> anyhow::Result<()> {
+        if self.offset != self.text.len() {
+            bail!("unexpected text at offset {}", self.offset);
+        }
+        Ok(())
+    }
+
+    fn identifier(&mut self) -> anyhow::Result<&'s str> {
+        let start = self.offset;
+        while let Some(b) = self.peek() {
+            if matches!(b, b'.' | b';' | b'[' | b'/' | b'<' | b'>' | b':') {
+                break;
+            }
+            self.offset += 1;
+        }
+        if start == self.offset {
+            bail!("expected identifier at offset {start}");
+        }
+        Ok(&self.text[start..self.offset])
+    }
+
+    /// `ClassSignature`, returning the generics, superclass, and superinterfaces.
+    fn class_signature(
+        mut self,
+    ) -> anyhow::Result<(Vec<Generic>, Option<ClassRef>, Vec<ClassRef>)> {
+        let generics = self.type_parameters()?;
+        self.expect(b'L')?;
+        let superclass = self.class_type()?;
+        let mut interfaces = vec![];
+        while self.eat(b'L') {
+            interfaces.push(self.class_type()?);
+        }
+        self.expect_end()?;
+        Ok((generics, Some(superclass), interfaces))
+    }
+
+    /// `FieldSignature` or field descriptor.
+    fn field_type(mut self) -> anyhow::Result<Type> {
+        let ty = self.java_type()?;
+        self.expect_end()?;
+        Ok(ty)
+    }
+
+    /// `MethodSignature` or method descriptor.
+    fn method_signature(mut self) -> anyhow::Result<MethodType> {
+        let generics = self.type_parameters()?;
+        self.expect(b'(')?;
+        let mut argument_tys = vec![];
+        while !self.eat(b')') {
+            argument_tys.push(self.java_type()?);
+        }
+        let return_ty = if self.eat(b'V') {
+            None
+        } else {
+            Some(self.java_type()?)
+        };
+        let mut throws = vec![];
+        while self.eat(b'^') {
+            throws.push(self.reference_type()?);
+        }
+        self.expect_end()?;
+        Ok(MethodType {
+            generics,
+            argument_tys,
+            return_ty,
+            throws,
+        })
+    }
+
+    fn type_parameters(&mut self) -> anyhow::Result<Vec<Generic>> {
+        let mut generics = vec![];
+        if self.eat(b'<') {
+            while !self.eat(b'>') {
+                generics.push(self.type_parameter()?);
+            }
+        }
+        Ok(generics)
+    }
+
+    fn type_parameter(&mut self) -> anyhow::Result<Generic> {
+        let id = Id::from(self.identifier()?);
+
+        // The class bound may be empty (e.g., `T::Ljava/lang/Comparable<TT;>;`),
+        // but there is always a `:` before it.
+        self.expect(b':')?;
+        let mut extends = vec![];
+        if matches!(self.peek(), Some(b'L' | b'T' | b'[')) {
+            let class_bound = self.reference_type()?;
+            // Like `javap`, omit an `Object` class bound.
+            if !matches!(&class_bound, RefType::Class(c) if is_object(c)) {
+                extends.push(class_bound);
+            }
+        }
+        while self.eat(b':') {
+            extends.push(self.reference_type()?);
+        }
+
+        Ok(Generic { id, extends })
+    }
+
+    fn java_type(&mut self) -> anyhow::Result<Type> {
+        let scalar = match self.peek() {
+            Some(b'B') => ScalarType::Byte,
+            Some(b'C') => ScalarType::Char,
+            Some(b'D') => ScalarType::F64,
+            Some(b'F') => ScalarType::F32,
+            Some(b'I') => ScalarType::Int,
+            Some(b'J') => ScalarType::Long,
+            Some(b'S') => ScalarType::Short,
+            Some(b'Z') => ScalarType::Boolean,
+            _ => return Ok(Type::Ref(self.reference_type()?)),
+        };
+        self.offset += 1;
+        Ok(Type::Scalar(scalar))
+    }
+
+    fn reference_type(&mut self) -> anyhow::Result<RefType> {
+        if self.eat(b'L') {
+            Ok(RefType::Class(self.class_type()?))
+        } else if self.eat(b'T') {
+            let id = Id::from(self.identifier()?);
+            self.expect(b';')?;
+            Ok(RefType::TypeParameter(id))
+        } else if self.eat(b'[') {
+            Ok(RefType::Array(Arc::new(self.java_type()?)))
+        } else {
+            bail!("expected reference type at offset {}", self.offset)
+        }
+    }
+
+    /// Parses a `ClassTypeSignature` after the leading `L`.
+    fn class_type(&mut self) -> anyhow::Result<ClassRef> {
+        let mut ids = vec![Id::from(self.identifier()?)];
+        while self.eat(b'/') {
+            ids.push(Id::from(self.identifier()?));
+        }
+        let mut class_ref = ClassRef {
+            name: ids.into_iter().collect(),
+            generics: self.type_arguments()?,
+        };
+
+        // Inner classes of a parameterized class, like `Outer<TT;>.Inner<TU;>;`, are printed
+        // by `javap` as `Outer<T>.Inner<U>`, which we parse as `Outer.Inner<U>`.
+        while self.eat(b'.') {
+            let inner = self.identifier()?;
+            class_ref = ClassRef {
+                name: class_ref.name.dot(inner),
+                generics: self.type_arguments()?,
+            };
+        }
+
+        self.expect(b';')?;
+        Ok(class_ref)
+    }
+
+    fn type_arguments(&mut self) -> anyhow::Result<Vec<RefType>> {
+        let mut arguments = vec![];
+        if self.eat(b'<') {
+            while !self.eat(b'>') {
+                let argument = if self.eat(b'*') {
+                    RefType::Wildcard
+                } else if self.eat(b'+') {
+                    RefType::Extends(Arc::new(self.reference_type()?))
+                } else if self.eat(b'-') {
+                    RefType::Super(Arc::new(self.reference_type()?))
+                } else {
+                    self.reference_type()?
+                };
+                arguments.push(argument);
+            }
+        }
+        Ok(arguments)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{decode_modified_utf8, SignatureParser};
+    use crate::class_info::{RefType, ScalarType, Type};
+
+    #[test]
+    fn modified_utf8() {
+        assert_eq!(decode_modified_utf8(b"hello").unwrap(), "hello");
+        assert_eq!(decode_modified_utf8(&[0xC0, 0x80]).unwrap(), "\0");
+        // U+1F980 (crab) as a surrogate pair
+        assert_eq!(
+            decode_modified_utf8(&[0xED, 0xA0, 0xBE, 0xED, 0xB6, 0x80]).unwrap(),
+            "🦀"
+        );
+        assert!(decode_modified_utf8(&[0xFF]).is_err());
+    }
+
+    #[test]
+    fn method_signatures() {
+        let ty = SignatureParser::new(
+            "<T::Ljava/lang/Comparable<-TT;>;>(Ljava/util/Collection<+TT;>;[I)TT;",
+        )
+        .method_signature()
+        .unwrap();
+        assert_eq!(
+            ty.generics
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<_>>(),
+            vec!["T extends java.lang.Comparable<? super T>"]
+        );
+        assert_eq!(
+            ty.argument_tys
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<_>>(),
+            vec!["java.util.Collection<? extends T>", "int[]"]
+        );
+        assert_eq!(ty.return_ty.unwrap().to_string(), "T",);
+
+        let ty = SignatureParser::new("(J[[Ljava/lang/String;)V")
+            .method_signature()
+            .unwrap();
+        assert_eq!(
+            ty.argument_tys,
+            vec![
+                Type::Scalar(ScalarType::Long),
+                Type::Ref(RefType::Array(Arc::new(Type::Ref(RefType::Array(
+                    Arc::new(Type::Ref(RefType::Class(crate::class_info::ClassRef {
+                        name: crate::class_info::DotId::parse("java.lang.String"),
+                        generics: vec![],
+                    })))
+                )))))
+            ]
+        );
+        assert!(ty.return_ty.is_none());
+
+        let ty = SignatureParser::new(
+            "<U:Ljava/lang/Object;T:TU;>(Ljava/util/concurrent/CompletableFuture<TT;>;)V",
+        )
+        .method_signature()
+        .unwrap();
+        assert_eq!(
+            ty.generics
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<_>>(),
+            vec!["U", "T extends U"]
+        );
+
+        assert!(SignatureParser::new("(I").method_signature().is_err());
+        assert!(SignatureParser::new("()VX").method_signature().is_err());
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
     argument::DuchessDeclaration,
-    class_info::{ClassInfo, Constructor, DotId, Field, Id, Method, RootMap, SpannedPackageInfo},
+    class_info::{
+        ClassInfo, ClassKind, Constructor, DotId, Field, Id, Method, RootMap, SpannedPackageInfo,
+    },
     config::Configuration,
     reflect::PrecomputedReflector,
     signature::Signature,
     upcasts::Upcasts,
 };
+use std::collections::{BTreeMap, BTreeSet};
+
 use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::{quote, quote_spanned};
 
@@ -43,11 +47,13 @@ impl SpannedPackageInfo {
             .map(|p| p.to_tokens(&package_id, root_map, reflector))
             .collect::<Result<_, _>>()?;
 
-        let class_tokens: TokenStream = self
-            .classes
-            .iter()
-            .map(|class_id| root_map.classes[class_id].to_tokens(&root_map.upcasts))
-            .collect::<Result<_, _>>()?;
+        let mut class_tokens = ClassTokens::default();
+        for class_id in &self.classes {
+            let (outer_names, _) = class_id.class_name().split_nested();
+            let tokens = root_map.classes[class_id].to_tokens(&root_map.upcasts)?;
+            class_tokens.push(&outer_names, tokens);
+        }
+        let class_tokens = class_tokens.into_tokens(self.span);
 
         let supers: Vec<TokenStream> = package_id
             .iter()
@@ -70,30 +76,78 @@ impl SpannedPackageInfo {
     }
 }
 
+/// The tokens for the classes in a package, where nested classes (e.g., `Map$Entry`)
+/// are placed in a module named after their enclosing class (e.g., `map::Entry`).
+#[derive(Default)]
+struct ClassTokens {
+    tokens: TokenStream,
+    nested: BTreeMap<Id, ClassTokens>,
+}
+
+impl ClassTokens {
+    fn push(&mut self, outer_names: &[Id], tokens: TokenStream) {
+        match outer_names.split_first() {
+            None => self.tokens.extend(tokens),
+            Some((outer, rest)) => self
+                .nested
+                .entry(outer.to_snake_case())
+                .or_default()
+                .push(rest, tokens),
+        }
+    }
+
+    fn into_tokens(self, span: Span) -> TokenStream {
+        let ClassTokens { tokens, nested } = self;
+        let modules = nested.into_iter().map(|(name, class_tokens)| {
+            let name = name.to_ident(span);
+            let class_tokens = class_tokens.into_tokens(span);
+            quote_spanned!(span =>
+                pub mod #name {
+                    // Import the contents of the module of the enclosing class
+                    use super::*;
+
+                    #class_tokens
+                }
+            )
+        });
+        quote_spanned!(span => #tokens #(#modules)*)
+    }
+}
+
 impl ClassInfo {
     pub fn to_tokens(&self, upcasts: &Upcasts) -> syn::Result<TokenStream> {
         let struct_name = self.struct_name();
         let java_class_generics = self.class_generic_names();
         let jni_class_name = self.jni_class_name();
 
-        // Convert constructors
+        // Convert constructors (enums cannot be constructed, their constants are used instead)
         let constructors: Vec<_> = self
             .constructors
             .iter()
+            .filter(|_| self.kind != ClassKind::Enum)
             .map(|c| self.constructor(c))
             .collect::<Result<_, _>>()?;
 
-        // Convert static methods (not instance methods, those are different)
+        // Convert static methods (not instance methods, those are different).
+        // Static methods with their own generic parameters are generated separately,
+        // since their generics are unrelated to (and may even shadow) those of the class.
         let static_methods: Vec<_> = self
             .methods
             .iter()
             .filter(|m| self.should_mirror_in_rust(m.flags.privacy))
-            .filter(|m| m.flags.is_static)
+            .filter(|m| m.flags.is_static && m.generics.is_empty())
+            .map(|m| self.static_method(m))
+            .collect::<Result<_, _>>()?;
+        let generic_static_methods: Vec<_> = self
+            .methods
+            .iter()
+            .filter(|m| self.should_mirror_in_rust(m.flags.privacy))
+            .filter(|m| m.flags.is_static && !m.generics.is_empty())
             .map(|m| self.static_method(m))
             .collect::<Result<_, _>>()?;
 
         // Convert instance methods (not static methods, those are different)
-        let op_methods: Vec<_> = self
+        let mut op_methods: Vec<_> = self
             .methods
             .iter()
             .filter(|m| self.should_mirror_in_rust(m.flags.privacy))
@@ -102,7 +156,7 @@ impl ClassInfo {
             .collect::<Result<_, _>>()?;
 
         // Convert instance methods (not static methods, those are different)
-        let obj_methods: Vec<_> = self
+        let mut obj_methods: Vec<_> = self
             .methods
             .iter()
             .filter(|m| self.should_mirror_in_rust(m.flags.privacy))
@@ -110,8 +164,9 @@ impl ClassInfo {
             .map(|m| self.obj_struct_method(m))
             .collect::<Result<_, _>>()?;
 
-        let op_name = Id::from(format!("ViewAs{}Op", self.name.class_name())).to_ident(self.span);
-        let obj_name = Id::from(format!("ViewAs{}Obj", self.name.class_name())).to_ident(self.span);
+        let (_, simple_name) = self.name.class_name().split_nested();
+        let op_name = Id::from(format!("ViewAs{}Op", simple_name)).to_ident(self.span);
+        let obj_name = Id::from(format!("ViewAs{}Obj", simple_name)).to_ident(self.span);
 
         // Convert instance methods of the form `Foo::method`
         let inherent_object_methods: Vec<_> = self
@@ -131,6 +186,56 @@ impl ClassInfo {
             .map(|f| self.static_field_getter(f))
             .collect::<Result<_, _>>()?;
 
+        // Field accessors are skipped if a method of the same name would be generated.
+        let method_names: BTreeSet<String> = self
+            .methods
+            .iter()
+            .filter(|m| self.should_mirror_in_rust(m.flags.privacy))
+            .map(|m| m.name.to_snake_case().to_string())
+            .collect();
+
+        // Generate static field setters (final fields cannot be set)
+        let static_field_setters: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|f| self.should_mirror_in_rust(f.flags.privacy))
+            .filter(|f| f.flags.is_static && !f.flags.is_final)
+            .filter(|f| !method_names.contains(&field_setter_name(f)))
+            .map(|f| self.static_field_setter(f))
+            .collect::<Result<_, _>>()?;
+
+        // Generate accessors for enum constants, named after the constant (e.g., `Color::RED()`)
+        let enum_constants: Vec<_> = self
+            .enum_constants()
+            .filter(|f| !method_names.contains(&f.name[..]))
+            .map(|f| self.enum_constant(f))
+            .collect::<Result<_, _>>()?;
+
+        // Generate getters and setters for instance fields.
+        // Like instance methods, these are available as `Foo::field(obj)` as well as on the op/obj structs.
+        let mut inherent_object_fields = vec![];
+        for field in &self.fields {
+            if !self.should_mirror_in_rust(field.flags.privacy) || field.flags.is_static {
+                continue;
+            }
+
+            // A setter without the matching getter would be confusing, so both are skipped
+            // if the getter clashes with a method.
+            if method_names.contains(&field_getter_name(field)) {
+                continue;
+            }
+
+            inherent_object_fields.push(self.inherent_object_field_getter(field)?);
+            op_methods.push(self.struct_field_getter(field, StructKind::Op)?);
+            obj_methods.push(self.struct_field_getter(field, StructKind::Obj)?);
+
+            if !field.flags.is_final && !method_names.contains(&field_setter_name(field)) {
+                inherent_object_fields.push(self.inherent_object_field_setter(field)?);
+                op_methods.push(self.struct_field_setter(field, StructKind::Op)?);
+                obj_methods.push(self.struct_field_setter(field, StructKind::Obj)?);
+            }
+        }
+
         let mro_tys = self.mro(upcasts)?;
 
         let output = quote! {
@@ -141,7 +246,11 @@ impl ClassInfo {
                 mro_tys: [#(#mro_tys,)*],
                 constructors: [#(#constructors)*],
                 static_methods: [#(#static_methods)*],
+                generic_static_methods: [#(#generic_static_methods)*],
                 static_field_getters: [#(#static_field_getters)*],
+                static_field_setters: [#(#static_field_setters)*],
+                enum_constants: [#(#enum_constants)*],
+                inherent_object_fields: [#(#inherent_object_fields)*],
                 inherent_object_methods: [#(#inherent_object_methods)*],
                 op_struct_methods: [#(#op_methods)*],
                 obj_struct_methods: [#(#obj_methods)*],
@@ -346,13 +455,21 @@ impl ClassInfo {
     ///
     /// NB. This function (particularly the JvmOp impl) has significant overlap with `object_method`
     /// and `static_field_getter`, so if you make changes here, you may well need changes there.
+    ///
+    /// Static methods with generic parameters of their own are generated for the erased class
+    /// (e.g., `List::of::<E>` rather than `List::<E>::of`), as Java static methods cannot
+    /// reference the generics of their class.
     fn static_method(&self, method: &Method) -> syn::Result<TokenStream> {
         assert!(method.flags.is_static);
 
         let struct_name = self.struct_name();
-        let java_class_generics = self.class_generic_names();
+        let (java_class_generics, class_generics) = if method.generics.is_empty() {
+            (self.class_generic_names(), &self.generics[..])
+        } else {
+            (vec![], &[][..])
+        };
 
-        let mut sig = Signature::new(&method.name, self.span, &self.generics)
+        let mut sig = Signature::new(&method.name, self.span, class_generics)
             .with_internal_generics(&method.generics)?;
 
         let (input_ty_tts, input_ty_ops, input_names, output_ty_tt) =
@@ -400,22 +517,63 @@ impl ClassInfo {
 
         let mut sig = Signature::new(&field.name, self.span, &self.generics);
 
-        let field_ty = sig.java_ty_tt(&field.ty)?;
+        let field_ty = sig.erase_wildcards(|sig| sig.java_ty_tt(&field.ty))?;
 
         let jni_field = jni_c_str(&*field.name, self.span);
-        let jni_descriptor = jni_c_str(&field.ty.descriptor(&self.generics_scope()), self.span);
+        let jni_descriptor = jni_c_str(field.ty.descriptor(&self.generics_scope()), self.span);
 
         let rust_field_name =
             Id::from(format!("get_{}", field.name.to_snake_case())).to_ident(self.span);
 
         let sig_where_clauses = &sig.where_clauses;
 
+        let doc = field_accessor_doc(field, "Reads");
+
         Ok(quote!(
             duchess::semver_unstable::setup_static_field_getter! {
+                doc: [#doc],
+                struct_name: [#struct_name],
+                java_class_generics: [#(#java_class_generics,)*],
+                rust_field_name: [#rust_field_name],
+                field_ty: [#field_ty],
+                sig_where_clauses: [#(#sig_where_clauses,)*],
+                jni_field: [#jni_field],
+                jni_descriptor: [#jni_descriptor],
+            }
+        ))
+    }
+
+    /// Generates a static field setter that should be part of the inherent methods
+    /// for the struct.
+    fn static_field_setter(&self, field: &Field) -> syn::Result<TokenStream> {
+        assert!(field.flags.is_static && !field.flags.is_final);
+
+        let struct_name = self.struct_name();
+        let java_class_generics = self.class_generic_names();
+
+        let mut sig = Signature::new(&field.name, self.span, &self.generics);
+
+        // Wildcards would require generics on the setter, which static accessors do not support.
+        let field_ty = sig.forbid_capture(|sig| sig.java_ty_tt(&field.ty))?;
+        let field_ty_op = sig.jvm_op_trait(&field.ty, &field_ty)?;
+
+        let jni_field = jni_c_str(&*field.name, self.span);
+        let jni_descriptor = jni_c_str(field.ty.descriptor(&self.generics_scope()), self.span);
+
+        let rust_field_name = Id::from(field_setter_name(field)).to_ident(self.span);
+
+        let sig_where_clauses = &sig.where_clauses;
+
+        let doc = field_accessor_doc(field, "Writes");
+
+        Ok(quote!(
+            duchess::semver_unstable::setup_static_field_setter! {
+                doc: [#doc],
                 struct_name: [#struct_name],
                 java_class_generics: [#(#java_class_generics,)*],
                 rust_field_name: [#rust_field_name],
                 field_ty: [#field_ty],
+                field_ty_op: [#field_ty_op],
                 sig_where_clauses: [#(#sig_where_clauses,)*],
                 jni_field: [#jni_field],
                 jni_descriptor: [#jni_descriptor],
@@ -423,8 +581,166 @@ impl ClassInfo {
         ))
     }
 
+    /// Generates the accessor for an enum constant that should be part of the inherent methods
+    /// for the struct. Unlike [`Self::static_field_getter`], the result is never null.
+    fn enum_constant(&self, field: &Field) -> syn::Result<TokenStream> {
+        assert_eq!(self.kind, ClassKind::Enum);
+
+        let struct_name = self.struct_name();
+
+        let mut sig = Signature::new(&field.name, self.span, &self.generics);
+
+        let field_ty = sig.java_ty_tt(&field.ty)?;
+
+        let jni_field = jni_c_str(&*field.name, self.span);
+        let jni_descriptor = jni_c_str(field.ty.descriptor(&self.generics_scope()), self.span);
+
+        let rust_constant_name = field.name.to_ident(self.span);
+
+        Ok(quote!(duchess::semver_unstable::setup_enum_constant! {
+            struct_name: [#struct_name],
+            rust_constant_name: [#rust_constant_name],
+            field_ty: [#field_ty],
+            jni_field: [#jni_field],
+            jni_descriptor: [#jni_descriptor],
+        }))
+    }
+
+    /// Generates an instance field getter of the form `Foo::field(obj)`.
+    fn inherent_object_field_getter(&self, field: &Field) -> syn::Result<TokenStream> {
+        assert!(!field.flags.is_static);
+
+        let struct_name = self.struct_name();
+        let java_class_generics = self.class_generic_names();
+
+        let mut sig = Signature::new(&field.name, self.span, &self.generics);
+
+        let field_ty = sig.erase_wildcards(|sig| sig.java_ty_tt(&field.ty))?;
+
+        let jni_field = jni_c_str(&*field.name, self.span);
+        let jni_descriptor = jni_c_str(field.ty.descriptor(&self.generics_scope()), self.span);
+
+        let rust_field_name = Id::from(field_getter_name(field)).to_ident(self.span);
+
+        let rust_field_generics = &sig.rust_generics;
+
+        let sig_where_clauses = &sig.where_clauses;
+
+        let doc = field_accessor_doc(field, "Reads");
+
+        Ok(quote!(
+            duchess::semver_unstable::setup_inherent_object_field_getter! {
+                doc: [#doc],
+                struct_name: [#struct_name],
+                java_class_generics: [#(#java_class_generics,)*],
+                rust_field_name: [#rust_field_name],
+                rust_field_generics: [#(#rust_field_generics,)*],
+                field_ty: [#field_ty],
+                sig_where_clauses: [#(#sig_where_clauses,)*],
+                jni_field: [#jni_field],
+                jni_descriptor: [#jni_descriptor],
+            }
+        ))
+    }
+
+    /// Generates an instance field setter of the form `Foo::set_field(obj, value)`.
+    fn inherent_object_field_setter(&self, field: &Field) -> syn::Result<TokenStream> {
+        assert!(!field.flags.is_static && !field.flags.is_final);
+
+        let struct_name = self.struct_name();
+        let java_class_generics = self.class_generic_names();
+
+        let mut sig = Signature::new(&field.name, self.span, &self.generics);
+
+        let field_ty = sig.java_ty_tt(&field.ty)?;
+        let field_ty_op = sig.jvm_op_trait(&field.ty, &field_ty)?;
+
+        let jni_field = jni_c_str(&*field.name, self.span);
+        let jni_descriptor = jni_c_str(field.ty.descriptor(&self.generics_scope()), self.span);
+
+        let rust_field_name = Id::from(field_setter_name(field)).to_ident(self.span);
+
+        let rust_field_generics = &sig.rust_generics;
+
+        let sig_where_clauses = &sig.where_clauses;
+
+        let doc = field_accessor_doc(field, "Writes");
+
+        Ok(quote!(
+            duchess::semver_unstable::setup_inherent_object_field_setter! {
+                doc: [#doc],
+                struct_name: [#struct_name],
+                java_class_generics: [#(#java_class_generics,)*],
+                rust_field_name: [#rust_field_name],
+                rust_field_generics: [#(#rust_field_generics,)*],
+                field_ty: [#field_ty],
+                field_ty_op: [#field_ty_op],
+                sig_where_clauses: [#(#sig_where_clauses,)*],
+                jni_field: [#jni_field],
+                jni_descriptor: [#jni_descriptor],
+            }
+        ))
+    }
+
+    /// Generates the getter for an instance field that goes on the op or obj struct;
+    /// it forwards to the getter generated by `inherent_object_field_getter`.
+    fn struct_field_getter(&self, field: &Field, kind: StructKind) -> syn::Result<TokenStream> {
+        let mut sig = Signature::new(&field.name, self.span, &self.generics);
+        let output_ty_tt = sig.erase_wildcards(|sig| sig.java_ty_tt(&field.ty))?;
+        let rust_method_name = Id::from(field_getter_name(field)).to_ident(self.span);
+        Ok(self.struct_method(kind, &sig, rust_method_name, &[], &[], output_ty_tt))
+    }
+
+    /// Generates the setter for an instance field that goes on the op or obj struct;
+    /// it forwards to the setter generated by `inherent_object_field_setter`.
+    fn struct_field_setter(&self, field: &Field, kind: StructKind) -> syn::Result<TokenStream> {
+        let mut sig = Signature::new(&field.name, self.span, &self.generics);
+        let input_ty_tt = sig.java_ty_tt(&field.ty)?;
+        let rust_method_name = Id::from(field_setter_name(field)).to_ident(self.span);
+        let input_name = Ident::new("value", self.span);
+        Ok(self.struct_method(
+            kind,
+            &sig,
+            rust_method_name,
+            &[input_name],
+            &[input_ty_tt],
+            quote!(void),
+        ))
+    }
+
+    fn struct_method(
+        &self,
+        kind: StructKind,
+        sig: &Signature,
+        rust_method_name: Ident,
+        input_names: &[Ident],
+        input_ty_tts: &[TokenStream],
+        output_ty_tt: TokenStream,
+    ) -> TokenStream {
+        let struct_name = self.struct_name();
+        let java_class_generics = self.class_generic_names();
+        let rust_method_generics = &sig.rust_generics;
+        let sig_where_clauses = &sig.where_clauses;
+        let setup_method = match kind {
+            StructKind::Op => quote!(setup_op_method),
+            StructKind::Obj => quote!(setup_obj_method),
+        };
+
+        quote!(duchess::semver_unstable::#setup_method! {
+            struct_name: [#struct_name],
+            java_class_generics: [#(#java_class_generics,)*],
+            rust_method_name: [#rust_method_name],
+            rust_method_generics: [#(#rust_method_generics,)*],
+            input_names: [#(#input_names,)*],
+            input_ty_tts: [#(#input_ty_tts,)*],
+            output_ty_tt: [#output_ty_tt],
+            sig_where_clauses: [#(#sig_where_clauses,)*],
+        })
+    }
+
     fn struct_name(&self) -> Ident {
-        self.name.class_name().to_ident(self.span)
+        let (_, simple_name) = self.name.class_name().split_nested();
+        simple_name.to_ident(self.span)
     }
 
     fn class_generic_names(&self) -> Vec<Ident> {
@@ -440,6 +756,35 @@ impl ClassInfo {
     }
 }
 
+/// The op and obj structs that host instance methods (see `setup_class`).
+#[derive(Copy, Clone)]
+enum StructKind {
+    Op,
+    Obj,
+}
+
+/// Rust name of the getter for an instance field.
+fn field_getter_name(field: &Field) -> String {
+    field.name.to_snake_case().to_string()
+}
+
+/// Rust name of the setter for a (static or instance) field.
+fn field_setter_name(field: &Field) -> String {
+    format!("set_{}", field.name.to_snake_case())
+}
+
+/// Doc comment for a generated accessor that `action`s `field` (e.g., "Reads").
+fn field_accessor_doc(field: &Field, action: &str) -> String {
+    let mut doc = format!("{action} the Java field `{}`.", field.name);
+    if field.flags.is_volatile {
+        doc.push_str(
+            "\n\nThe field is `volatile`, but JNI does not specify whether accessing it \
+             through JNI has the ordering guarantees of a volatile access in Java.",
+        );
+    }
+    doc
+}
+
 fn jni_c_str(contents: impl Into<String>, span: Span) -> TokenStream {
     let mut contents = contents.into().into_bytes();
     // \0 isn't valid UTF-8, so don't need to check that contents doesn't contain interior nul bytes.
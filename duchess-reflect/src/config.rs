@@ -77,6 +77,11 @@ impl Configuration {
         }
     }
 
+    /// Read the configured `JAVA_HOME`, if any
+    pub fn java_home(&self) -> Option<&Path> {
+        self.data.java_home.as_deref()
+    }
+
     /// Read current classpath configuration
     pub fn bin_path(&self, command: &str) -> PathBuf {
         if let Some(java_home) = &self.data.java_home {
@@ -106,7 +111,7 @@ fn use_platform_appropriate_delimiters(input: String) -> String {
     input.replace([';', ':'], classpath_delimiter())
 }
 
-fn classpath_delimiter() -> &'static str {
+pub(crate) fn classpath_delimiter() -> &'static str {
     if cfg!(windows) {
         ";"
     } else {
@@ -172,10 +172,16 @@ impl<'p> TextAccum<'p> {
 
     /// Return the string we accumulated.
     pub fn into_accumulated_result(self) -> (String, Span) {
-        (self.text, self.span)
+        (join_dollars(&self.text), self.span)
     }
 }
 
+/// Rust tokenizes `$` on its own, so the names of nested classes like `Map$Entry`
+/// get separated by whitespace (`Map $ Entry`); glue them back together.
+fn join_dollars(text: &str) -> String {
+    text.split('$').map(str::trim).collect::<Vec<_>>().join("$")
+}
+
 /// A trait for things that can be parsed from a token stream.
 pub trait Parse: Sized {
     /// We assume an LL(1) grammar, so no need for backtracking.
@@ -17,6 +17,10 @@ use crate::{
     upcasts::Upcasts,
 };
 
+#[cfg(feature = "bytecode-reflection")]
+mod bytecode;
+#[cfg(feature = "bytecode-reflection")]
+pub use bytecode::BytecodeReflector;
 #[cfg(feature = "javap-reflection")]
 mod javap;
 #[cfg(feature = "javap-reflection")]
@@ -88,10 +92,16 @@ impl JavaPackage {
                     let info = reflector.reflect(&dot_id, c.span)?;
 
                     // We copy over the span and kind for proper error specification and error checking
+                    // (but a `class` that turns out to be an enum becomes an enum).
+                    let kind = if c.kind.matches_reflected(info.kind) {
+                        info.kind
+                    } else {
+                        c.kind
+                    };
                     (
                         dot_id,
                         Arc::new(ClassInfo {
-                            kind: c.kind,
+                            kind,
                             ..info.to_class_info(c.span)
                         }),
                     )
@@ -147,6 +157,27 @@ fn reflection_cache(out_dir: impl AsRef<Path>) -> PathBuf {
     out_dir.as_ref().join("reflection-cache.json")
 }
 
+/// Serializes reflected classes in the format read by [`PrecomputedReflector`].
+fn serialize_reflection_cache(classes: &BTreeMap<DotId, Arc<JavapClassInfo>>) -> String {
+    serde_json::to_string_pretty(classes).expect("failed to serialize JSON")
+}
+
+fn dump_reflection_cache(json: &str, out_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+    let path = reflection_cache(out_dir);
+    std::fs::write(&path, json)
+        .with_context(|| format!("writing reflection cache data to {:?}", path))?;
+    Ok(())
+}
+
+/// Writes `classes` to the reflection cache in `out_dir`, to be read by [`PrecomputedReflector`].
+/// Used when the classes were gathered from more than one reflector.
+pub fn dump_classes(
+    classes: &BTreeMap<DotId, Arc<JavapClassInfo>>,
+    out_dir: impl AsRef<Path>,
+) -> anyhow::Result<()> {
+    dump_reflection_cache(&serialize_reflection_cache(classes), out_dir)
+}
+
 pub trait Reflect {
     fn reflect(&mut self, dot_id: &DotId, span: Span) -> syn::Result<Arc<JavapClassInfo>>;
 }
@@ -370,20 +401,43 @@ impl ReflectedMethod {
             ReflectedMethod::Method(c, m) => &c.methods[*m].argument_tys,
         }
     }
+
+    /// The type returned; constructors return nothing.
+    pub fn return_ty(&self) -> Option<&Type> {
+        match self {
+            ReflectedMethod::Constructor(..) => None,
+            ReflectedMethod::Method(c, m) => c.methods[*m].return_ty.as_ref(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use serde::Serialize;
-
-    use crate::class_info::DotId;
+    use crate::class_info::{ClassKind, DotId};
     use crate::config::Configuration;
 
-    use super::{JavapReflector, PrecomputedReflector};
+    use super::PrecomputedReflector;
 
+    #[cfg(feature = "javap-reflection")]
     #[test]
     fn reflector_rountrips() {
-        let mut reflector = Reflector::new_javap(&Configuration::default());
+        let mut reflector = super::JavapReflector::new(&Configuration::default());
+        let _class = reflector
+            .reflect_and_cache(
+                &DotId::parse("java.lang.String"),
+                proc_macro2::Span::call_site(),
+            )
+            .unwrap();
+
+        let serialized = reflector.serialize();
+        let parsed = PrecomputedReflector::new_from_contents(serialized.as_bytes()).unwrap();
+        assert_eq!(parsed.classes.len(), 1);
+    }
+
+    #[cfg(feature = "bytecode-reflection")]
+    #[test]
+    fn bytecode_reflector_rountrips() {
+        let mut reflector = super::BytecodeReflector::new(&Configuration::default());
         let _class = reflector
             .reflect_and_cache(
                 &DotId::parse("java.lang.String"),
@@ -392,7 +446,209 @@ mod test {
             .unwrap();
 
         let serialized = reflector.serialize();
-        let parsed = PrecomputedReflector::new_from_contents(serialized.as_bytes());
+        let parsed = PrecomputedReflector::new_from_contents(serialized.as_bytes()).unwrap();
         assert_eq!(parsed.classes.len(), 1);
     }
+
+    #[cfg(feature = "bytecode-reflection")]
+    #[test]
+    fn bytecode_reflector_recognizes_enums() {
+        let span = proc_macro2::Span::call_site();
+        let mut reflector = super::BytecodeReflector::new(&Configuration::default());
+
+        let state = reflector
+            .reflect_and_cache(&DotId::parse("java.lang.Thread$State"), span)
+            .unwrap()
+            .to_class_info(span);
+        assert_eq!(state.kind, ClassKind::Enum);
+        let constants: Vec<_> = state.enum_constants().map(|f| f.name.to_string()).collect();
+        assert_eq!(
+            constants,
+            [
+                "NEW",
+                "RUNNABLE",
+                "BLOCKED",
+                "WAITING",
+                "TIMED_WAITING",
+                "TERMINATED"
+            ]
+        );
+
+        let string = reflector
+            .reflect_and_cache(&DotId::parse("java.lang.String"), span)
+            .unwrap();
+        assert_eq!(string.kind, ClassKind::Class);
+    }
+
+    #[cfg(feature = "bytecode-reflection")]
+    #[test]
+    fn bytecode_reflector_recognizes_records() {
+        let span = proc_macro2::Span::call_site();
+        let mut reflector = super::BytecodeReflector::new(&Configuration::default());
+
+        let principal = reflector
+            .reflect_and_cache(&DotId::parse("jdk.net.UnixDomainPrincipal"), span)
+            .unwrap()
+            .to_class_info(span);
+        assert_eq!(principal.kind, ClassKind::Record);
+        let components: Vec<_> = principal
+            .record_components()
+            .map(|f| f.name.to_string())
+            .collect();
+        assert_eq!(components, ["user", "group"]);
+        assert!(principal.canonical_constructor().is_some());
+    }
+
+    #[cfg(all(feature = "javap-reflection", feature = "bytecode-reflection"))]
+    mod bytecode_matches_javap {
+        use std::process::Command;
+
+        use crate::class_info::{DotId, Privacy};
+        use crate::config::Configuration;
+        use crate::reflect::{BytecodeReflector, JavapReflector};
+
+        /// Asserts that both reflectors agree on each of `classes`.
+        ///
+        /// Classes that `javap` reflection fails on (e.g., because they throw a type parameter)
+        /// are only checked to reflect successfully. `javap` does not show the declared flags of
+        /// member classes, so those are not compared.
+        fn check(configuration: &Configuration, classes: &[&str]) {
+            let span = proc_macro2::Span::call_site();
+            let mut javap = JavapReflector::new(configuration);
+            let mut bytecode = BytecodeReflector::new(configuration);
+            let mut checked = 0;
+            for class in classes {
+                let class = DotId::parse(class);
+                let mut actual = (*bytecode.reflect_and_cache(&class, span).unwrap()).clone();
+                let Ok(expected) = javap.reflect_and_cache(&class, span) else {
+                    continue;
+                };
+                if class.outer_class().is_some() {
+                    actual.flags = expected.flags;
+                }
+                assert_eq!(
+                    serde_json::to_string_pretty(&actual).unwrap(),
+                    serde_json::to_string_pretty(&expected).unwrap(),
+                    "reflecting on `{class}`",
+                );
+                checked += 1;
+            }
+            assert!(checked > 0, "javap failed on every class");
+        }
+
+        #[test]
+        fn jdk_classes() {
+            check(
+                &Configuration::default(),
+                &[
+                    "java.lang.Object",
+                    "java.lang.String",
+                    "java.lang.Thread",
+                    "java.lang.Thread$State",
+                    "java.lang.Throwable",
+                    "java.lang.Enum",
+                    "java.lang.Record",
+                    "java.lang.Integer",
+                    "java.lang.annotation.Retention",
+                    "java.util.ArrayList",
+                    "java.util.Collections",
+                    "java.util.Comparator",
+                    "java.util.HashMap",
+                    "java.util.HashMap$Node",
+                    "java.util.List",
+                    "java.util.Map",
+                    "java.util.Map$Entry",
+                    "java.util.Optional",
+                    "java.util.concurrent.CompletableFuture",
+                    "java.util.function.Function",
+                ],
+            );
+        }
+
+        #[test]
+        fn compiled_classes() {
+            let out_dir = tempfile::tempdir().unwrap();
+            let classes_dir = out_dir.path().join("classes");
+            let jar = out_dir.path().join("kitchen.jar");
+            let configuration = Configuration::default();
+
+            let source = concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/test-files/reflect/Kitchen.java"
+            );
+            let status = Command::new(configuration.bin_path("javac"))
+                .arg("-encoding")
+                .arg("UTF-8")
+                .arg("-d")
+                .arg(&classes_dir)
+                .arg(source)
+                .status()
+                .unwrap();
+            assert!(status.success());
+
+            let status = Command::new(configuration.bin_path("jar"))
+                .arg("--create")
+                .arg("--file")
+                .arg(&jar)
+                .arg("-C")
+                .arg(&classes_dir)
+                .arg(".")
+                .status()
+                .unwrap();
+            assert!(status.success());
+
+            let classes = [
+                "reflect.Kitchen",
+                "reflect.Kitchen$Inner",
+                "reflect.Kitchen$Nested",
+                "reflect.Kitchen$Shelf",
+                "reflect.Kitchen$Utensil",
+                "reflect.Kitchen$Utensil$1",
+            ];
+            check(
+                &configuration.clone().with_classpath(classes_dir.display()),
+                &classes,
+            );
+            check(
+                &configuration.clone().with_classpath(jar.display()),
+                &classes,
+            );
+
+            let span = proc_macro2::Span::call_site();
+            let mut reflector =
+                BytecodeReflector::new(&configuration.with_classpath(jar.display()));
+            let mut reflect = |class: &str| {
+                reflector
+                    .reflect_and_cache(&DotId::parse(class), span)
+                    .unwrap()
+                    .to_class_info(span)
+            };
+
+            // Member classes take their flags from the `InnerClasses` attribute
+            for (class, is_static, privacy) in [
+                ("reflect.Kitchen$Inner", false, Privacy::Public),
+                ("reflect.Kitchen$Nested", true, Privacy::Public),
+                ("reflect.Kitchen$Shelf", true, Privacy::Public),
+                ("reflect.Kitchen$Utensil", true, Privacy::Public),
+                ("reflect.Kitchen$Drawer", true, Privacy::Private),
+                ("reflect.Kitchen$Pantry", false, Privacy::Protected),
+            ] {
+                let info = reflect(class);
+                assert_eq!(info.flags.is_static, is_static, "{class}");
+                assert_eq!(info.flags.privacy, privacy, "{class}");
+            }
+            assert!(!reflect("reflect.Kitchen").flags.is_static);
+
+            // Constructors of inner classes take the instance of the enclosing class
+            let inner = reflect("reflect.Kitchen$Inner");
+            assert_eq!(inner.constructors.len(), 2);
+            for constructor in &inner.constructors {
+                assert_eq!(
+                    constructor.argument_tys[0].to_string(),
+                    "reflect.Kitchen",
+                    "{constructor:?}"
+                );
+            }
+        }
+    }
 }
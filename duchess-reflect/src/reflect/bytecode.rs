@@ -0,0 +1,335 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Arc,
+};
+
+use anyhow::{bail, Context};
+use proc_macro2::Span;
+use zip::ZipArchive;
+
+use crate::{
+    class_info::{ClassInfo, DotId},
+    config::{classpath_delimiter, Configuration},
+};
+
+use super::{dump_reflection_cache, serialize_reflection_cache, JavapClassInfo, Reflect};
+
+/// Reflector that reads `.class` files directly, from the JDK and from the configured classpath.
+///
+/// Unlike [`JavapReflector`](`super::JavapReflector`), this does not spawn a process per class,
+/// which makes it much faster when reflecting on many classes.
+pub struct BytecodeReflector {
+    configuration: Configuration,
+
+    /// Opened the first time we reflect on a class.
+    class_path: Option<Vec<ClassPathEntry>>,
+
+    classes: BTreeMap<DotId, Arc<JavapClassInfo>>,
+}
+
+impl std::fmt::Debug for BytecodeReflector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BytecodeReflector")
+            .field("configuration", &self.configuration)
+            .field("classes", &self.classes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Reflect for BytecodeReflector {
+    fn reflect(&mut self, dot_id: &DotId, span: Span) -> syn::Result<Arc<JavapClassInfo>> {
+        BytecodeReflector::reflect_and_cache(self, dot_id, span)
+    }
+}
+
+impl BytecodeReflector {
+    pub fn new(configuration: &Configuration) -> Self {
+        Self {
+            configuration: configuration.clone(),
+            class_path: None,
+            classes: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> String {
+        serialize_reflection_cache(&self.classes)
+    }
+
+    pub fn dump_to(&self, out_dir: impl AsRef<Path>) -> anyhow::Result<()> {
+        dump_reflection_cache(&self.serialize(), out_dir)
+    }
+
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    fn reflect_via_bytecode(
+        &mut self,
+        class_name: &DotId,
+        span: Span,
+    ) -> anyhow::Result<JavapClassInfo> {
+        if self.class_path.is_none() {
+            self.class_path = Some(open_class_path(&self.configuration)?);
+        }
+        let class_path = self.class_path.as_mut().unwrap();
+
+        let file_name = format!("{}.class", class_name.to_jni_name());
+        for entry in class_path {
+            if let Some(bytes) = entry.read(&file_name)? {
+                let ci = ClassInfo::parse_class_file(&bytes, span)
+                    .with_context(|| format!("reading `{file_name}` from {entry}"))?;
+                return Ok(JavapClassInfo::from(ci));
+            }
+        }
+
+        bail!("class `{class_name}` not found")
+    }
+
+    pub fn reflect_and_cache(
+        &mut self,
+        class_name: &DotId,
+        span: Span,
+    ) -> syn::Result<Arc<JavapClassInfo>> {
+        if let Some(ci) = self.classes.get(class_name) {
+            return Ok(Arc::clone(ci));
+        }
+
+        let class_info = self
+            .reflect_via_bytecode(class_name, span)
+            .map_err(|err| syn::Error::new(span, format!("{:#}", err)))?;
+
+        let ci = Arc::new(class_info);
+
+        self.classes.insert(class_name.clone(), Arc::clone(&ci));
+
+        Ok(ci)
+    }
+}
+
+/// A place to look for `.class` files.
+enum ClassPathEntry {
+    Directory(PathBuf),
+    Archive {
+        path: PathBuf,
+        /// Prefix of class files within the archive (`classes/` for `.jmod` files).
+        prefix: &'static str,
+        archive: ZipArchive<Box<dyn ReadSeek>>,
+    },
+}
+
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+impl std::fmt::Display for ClassPathEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClassPathEntry::Directory(path) | ClassPathEntry::Archive { path, .. } => {
+                write!(f, "{path:?}")
+            }
+        }
+    }
+}
+
+impl ClassPathEntry {
+    fn open_archive(path: &Path, prefix: &'static str, skip: u64) -> anyhow::Result<Self> {
+        let file = BufReader::new(File::open(path).with_context(|| format!("opening {path:?}"))?);
+        let reader: Box<dyn ReadSeek> = Box::new(SkipHeader::new(file, skip)?);
+        let archive = ZipArchive::new(reader).with_context(|| format!("reading {path:?}"))?;
+        Ok(ClassPathEntry::Archive {
+            path: path.to_path_buf(),
+            prefix,
+            archive,
+        })
+    }
+
+    /// Reads `file_name` (e.g., `java/lang/Object.class`), returning `None` if it is not present.
+    fn read(&mut self, file_name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match self {
+            ClassPathEntry::Directory(dir) => {
+                let path = dir.join(file_name);
+                if !path.is_file() {
+                    return Ok(None);
+                }
+                let bytes = std::fs::read(&path).with_context(|| format!("reading {path:?}"))?;
+                Ok(Some(bytes))
+            }
+            ClassPathEntry::Archive {
+                path,
+                prefix,
+                archive,
+            } => {
+                let name = format!("{prefix}{file_name}");
+                if archive.index_for_name(&name).is_none() {
+                    return Ok(None);
+                }
+                let mut file = archive
+                    .by_name(&name)
+                    .with_context(|| format!("reading `{name}` from {path:?}"))?;
+                let mut bytes = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut bytes)
+                    .with_context(|| format!("reading `{name}` from {path:?}"))?;
+                Ok(Some(bytes))
+            }
+        }
+    }
+}
+
+/// Opens the JDK's classes, followed by the entries of the configured classpath.
+fn open_class_path(configuration: &Configuration) -> anyhow::Result<Vec<ClassPathEntry>> {
+    let java_home = match configuration.java_home() {
+        Some(java_home) => java_home.to_path_buf(),
+        None => find_java_home(configuration)?,
+    };
+
+    let mut entries = open_jdk(&java_home)?;
+
+    for element in configuration
+        .classpath()
+        .into_iter()
+        .flat_map(|classpath| classpath.split(classpath_delimiter()))
+        .filter(|element| !element.is_empty())
+    {
+        // Like `java`, a trailing `*` includes all jars in a directory.
+        if let Some(dir) = element.strip_suffix('*') {
+            let dir = if dir.is_empty() { "." } else { dir };
+            let Ok(read_dir) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            let mut jars = read_dir
+                .map(|entry| Ok(entry?.path()))
+                .collect::<std::io::Result<Vec<_>>>()
+                .with_context(|| format!("listing classpath directory {dir:?}"))?;
+            jars.retain(|path| is_jar(path));
+            jars.sort();
+            for jar in jars {
+                entries.push(ClassPathEntry::open_archive(&jar, "", 0)?);
+            }
+            continue;
+        }
+
+        // Like `java`, nonexistent classpath entries are ignored.
+        let path = Path::new(element);
+        if path.is_dir() {
+            entries.push(ClassPathEntry::Directory(path.to_path_buf()));
+        } else if path.is_file() {
+            entries.push(ClassPathEntry::open_archive(path, "", 0)?);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Opens the classes of the JDK at `java_home`: the `.jmod` files of JDK 9+ or the `rt.jar` of older JDKs.
+fn open_jdk(java_home: &Path) -> anyhow::Result<Vec<ClassPathEntry>> {
+    let jmods = java_home.join("jmods");
+    if jmods.is_dir() {
+        let mut paths = std::fs::read_dir(&jmods)
+            .with_context(|| format!("listing {jmods:?}"))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<std::io::Result<Vec<_>>>()
+            .with_context(|| format!("listing {jmods:?}"))?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "jmod"));
+
+        // Check `java.base` first, since that is where most lookups end up.
+        paths.sort_by_key(|path| (!path.ends_with("java.base.jmod"), path.clone()));
+
+        // A `.jmod` file is a zip archive preceded by a 4 byte header.
+        return paths
+            .iter()
+            .map(|path| ClassPathEntry::open_archive(path, "classes/", 4))
+            .collect();
+    }
+
+    for rt_jar in [
+        java_home.join("jre").join("lib").join("rt.jar"),
+        java_home.join("lib").join("rt.jar"),
+    ] {
+        if rt_jar.is_file() {
+            return Ok(vec![ClassPathEntry::open_archive(&rt_jar, "", 0)?]);
+        }
+    }
+
+    bail!(
+        "cannot find the JDK classes in {java_home:?} \
+        (expected a `jmods` directory or `rt.jar`); \
+        JDK installations without `jmods` require `javap` reflection"
+    )
+}
+
+fn is_jar(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+}
+
+/// Asks `java` where it is installed, for when `JAVA_HOME` is not set.
+fn find_java_home(configuration: &Configuration) -> anyhow::Result<PathBuf> {
+    let mut command = Command::new(configuration.bin_path("java"));
+    command.arg("-XshowSettings:properties").arg("-version");
+
+    let output = match command.output() {
+        Ok(o) => o,
+        Err(err) => bail!("failed to execute `{command:?}`: {err}"),
+    };
+
+    if !output.status.success() {
+        bail!(
+            "unsuccessful execution of `{command:?}` (exit status: {}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    // The settings are printed to stderr, one `key = value` per line.
+    let settings = String::from_utf8_lossy(&output.stderr);
+    match settings
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("java.home = "))
+    {
+        Some(java_home) => Ok(PathBuf::from(java_home)),
+        None => bail!("`java.home` not found in the output of `{command:?}`"),
+    }
+}
+
+/// Hides the first `skip` bytes of a reader.
+struct SkipHeader<R> {
+    inner: R,
+    skip: u64,
+}
+
+impl<R: Seek> SkipHeader<R> {
+    fn new(mut inner: R, skip: u64) -> std::io::Result<Self> {
+        inner.seek(SeekFrom::Start(skip))?;
+        Ok(SkipHeader { inner, skip })
+    }
+}
+
+impl<R: Read> Read for SkipHeader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for SkipHeader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => SeekFrom::Start(offset + self.skip),
+            pos => pos,
+        };
+        let offset = self.inner.seek(pos)?;
+        offset.checked_sub(self.skip).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek to before the start of the archive",
+            )
+        })
+    }
+}
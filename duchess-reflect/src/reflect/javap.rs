@@ -1,14 +1,14 @@
 use std::{collections::BTreeMap, path::Path, process::Command, sync::Arc};
 
-use anyhow::{bail, Context};
+use anyhow::bail;
 use proc_macro2::Span;
 
 use crate::{
-    class_info::{ClassInfo, DotId},
+    class_info::{missing_outer_instance, ClassInfo, DotId},
     config::Configuration,
 };
 
-use super::{reflection_cache, JavapClassInfo, Reflect};
+use super::{dump_reflection_cache, serialize_reflection_cache, JavapClassInfo, Reflect};
 
 /// Reflector that uses JavaP to perform reflection
 #[derive(Debug)]
@@ -32,22 +32,22 @@ impl JavapReflector {
         }
     }
 
-    fn serialize(&self) -> String {
-        serde_json::to_string_pretty(&self.classes).expect("failed to serialize JSON")
+    pub(crate) fn serialize(&self) -> String {
+        serialize_reflection_cache(&self.classes)
     }
 
     pub fn dump_to(&self, out_dir: impl AsRef<Path>) -> anyhow::Result<()> {
-        let path = reflection_cache(out_dir);
-        let json = self.serialize();
-        std::fs::write(&path, json)
-            .with_context(|| format!("writing reflection cache data to {:?}", path))?;
-        Ok(())
+        dump_reflection_cache(&self.serialize(), out_dir)
     }
 
     pub fn len(&self) -> usize {
         self.classes.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
     fn reflect_via_javap(&self, class_name: &DotId, span: Span) -> anyhow::Result<JavapClassInfo> {
         let mut command = Command::new(self.configuration.bin_path("javap"));
 
@@ -55,7 +55,7 @@ impl JavapReflector {
             command.arg("-cp").arg(classpath);
         }
 
-        command.arg("-p").arg(format!("{}", class_name));
+        command.arg("-p").arg("-s").arg(format!("{}", class_name));
 
         let output_or_err = command.output();
 
@@ -81,7 +81,41 @@ impl JavapReflector {
             }
         };
 
-        let ci = ClassInfo::parse(&s, span)?;
+        // Separate the descriptors (printed due to `-s`) from the declarations,
+        // keeping those of constructors, which immediately follow their declaration
+        let mut text = String::new();
+        let mut constructor_descriptors = vec![];
+        let mut previous_member = None;
+        for line in s.lines() {
+            if let Some(descriptor) = line.trim().strip_prefix("descriptor:") {
+                let Some(member) = previous_member.take() else {
+                    bail!("unexpected descriptor in output of `{command:?}`: {line}");
+                };
+                if is_constructor(class_name, member) {
+                    constructor_descriptors.push(descriptor.trim());
+                }
+            } else {
+                text.push_str(line);
+                text.push('\n');
+                previous_member = Some(line);
+            }
+        }
+
+        let mut ci = ClassInfo::parse(&text, span)?;
+        if ci.constructors.len() != constructor_descriptors.len() {
+            bail!(
+                "found {} constructors of `{class_name}` but {} constructor descriptors in output of `{command:?}`",
+                ci.constructors.len(),
+                constructor_descriptors.len(),
+            );
+        }
+        for (constructor, descriptor) in ci.constructors.iter_mut().zip(constructor_descriptors) {
+            if let Some(outer) =
+                missing_outer_instance(class_name, descriptor, &constructor.argument_tys)
+            {
+                constructor.argument_tys.insert(0, outer);
+            }
+        }
         Ok(JavapClassInfo::from(ci))
     }
 
@@ -106,3 +140,34 @@ impl JavapReflector {
         Ok(ci)
     }
 }
+
+/// Whether `member`, a declaration printed by javap, declares a constructor of `class_name`,
+/// e.g. `public java.util.ArrayList(int);` or `public <T> foo.Bar(T);`
+fn is_constructor(class_name: &DotId, member: &str) -> bool {
+    let Some((before_args, _)) = member.split_once('(') else {
+        return false;
+    };
+    before_args.split_whitespace().last() == Some(class_name.to_string().as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_constructor;
+    use crate::class_info::DotId;
+
+    #[test]
+    fn constructors() {
+        let class_name = DotId::parse("foo.Outer$Inner");
+        assert!(is_constructor(
+            &class_name,
+            "  public foo.Outer$Inner(foo.Outer);"
+        ));
+        assert!(is_constructor(&class_name, "  <T> foo.Outer$Inner(T);"));
+        assert!(!is_constructor(
+            &class_name,
+            "  public foo.Outer$Inner copy();"
+        ));
+        assert!(!is_constructor(&class_name, "  void set(foo.Outer$Inner);"));
+        assert!(!is_constructor(&class_name, "  static {};"));
+    }
+}
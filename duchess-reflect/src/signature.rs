@@ -1,5 +1,5 @@
 use crate::class_info::{
-    ClassRef, Generic, Id, Method, NonRepeatingType, RefType, ScalarType, Type,
+    ClassRef, DotId, Generic, Id, Method, NonRepeatingType, RefType, ScalarType, Type,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
@@ -38,6 +38,10 @@ pub struct Signature {
     /// If false, report an error if `?` appears, because it is a context where
     /// we don't support capture.
     capture_generics: bool,
+
+    /// If true, translate `?` to its upper bound rather than to fresh generics.
+    /// Used for return types, where the caller cannot choose the type of the wildcard.
+    erase_wildcards: bool,
 }
 
 impl Signature {
@@ -58,6 +62,7 @@ impl Signature {
             rust_generics: vec![],
             where_clauses: vec![],
             capture_generics: true,
+            erase_wildcards: false,
         }
     }
 
@@ -81,7 +86,7 @@ impl Signature {
                 s.where_clauses
                     .push(quote_spanned!(s.span => #ident : duchess::JavaObject));
                 for e in &g.extends {
-                    let ty = s.class_ref_ty_rs(e)?;
+                    let ty = s.java_ref_ty_rs(e)?;
                     s.where_clauses
                         .push(quote_spanned!(s.span => #ident : duchess::AsJRef<#ty>));
                 }
@@ -101,6 +106,19 @@ impl Signature {
         r
     }
 
+    /// Set the `erase_wildcards` field to true while `op` executes,
+    /// then restore its value.
+    ///
+    /// Wildcards are then translated to their upper bound, so that
+    /// `List<? extends Number>` becomes `List<Number>` and `List<?>` or
+    /// `List<? super Number>` becomes `List<Object>`.
+    pub fn erase_wildcards<R>(&mut self, op: impl FnOnce(&mut Self) -> R) -> R {
+        let v = std::mem::replace(&mut self.erase_wildcards, true);
+        let r = op(self);
+        self.erase_wildcards = v;
+        r
+    }
+
     /// Create and return a tuple with three fields:
     ///
     /// * the `input_ty_tts` token trees describing the input types to `method` (see [`Self::java_ty_tt`][])
@@ -167,7 +185,8 @@ impl Signature {
     /// Push a where bound into the list of where clauses that will be
     /// emitted later. Used to manage Java wildcards. A type like
     /// `ArrayList<? extends Foo>` becomes `ArrayList<X>` with a bound
-    /// `X: Upcast<Foo>`.
+    /// `X: AsJRef<Foo>`, and `ArrayList<? super Foo>` becomes `ArrayList<X>`
+    /// with a bound `Foo: AsJRef<X>`.
     ///
     /// See also `Self::fresh_generic`.
     fn push_where_bound(&mut self, t: TokenStream) {
@@ -193,9 +212,10 @@ impl Signature {
 
     /// Return a token tree that can be passed to the macro-rules macros
     /// to represent the output type of a function; this can include `void`.
+    /// Wildcards in the output type are erased (see [`Self::erase_wildcards`][]).
     pub fn output_ty_tt(&mut self, ty: &Option<Type>) -> syn::Result<TokenStream> {
         match ty {
-            Some(ty) => self.java_ty_tt(ty),
+            Some(ty) => self.erase_wildcards(|s| s.java_ty_tt(ty)),
             None => Ok(quote!(void)),
         }
     }
@@ -211,9 +231,14 @@ impl Signature {
     /// takes `List<?>` can be translated to a Rust function taking
     /// `List<T>` where `T: JavaObject`.
     pub fn java_ty_tt(&mut self, ty: &Type) -> syn::Result<TokenStream> {
-        match &ty.to_non_repeating() {
-            NonRepeatingType::Ref(ty) => self.java_ref_ty_tt(ty),
-            NonRepeatingType::Scalar(ty) => Ok(self.java_scalar_ty_tt(ty)),
+        match ty {
+            // Varargs are passed to Java as an array, but we accept any collection of arguments
+            Type::Repeat(e) => {
+                let e = self.java_ty_tt(e)?;
+                Ok(quote!((varargs #e)))
+            }
+            Type::Ref(ty) => self.java_ref_ty_tt(ty),
+            Type::Scalar(ty) => Ok(self.java_scalar_ty_tt(ty)),
         }
     }
 
@@ -252,15 +277,27 @@ impl Signature {
                     Err(syn::Error::new(self.span, msg))
                 }
             }
+            RefType::Extends(ty) if self.erase_wildcards => self.java_ref_ty_tt(ty),
+            RefType::Super(_) | RefType::Wildcard if self.erase_wildcards => {
+                self.class_ref_ty_tt(&ClassRef {
+                    name: DotId::object(),
+                    generics: vec![],
+                })
+            }
             RefType::Extends(ty) => {
                 let g = self.fresh_generic()?;
                 let e = self.java_ref_ty_rs(ty)?;
                 self.push_where_bound(quote_spanned!(self.span => #g : duchess::AsJRef<#e>));
                 Ok(quote!((generic #g)))
             }
-            RefType::Super(_) => {
+            RefType::Super(ty) => {
                 let g = self.fresh_generic()?;
-                // FIXME: missing where bound, really
+                // A bound like `T: AsJRef<G>` on a type parameter `T` could not be proven
+                // by generic callers, so we only bound wildcards whose bound is a class.
+                if !matches!(&**ty, RefType::TypeParameter(_)) {
+                    let s = self.java_ref_ty_rs(ty)?;
+                    self.push_where_bound(quote_spanned!(self.span => #s : duchess::AsJRef<#g>));
+                }
                 Ok(quote!((generic #g)))
             }
             RefType::Wildcard => {
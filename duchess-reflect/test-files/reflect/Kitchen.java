@@ -0,0 +1,90 @@
+package reflect;
+
+import java.io.IOException;
+import java.io.Serializable;
+import java.util.Comparator;
+import java.util.List;
+import java.util.Map;
+import java.util.function.Supplier;
+
+/** Exercises the class file features that reflection needs to understand. */
+public abstract class Kitchen<K extends Comparable<K> & Serializable, V> implements Comparator<K> {
+    public static final int CONSTANT = 42;
+    public static final long LONG_CONSTANT = 1L << 40;
+    public static final double DOUBLE_CONSTANT = 0.5;
+    public static final String STRING_CONSTANT = "\0 nul and 🦀 crab";
+    protected volatile Map<K, List<? extends V>> map;
+    transient V[] values;
+    private int[][] grid;
+
+    public Kitchen() {}
+
+    protected Kitchen(K key, V... values) throws IOException {}
+
+    public abstract <T extends Throwable> V orElseThrow(Supplier<? extends T> supplier) throws T;
+
+    public synchronized native void sync();
+
+    public static <T extends Comparable<? super T>> T max(List<? super T> list, T... rest) {
+        return null;
+    }
+
+    public final Map<? super K, ?> wildcard() {
+        return null;
+    }
+
+    public void run() throws IOException, InterruptedException {
+        Runnable r = () -> {};
+        r.run();
+    }
+
+    public class Inner {
+        public Inner() {}
+
+        // The generic signature of this constructor omits the enclosing `Kitchen`
+        public Inner(java.util.List<? extends V> values) {}
+
+        public Kitchen<K, V> outer() {
+            return Kitchen.this;
+        }
+    }
+
+    public static class Nested<T> {
+        public Kitchen<?, T>.Inner inner() {
+            return null;
+        }
+    }
+
+    private static class Drawer {}
+
+    protected class Pantry {}
+
+    public interface Shelf<T> extends Comparable<Shelf<T>> {
+        T get();
+
+        default T getOrDefault(T value) {
+            T t = get();
+            return t == null ? value : t;
+        }
+
+        static <T> Shelf<T> empty() {
+            return null;
+        }
+
+        private void helper() {}
+    }
+
+    public enum Utensil {
+        FORK,
+        KNIFE {
+            @Override
+            public boolean sharp() {
+                return true;
+            }
+        };
+
+        public boolean sharp() {
+            return false;
+        }
+    }
+}
@@ -0,0 +1,62 @@
+package duchess;
+
+import java.lang.ref.Cleaner;
+import java.util.function.BiConsumer;
+
+/**
+ * A Java object whose behavior is implemented by a Rust value.
+ *
+ * The Rust side (see `src/callback.rs`) defines this class at runtime and
+ * hands out instances typed as whichever interface it needs. `pointer`
+ * is owned by this object and released by the cleaner once it becomes
+ * unreachable.
+ */
+final class RustCallback implements Runnable, BiConsumer<Object, Object> {
+    private static final Cleaner CLEANER = Cleaner.create();
+
+    private final long pointer;
+
+    RustCallback(long pointer) {
+        this.pointer = pointer;
+        CLEANER.register(this, new Drop(pointer));
+    }
+
+    /**
+     * Runs `action` once `object` becomes unreachable.
+     * Used to release Rust memory that backs a Java object, like a direct `ByteBuffer`.
+     */
+    static void runWhenUnreachable(Object object, Runnable action) {
+        CLEANER.register(object, action);
+    }
+
+    @Override
+    public void run() {
+        nativeRun(pointer);
+    }
+
+    @Override
+    public void accept(Object t, Object u) {
+        nativeAccept(pointer, t, u);
+    }
+
+    // These are instance methods so that `this` stays reachable
+    // (and `pointer` alive) for the duration of the native call.
+    private native void nativeRun(long pointer);
+
+    private native void nativeAccept(long pointer, Object t, Object u);
+
+    static native void nativeDrop(long pointer);
+
+    static final class Drop implements Runnable {
+        private final long pointer;
+
+        Drop(long pointer) {
+            this.pointer = pointer;
+        }
+
+        @Override
+        public void run() {
+            nativeDrop(pointer);
+        }
+    }
+}
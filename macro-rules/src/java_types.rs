@@ -9,13 +9,18 @@
 //! Java reference types are a `()`-token tree like:
 //! * `(class[$path] $javaty*)`, e.g., `(class[java::util::Vector] (class[java::lang::String))` for `Vector<String>`
 //! * `(array $javaty)`, e.g., `(array[(class[java::lang::String])])` for `String[]`
+//! * `(varargs $javaty)`, e.g., `(varargs (class[java::lang::String]))` for `String...`
 //! * `(generic $name)` to reference a generic (possible captured) type, e.g., `(generic[T])`
 
 mod argument_impl_trait;
 mod field_output_trait;
 mod jni_call_fn;
+mod jni_field_get_fn;
+mod jni_field_set_fn;
+mod jni_field_value;
 mod jni_static_call_fn;
 mod jni_static_field_get_fn;
+mod jni_static_field_set_fn;
 mod output_trait;
 mod output_type;
 mod prepare_input;
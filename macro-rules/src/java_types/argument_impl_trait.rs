@@ -7,8 +7,18 @@
 /// * `int + 'a` expands to `impl IntoScalar<i32> + 'a`
 /// * `(class[java::lang::Object])` expands to `impl IntoJava<java::lang::Object>`
 /// * `(class[java::lang::Object]) + 'a` expands to `impl IntoJava<java::lang::Object> + 'a`
+/// * `(varargs int)` expands to `impl IntoScalarVarargs<i32>`
+/// * `(varargs (class[java::lang::Object]))` expands to `impl IntoVarargs<java::lang::Object>`
 #[macro_export]
 macro_rules! argument_impl_trait {
+    ((varargs $scalar:ident) $(+ $lt:lifetime)?) => {
+        impl duchess::IntoScalarVarargs< duchess::semver_unstable::rust_ty!($scalar) > $(+ $lt)?
+    };
+
+    ((varargs $r:tt) $(+ $lt:lifetime)?) => {
+        impl duchess::IntoVarargs< duchess::semver_unstable::rust_ty!($r) > $(+ $lt)?
+    };
+
     ($scalar:ident $(+ $lt:lifetime)?) => {
         impl duchess::IntoScalar< duchess::semver_unstable::rust_ty!($scalar) > $(+ $lt)?
     };
@@ -0,0 +1,38 @@
+/// Generates a closure that selects the appropriate JNI method
+/// to call to get an instance field based on the field type.
+///
+/// # Examples
+///
+/// * `int` expands to `|env| env.GetIntField`
+#[macro_export]
+macro_rules! jni_field_get_fn {
+    (byte) => {
+        |env| env.GetByteField
+    };
+    (short) => {
+        |env| env.GetShortField
+    };
+    (int) => {
+        |env| env.GetIntField
+    };
+    (long) => {
+        |env| env.GetLongField
+    };
+    (float) => {
+        |env| env.GetFloatField
+    };
+    (double) => {
+        |env| env.GetDoubleField
+    };
+    (char) => {
+        |env| env.GetCharField
+    };
+    (boolean) => {
+        |env| env.GetBooleanField
+    };
+
+    // Reference types
+    ($r:tt) => {
+        |env| env.GetObjectField
+    };
+}
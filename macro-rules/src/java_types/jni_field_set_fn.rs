@@ -0,0 +1,38 @@
+/// Generates a closure that selects the appropriate JNI method
+/// to call to set an instance field based on the field type.
+///
+/// # Examples
+///
+/// * `int` expands to `|env| env.SetIntField`
+#[macro_export]
+macro_rules! jni_field_set_fn {
+    (byte) => {
+        |env| env.SetByteField
+    };
+    (short) => {
+        |env| env.SetShortField
+    };
+    (int) => {
+        |env| env.SetIntField
+    };
+    (long) => {
+        |env| env.SetLongField
+    };
+    (float) => {
+        |env| env.SetFloatField
+    };
+    (double) => {
+        |env| env.SetDoubleField
+    };
+    (char) => {
+        |env| env.SetCharField
+    };
+    (boolean) => {
+        |env| env.SetBooleanField
+    };
+
+    // Reference types
+    ($r:tt) => {
+        |env| env.SetObjectField
+    };
+}
@@ -0,0 +1,41 @@
+/// Selects the member of a `jvalue` union that holds a value of the given type,
+/// as needed for the JNI functions that set fields.
+///
+/// Must be used within an `unsafe` block.
+///
+/// # Examples
+///
+/// * `int, v` expands to `v.i`
+/// * `(class[java::lang::Object]), v` expands to `v.l`
+#[macro_export]
+macro_rules! jni_field_value {
+    (byte, $v:expr) => {
+        $v.b
+    };
+    (short, $v:expr) => {
+        $v.s
+    };
+    (int, $v:expr) => {
+        $v.i
+    };
+    (long, $v:expr) => {
+        $v.j
+    };
+    (float, $v:expr) => {
+        $v.f
+    };
+    (double, $v:expr) => {
+        $v.d
+    };
+    (char, $v:expr) => {
+        $v.c
+    };
+    (boolean, $v:expr) => {
+        $v.z
+    };
+
+    // Reference types
+    ($r:tt, $v:expr) => {
+        $v.l
+    };
+}
@@ -0,0 +1,38 @@
+/// Generates a closure that selects the appropriate JNI method
+/// to call to set a static field based on the field type.
+///
+/// # Examples
+///
+/// * `int` expands to `|env| env.SetStaticIntField`
+#[macro_export]
+macro_rules! jni_static_field_set_fn {
+    (byte) => {
+        |env| env.SetStaticByteField
+    };
+    (short) => {
+        |env| env.SetStaticShortField
+    };
+    (int) => {
+        |env| env.SetStaticIntField
+    };
+    (long) => {
+        |env| env.SetStaticLongField
+    };
+    (float) => {
+        |env| env.SetStaticFloatField
+    };
+    (double) => {
+        |env| env.SetStaticDoubleField
+    };
+    (char) => {
+        |env| env.SetStaticCharField
+    };
+    (boolean) => {
+        |env| env.SetStaticBooleanField
+    };
+
+    // Reference types
+    ($r:tt) => {
+        |env| env.SetStaticObjectField
+    };
+}
@@ -45,6 +45,9 @@ macro_rules! rust_ty {
     ((array $elem:tt)) => {
         java::Array<duchess::semver_unstable::rust_ty!($elem)>
     };
+    ((varargs $elem:tt)) => {
+        java::Array<duchess::semver_unstable::rust_ty!($elem)>
+    };
     ((generic $name:ident)) => {
         $name
     };
@@ -3,9 +3,13 @@ mod macro_if;
 mod mro;
 mod setup_class;
 mod setup_constructor;
+mod setup_enum_constant;
+mod setup_inherent_object_field_getter;
+mod setup_inherent_object_field_setter;
 mod setup_inherent_object_method;
 mod setup_java_function;
 mod setup_obj_method;
 mod setup_op_method;
 mod setup_static_field_getter;
+mod setup_static_field_setter;
 mod setup_static_method;
@@ -7,7 +7,11 @@ macro_rules! setup_class {
         mro_tys: [$($mro_ty:ty,)*],
         constructors: [$($constructors:tt)*],
         static_methods: [$($static_methods:tt)*],
+        generic_static_methods: [$($generic_static_methods:tt)*],
         static_field_getters: [$($static_field_getters:tt)*],
+        static_field_setters: [$($static_field_setters:tt)*],
+        enum_constants: [$($enum_constants:tt)*],
+        inherent_object_fields: [$($inherent_object_fields:tt)*],
         inherent_object_methods: [$($inherent_object_methods:tt)*],
         op_struct_methods: [$($op_struct_methods:tt)*],
         obj_struct_methods: [$($obj_struct_methods:tt)*],
@@ -135,9 +139,22 @@ macro_rules! setup_class {
 
                 $($static_field_getters)*
 
+                $($static_field_setters)*
+
+                $($enum_constants)*
+
+                $($inherent_object_fields)*
+
                 $($inherent_object_methods)*
             }
 
+            // Static methods with generic parameters of their own are unrelated to the
+            // generics of the class, so they are added to the erased class (e.g., `List::of::<E>`).
+
+            impl $S {
+                $($generic_static_methods)*
+            }
+
             // Helper structs for [managing method dispatch][mro]:
             //
             // * The `Op` struct, or "operation type", hosts methods that are available on the `JvmOp`
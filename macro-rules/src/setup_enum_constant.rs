@@ -0,0 +1,69 @@
+#[macro_export]
+macro_rules! setup_enum_constant {
+    (
+        struct_name: [$S:ident],
+        rust_constant_name: [$F:ident],
+        field_ty: [$F_ty:tt],
+        jni_field: [$jni_field:expr],
+        jni_descriptor: [$jni_descriptor:expr],
+    ) => {
+        #[allow(non_snake_case)]
+        pub fn $F() -> impl duchess::JavaConstructor<$S> {
+            // Enum constants are never null, so unlike a static field getter,
+            // this yields the constant itself and permits calling methods on it.
+            #[allow(non_camel_case_types)]
+            #[derive(Clone, Copy)]
+            pub struct $F;
+
+            impl duchess::prelude::JvmOp for $F {
+                type Output<'jvm> = duchess::Local<'jvm, $S>;
+
+                fn do_jni<'jvm>(
+                    self,
+                    jvm: &mut duchess::Jvm<'jvm>,
+                ) -> duchess::LocalResult<'jvm, Self::Output<'jvm>> {
+                    use duchess::semver_unstable::once_cell::sync::OnceCell;
+
+                    static FIELD: OnceCell<duchess::semver_unstable::FieldPtr> = OnceCell::new();
+                    let field = FIELD.get_or_try_init(|| {
+                        let class = <$S as duchess::JavaObject>::class(jvm)?;
+                        duchess::semver_unstable::find_field(
+                            jvm,
+                            &class,
+                            $jni_field,
+                            $jni_descriptor,
+                            true,
+                        )
+                    })?;
+
+                    let class = <$S as duchess::JavaObject>::class(jvm)?;
+                    let value: ::core::option::Option<duchess::Local<'jvm, $S>> = unsafe {
+                        jvm.env().invoke(
+                            duchess::semver_unstable::jni_static_field_get_fn!($F_ty),
+                            |env, f| {
+                                f(
+                                    env,
+                                    duchess::semver_unstable::JavaObjectExt::as_raw(&*class)
+                                        .as_ptr(),
+                                    field.as_ptr(),
+                                )
+                            },
+                        )
+                    }?;
+
+                    value.ok_or(duchess::Error::NullDeref)
+                }
+            }
+
+            impl ::core::ops::Deref for $F {
+                type Target = duchess::semver_unstable::view_of_op!($F_ty);
+
+                fn deref(&self) -> &Self::Target {
+                    <Self::Target as duchess::semver_unstable::FromRef<_>>::from_ref(self)
+                }
+            }
+
+            $F
+        }
+    };
+}
@@ -0,0 +1,112 @@
+#[macro_export]
+macro_rules! setup_inherent_object_field_getter {
+    (
+        doc: [$doc:expr],
+        struct_name: [$S:ident],
+        java_class_generics: [$($G:ident,)*],
+
+        // Snake case version of java field name
+        rust_field_name: [$F:ident],
+        rust_field_generics: [$($FG:ident,)*],
+        field_ty: [$F_ty:tt],
+        sig_where_clauses: [$($SIG:tt)*],
+        jni_field: [$jni_field:expr],
+        jni_descriptor: [$jni_descriptor:expr],
+    ) => {
+        #[doc = $doc]
+        pub fn $F<$($FG,)*>(
+            this: impl duchess::prelude::IntoJava<$S<$($G,)*>>,
+        ) -> duchess::semver_unstable::output_trait!($F_ty)
+        where
+            $($SIG)*
+        {
+            #[allow(non_camel_case_types)]
+            pub struct $F<
+                $($G,)*
+                $($FG,)*
+                this,
+            > {
+                this: this,
+                phantom: ::core::marker::PhantomData<($($G,)* $($FG,)*)>,
+            }
+
+            impl<$($G,)* $($FG,)* this> ::core::clone::Clone
+            for $F<$($G,)* $($FG,)* this>
+            where
+                this: duchess::semver_unstable::JvmRefOp<$S<$($G,)*>>,
+                $($G: duchess::JavaObject,)*
+                $($SIG)*
+            {
+                fn clone(&self) -> Self {
+                    $F {
+                        this: Clone::clone(&self.this),
+                        phantom: self.phantom,
+                    }
+                }
+            }
+
+            impl<$($G,)* $($FG,)* this> duchess::prelude::JvmOp
+            for $F<$($G,)* $($FG,)* this>
+            where
+                this: duchess::semver_unstable::JvmRefOp<$S<$($G,)*>>,
+                $($G: duchess::JavaObject,)*
+                $($SIG)*
+            {
+                type Output<'jvm> = duchess::semver_unstable::output_type!('jvm, $F_ty);
+
+                fn do_jni<'jvm>(
+                    self,
+                    jvm: &mut duchess::Jvm<'jvm>,
+                ) -> duchess::LocalResult<'jvm, Self::Output<'jvm>> {
+                    use duchess::semver_unstable::once_cell::sync::OnceCell;
+
+                    let this = self.this.into_as_jref(jvm)?;
+                    let this: &$S<$($G,)*> = duchess::prelude::AsJRef::as_jref(&this)?;
+                    let this = duchess::semver_unstable::JavaObjectExt::as_raw(this);
+
+                    // Cache the field id for this field -- note that we only have one cache
+                    // no matter how many generic monomorphizations there are. This makes sense
+                    // given Java's erased-based generics system.
+                    static FIELD: OnceCell<duchess::semver_unstable::FieldPtr> = OnceCell::new();
+                    let field = FIELD.get_or_try_init(|| {
+                        let class = <$S<$($G,)*> as duchess::JavaObject>::class(jvm)?;
+                        duchess::semver_unstable::find_field(jvm, &class, $jni_field, $jni_descriptor, false)
+                    })?;
+
+                    unsafe {
+                        jvm.env().invoke(
+                            duchess::semver_unstable::jni_field_get_fn!($F_ty),
+                            |env, f| f(
+                                env,
+                                this.as_ptr(),
+                                field.as_ptr(),
+                            ),
+                        )
+                    }
+                }
+            }
+
+            duchess::semver_unstable::macro_if! {
+                if is_ref_ty($F_ty) {
+                    impl<$($G,)* $($FG,)* this> ::core::ops::Deref
+                    for $F<$($G,)* $($FG,)* this>
+                    where
+                        $($G: duchess::JavaObject,)*
+                        $($SIG)*
+                    {
+                        type Target = duchess::semver_unstable::view_of_op!($F_ty);
+
+                        fn deref(&self) -> &Self::Target {
+                            <Self::Target as duchess::semver_unstable::FromRef<_>>::from_ref(self)
+                        }
+                    }
+                }
+            }
+
+            $F {
+                this: this.into_op(),
+                phantom: ::core::default::Default::default(),
+            }
+        }
+    };
+}
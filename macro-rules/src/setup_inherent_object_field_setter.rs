@@ -0,0 +1,109 @@
+#[macro_export]
+macro_rules! setup_inherent_object_field_setter {
+    (
+        doc: [$doc:expr],
+        struct_name: [$S:ident],
+        java_class_generics: [$($G:ident,)*],
+
+        // `set_` followed by the snake case version of java field name
+        rust_field_name: [$F:ident],
+        rust_field_generics: [$($FG:ident,)*],
+        field_ty: [$F_ty:tt],
+        field_ty_op: [$F_op:path],
+        sig_where_clauses: [$($SIG:tt)*],
+        jni_field: [$jni_field:expr],
+        jni_descriptor: [$jni_descriptor:expr],
+    ) => {
+        #[doc = $doc]
+        pub fn $F<$($FG,)*>(
+            this: impl duchess::prelude::IntoJava<$S<$($G,)*>>,
+            value: duchess::semver_unstable::argument_impl_trait!($F_ty),
+        ) -> impl duchess::VoidMethod
+        where
+            $($SIG)*
+        {
+            #[allow(non_camel_case_types)]
+            pub struct $F<
+                $($G,)*
+                $($FG,)*
+                this,
+                value,
+            > {
+                this: this,
+                value: value,
+                phantom: ::core::marker::PhantomData<($($G,)* $($FG,)*)>,
+            }
+
+            impl<$($G,)* $($FG,)* this, value> ::core::clone::Clone
+            for $F<$($G,)* $($FG,)* this, value>
+            where
+                this: duchess::semver_unstable::JvmRefOp<$S<$($G,)*>>,
+                value: $F_op,
+                $($G: duchess::JavaObject,)*
+                $($SIG)*
+            {
+                fn clone(&self) -> Self {
+                    $F {
+                        this: Clone::clone(&self.this),
+                        value: Clone::clone(&self.value),
+                        phantom: self.phantom,
+                    }
+                }
+            }
+
+            impl<$($G,)* $($FG,)* this, value> duchess::prelude::JvmOp
+            for $F<$($G,)* $($FG,)* this, value>
+            where
+                this: duchess::semver_unstable::JvmRefOp<$S<$($G,)*>>,
+                value: $F_op,
+                $($G: duchess::JavaObject,)*
+                $($SIG)*
+            {
+                type Output<'jvm> = ();
+
+                fn do_jni<'jvm>(
+                    self,
+                    jvm: &mut duchess::Jvm<'jvm>,
+                ) -> duchess::LocalResult<'jvm, Self::Output<'jvm>> {
+                    use duchess::semver_unstable::once_cell::sync::OnceCell;
+
+                    let this = self.this.into_as_jref(jvm)?;
+                    let this: &$S<$($G,)*> = duchess::prelude::AsJRef::as_jref(&this)?;
+                    let this = duchess::semver_unstable::JavaObjectExt::as_raw(this);
+
+                    duchess::semver_unstable::prepare_input!(let value = (self.value: $F_ty) in jvm);
+
+                    // Cache the field id for this field -- note that we only have one cache
+                    // no matter how many generic monomorphizations there are. This makes sense
+                    // given Java's erased-based generics system.
+                    static FIELD: OnceCell<duchess::semver_unstable::FieldPtr> = OnceCell::new();
+                    let field = FIELD.get_or_try_init(|| {
+                        let class = <$S<$($G,)*> as duchess::JavaObject>::class(jvm)?;
+                        duchess::semver_unstable::find_field(jvm, &class, $jni_field, $jni_descriptor, false)
+                    })?;
+
+                    let value = duchess::semver_unstable::IntoJniValue::into_jni_value(value);
+                    let () = unsafe {
+                        jvm.env().invoke(
+                            duchess::semver_unstable::jni_field_set_fn!($F_ty),
+                            |env, f| f(
+                                env,
+                                this.as_ptr(),
+                                field.as_ptr(),
+                                duchess::semver_unstable::jni_field_value!($F_ty, value),
+                            ),
+                        )
+                    }?;
+
+                    Ok(())
+                }
+            }
+
+            $F {
+                this: this.into_op(),
+                value: value.into_op(),
+                phantom: ::core::default::Default::default(),
+            }
+        }
+    };
+}
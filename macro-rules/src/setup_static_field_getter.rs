@@ -1,6 +1,7 @@
 #[macro_export]
 macro_rules! setup_static_field_getter {
     (
+        doc: [$doc:expr],
         struct_name: [$S:ident],
         java_class_generics: [$($G:ident,)*],
         rust_field_name: [$F:ident],
@@ -9,6 +10,7 @@ macro_rules! setup_static_field_getter {
         jni_field: [$jni_field:expr],
         jni_descriptor: [$jni_descriptor:expr],
     ) => {
+        #[doc = $doc]
         pub fn $F() -> duchess::semver_unstable::field_output_trait!($F_ty)
         where
             $($SIG)*
@@ -73,7 +75,7 @@ macro_rules! setup_static_field_getter {
             }
 
             $F {
-                phantom: ::core::default::Default::default(),
+                phantom: ::core::marker::PhantomData::<($($G,)*)>,
             }
         }
     };
@@ -0,0 +1,96 @@
+#[macro_export]
+macro_rules! setup_static_field_setter {
+    (
+        doc: [$doc:expr],
+        struct_name: [$S:ident],
+        java_class_generics: [$($G:ident,)*],
+        rust_field_name: [$F:ident],
+        field_ty: [$F_ty:tt],
+        field_ty_op: [$F_op:path],
+        sig_where_clauses: [$($SIG:tt)*],
+        jni_field: [$jni_field:expr],
+        jni_descriptor: [$jni_descriptor:expr],
+    ) => {
+        #[doc = $doc]
+        pub fn $F(
+            value: duchess::semver_unstable::argument_impl_trait!($F_ty),
+        ) -> impl duchess::VoidMethod
+        where
+            $($SIG)*
+        {
+            #[allow(non_camel_case_types)]
+            pub struct $F<
+                $($G,)*
+                value,
+            > {
+                value: value,
+                phantom: ::core::marker::PhantomData<(
+                    $($G,)*
+                )>,
+            }
+
+            impl<$($G,)* value> duchess::prelude::JvmOp
+            for $F<$($G,)* value>
+            where
+                value: $F_op,
+                $($G: duchess::JavaObject,)*
+                $($SIG)*
+            {
+                type Output<'jvm> = ();
+
+                fn do_jni<'jvm>(
+                    self,
+                    jvm: &mut duchess::Jvm<'jvm>,
+                ) -> duchess::LocalResult<'jvm, Self::Output<'jvm>> {
+                    use duchess::semver_unstable::once_cell::sync::OnceCell;
+
+                    duchess::semver_unstable::prepare_input!(let value = (self.value: $F_ty) in jvm);
+
+                    // Cache the field id for this field -- note that we only have one cache
+                    // no matter how many generic monomorphizations there are. This makes sense
+                    // given Java's erased-based generics system.
+                    static FIELD: OnceCell<duchess::semver_unstable::FieldPtr> = OnceCell::new();
+                    let field = FIELD.get_or_try_init(|| {
+                        let class = <$S<$($G,)*> as duchess::JavaObject>::class(jvm)?;
+                        duchess::semver_unstable::find_field(jvm, &class, $jni_field, $jni_descriptor, true)
+                    })?;
+
+                    let class = <$S<$($G,)*> as duchess::JavaObject>::class(jvm)?;
+                    let value = duchess::semver_unstable::IntoJniValue::into_jni_value(value);
+                    let () = unsafe {
+                        jvm.env().invoke(
+                            duchess::semver_unstable::jni_static_field_set_fn!($F_ty),
+                            |env, f| f(
+                                env,
+                                duchess::semver_unstable::JavaObjectExt::as_raw(&*class).as_ptr(),
+                                field.as_ptr(),
+                                duchess::semver_unstable::jni_field_value!($F_ty, value),
+                            ),
+                        )
+                    }?;
+
+                    Ok(())
+                }
+            }
+
+            impl<$($G,)* value> ::core::clone::Clone for $F<$($G,)* value>
+            where
+                value: $F_op,
+                $($G: duchess::JavaObject,)*
+                $($SIG)*
+            {
+                fn clone(&self) -> Self {
+                    $F {
+                        value: Clone::clone(&self.value),
+                        phantom: self.phantom,
+                    }
+                }
+            }
+
+            $F {
+                value: value.into_op(),
+                phantom: ::core::marker::PhantomData::<($($G,)*)>,
+            }
+        }
+    };
+}
@@ -4,13 +4,17 @@ use std::{
 };
 
 use duchess_reflect::{
-    class_info::ClassInfoAccessors,
-    reflect::{JavapClassInfo, PrecomputedReflector},
+    class_info::{
+        ClassInfo, ClassInfoAccessors, ClassKind, Field, Id, Method, RefType, ScalarType,
+    },
+    reflect::{JavapClassInfo, PrecomputedReflector, ReflectedMethod},
+    substitution::{Substitute, Substitution},
 };
+use inflector::Inflector;
 use proc_macro2::{Span, TokenStream};
-use quote::quote_spanned;
+use quote::{quote, quote_spanned};
 use syn::{spanned::Spanned, Attribute};
-use synstructure::VariantInfo;
+use synstructure::{BindingInfo, VariantInfo};
 
 use crate::{
     argument::{JavaPath, MethodSelector},
@@ -48,6 +52,20 @@ pub fn derive_to_java(s: synstructure::Structure) -> proc_macro2::TokenStream {
     }
 }
 
+pub fn derive_java_exception(s: synstructure::Structure) -> proc_macro2::TokenStream {
+    let mut driver = Driver {
+        input: &s,
+        reflector: &PrecomputedReflector::new().unwrap(),
+    };
+    match driver.try_derive_java_exception() {
+        Ok(t) => {
+            crate::debug_tokens(&s.ast().ident, &t);
+            t
+        }
+        Err(e) => e.into_compile_error(),
+    }
+}
+
 struct Driver<'a> {
     input: &'a synstructure::Structure<'a>,
     reflector: &'a PrecomputedReflector,
@@ -86,6 +104,13 @@ impl Driver<'_> {
 
     fn try_derive_to_rust_enum(&mut self) -> Result<proc_macro2::TokenStream, syn::Error> {
         let root_path: JavaPath = self.find_java_attr(self.span(), &self.input.ast().attrs)?;
+        let root_class = self
+            .reflector
+            .reflect(&root_path.to_dot_id(), root_path.span)?;
+        if root_class.kind == ClassKind::Enum {
+            return self.try_derive_to_rust_java_enum(&root_path, &root_class);
+        }
+
         let variants = self.to_rust_variants()?;
         let upcasts: Upcasts = variants.iter().map(|v| &*v.class).collect();
 
@@ -122,6 +147,7 @@ impl Driver<'_> {
         let root_to_rust = self.variant_to_rust(
             quote_spanned!(root.variant.ast().ident.span() => self),
             root.variant,
+            &root.class,
         )?;
 
         let child_class_names = children
@@ -134,6 +160,7 @@ impl Driver<'_> {
                 self.variant_to_rust(
                     quote_spanned!(c.variant.ast().ident.span() => variant),
                     c.variant,
+                    &c.class,
                 )
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -159,8 +186,117 @@ impl Driver<'_> {
         ))
     }
 
+    // Emits an `impl JavaException` that tries to downcast the exception to each variant's class, most specific
+    // first, along with the `From` impl that makes `?` convert errors through it. Errors that match no variant go
+    // to the fallback variant, the one without a `#[java(...)]` class.
+    fn try_derive_java_exception(&mut self) -> Result<proc_macro2::TokenStream, syn::Error> {
+        let syn::Data::Enum(_) = self.input.ast().data else {
+            return Err(syn::Error::new(
+                self.span(),
+                "`JavaException` can only be derived for enums",
+            ));
+        };
+
+        let (fallbacks, variants): (Vec<_>, Vec<_>) = self
+            .input
+            .variants()
+            .iter()
+            .partition(|v| !has_java_class_attr(v.ast().attrs));
+        let fallback =
+            match &fallbacks[..] {
+                [fallback] => *fallback,
+                [] => return Err(syn::Error::new(
+                    self.span(),
+                    "must have one variant without a `#[java(...)]` class, which holds the errors \
+                     that are not a matching Java exception",
+                )),
+                [_, second, ..] => {
+                    return Err(syn::Error::new(
+                        second.ast().ident.span(),
+                        "only one variant can be without a `#[java(...)]` class",
+                    ))
+                }
+            };
+        if !matches!(fallback.ast().fields, syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+        {
+            return Err(syn::Error::new(
+                fallback.ast().ident.span(),
+                "the variant without a `#[java(...)]` class must have a single unnamed field, \
+                 which holds a `duchess::Error`",
+            ));
+        }
+
+        let variants = variants
+            .into_iter()
+            .map(|v| self.to_rust_variant(v))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Exception classes often extend classes that have no variant, so include the superclasses
+        // to order the variants correctly.
+        let classes = variants
+            .iter()
+            .flat_map(|v| self.class_and_superclasses(&v.class))
+            .map(|c| (c.name.clone(), c))
+            .collect::<BTreeMap<_, _>>();
+        let upcasts: Upcasts = classes.values().map(|c| &**c).collect();
+
+        let variant_classes = unique_variant_classes(&variants)?;
+        let variants = order_by_specificity(&variant_classes, &upcasts);
+
+        let class_names = variants
+            .iter()
+            .map(|v| v.class.name.to_module_name(v.selector.span()))
+            .collect::<Vec<_>>();
+        let to_rust = variants
+            .iter()
+            .map(|v| {
+                self.variant_to_rust(
+                    quote_spanned!(v.variant.ast().ident.span() => variant),
+                    v.variant,
+                    &v.class,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let from_error = fallback.construct(|_, _| quote!(error));
+
+        let self_ty = &self.input.ast().ident;
+        let (impl_generics, ty_generics, where_clause) = self.input.ast().generics.split_for_impl();
+
+        Ok(quote_spanned!(self.span() =>
+            #[allow(unused_imports, unused_variables)]
+            impl #impl_generics duchess::JavaException for #self_ty #ty_generics #where_clause {
+                fn from_exception<'jvm>(
+                    jvm: &mut duchess::Jvm<'jvm>,
+                    exception: &duchess::java::lang::Throwable,
+                ) -> duchess::LocalResult<'jvm, ::core::option::Option<Self>> {
+                    use duchess::prelude::*;
+                    #(
+                        if let Ok(variant) = exception.try_downcast::<#class_names>().do_jni(jvm)? {
+                            return Ok(Some(#to_rust));
+                        }
+                    )*
+                    Ok(None)
+                }
+
+                fn from_error(error: duchess::Error<duchess::Java<duchess::java::lang::Throwable>>) -> Self {
+                    #from_error
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<duchess::Error<duchess::Java<duchess::java::lang::Throwable>>>
+                for #self_ty #ty_generics #where_clause
+            {
+                fn from(error: duchess::Error<duchess::Java<duchess::java::lang::Throwable>>) -> Self {
+                    <Self as duchess::JavaException>::from_duchess_error(error)
+                }
+            }
+        ))
+    }
+
     fn try_derive_to_java_struct(&mut self) -> Result<proc_macro2::TokenStream, syn::Error> {
         let variant = &self.input.variants()[0];
+        if let Some(builder) = self.container_attrs()?.builder {
+            return self.try_derive_to_java_builder(variant, &builder);
+        }
         let method = self.find_method_selector(variant.ast().ident.span(), variant.ast().attrs)?;
         let class = self
             .reflector
@@ -168,11 +304,150 @@ impl Driver<'_> {
         self.try_derive_to_java_variants(&class, [variant])
     }
 
+    /// Emits the `ToJava` impls for a struct that is constructed through a builder:
+    /// the static `builder` method is called, then one setter per field, then the `build` method.
+    fn try_derive_to_java_builder(
+        &self,
+        variant: &VariantInfo,
+        builder: &MethodSelector,
+    ) -> Result<proc_macro2::TokenStream, syn::Error> {
+        let rename_all = self.container_attrs()?.rename_all;
+        let build = self
+            .container_attrs()?
+            .build
+            .unwrap_or_else(|| syn::Ident::new("build", builder.span()));
+
+        let builder_method = self.reflector.reflect_method(builder)?;
+        if !builder_method.is_static() || !builder_method.argument_tys().is_empty() {
+            return Err(syn::Error::new(
+                builder.span(),
+                "selected builder method must be a static method without arguments",
+            ));
+        }
+        let Some(Type::Ref(RefType::Class(mut step))) = builder_method.return_ty().cloned() else {
+            return Err(syn::Error::new(
+                builder.span(),
+                "selected builder method must return a builder class",
+            ));
+        };
+
+        let mut signature = Signature::new(&builder_method.name(), builder.span(), &[]);
+        let mut setters = vec![];
+        for binding in variant.bindings() {
+            let field = binding.ast();
+            let attrs = FieldAttrs::parse(field)?;
+            if attrs.skip {
+                continue;
+            }
+
+            let setter = self.find_setter(&step, field, &attrs, rename_all)?;
+            let setter_name = setter.name.to_snake_case().to_ident(binding.span());
+            let Some(Type::Ref(RefType::Class(next_step))) = setter.return_ty else {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!(
+                        "setter `{}` of `{step}` must return a builder class",
+                        setter.name
+                    ),
+                ));
+            };
+
+            // `None` values skip the setter, so the builder must be the same either way.
+            let is_option = self.is_option(&field.ty);
+            if is_option && next_step != step {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!(
+                        "setter `{}` for optional field must return the builder `{step}`, not `{next_step}`",
+                        setter.name
+                    ),
+                ));
+            }
+
+            let value = if is_option {
+                quote_spanned!(binding.span()=> value)
+            } else {
+                quote_spanned!(binding.span()=> #binding)
+            };
+            let arg = signature.forbid_capture(|signature| {
+                self.argument_to_java(signature, &value, &setter.argument_tys[0], &attrs)
+            })?;
+            setters.push(if is_option {
+                quote_spanned!(binding.span()=>
+                    let builder = match #binding {
+                        Some(value) => builder.#setter_name(#arg).assert_not_null().do_jni(jvm)?,
+                        None => builder,
+                    };
+                )
+            } else {
+                quote_spanned!(binding.span()=>
+                    let builder = builder.#setter_name(#arg).assert_not_null().do_jni(jvm)?;
+                )
+            });
+            step = next_step;
+        }
+
+        let Some(build_method) = self.find_builder_method(&step, build.span(), |m| {
+            build == m.name[..] && m.argument_tys.is_empty()
+        })?
+        else {
+            return Err(syn::Error::new(
+                build.span(),
+                format!("no method `{build}` without arguments found on builder `{step}`"),
+            ));
+        };
+        let Some(Type::Ref(RefType::Class(built))) = build_method.return_ty else {
+            return Err(syn::Error::new(
+                build.span(),
+                format!("method `{build}` of builder `{step}` must return a class"),
+            ));
+        };
+
+        // The struct is converted to the class named in `#[java(...)]`, if any, and otherwise
+        // to the class returned by the `build` method.
+        let root_class = if has_java_class_attr(&self.input.ast().attrs) {
+            let selector =
+                self.find_method_selector(variant.ast().ident.span(), variant.ast().attrs)?;
+            self.reflector
+                .reflect(&selector.class_name(), selector.class_span())?
+        } else {
+            self.reflector.reflect(&built.name, build.span())?
+        };
+
+        let class_name = builder_method
+            .class()
+            .name
+            .to_module_name(builder.class_span());
+        let builder_name = builder_method
+            .name()
+            .to_snake_case()
+            .to_ident(builder.span());
+        let build_name = build_method.name.to_snake_case().to_ident(build.span());
+        let pattern = variant.pat();
+        let body = quote_spanned!(self.span() =>
+            #pattern => {
+                let builder = #class_name :: #builder_name ().assert_not_null().do_jni(jvm)?;
+                #(#setters)*
+                builder.#build_name().assert_not_null().upcast().do_jni(jvm)
+            }
+        );
+        Ok(self.to_java_impl(&root_class, &[body]))
+    }
+
     fn try_derive_to_java_enum(&mut self) -> Result<proc_macro2::TokenStream, syn::Error> {
+        if self.container_attrs()?.builder.is_some() {
+            return Err(syn::Error::new(
+                self.span(),
+                "`builder` is only supported on structs",
+            ));
+        }
         let root_path: JavaPath = self.find_java_attr(self.span(), &self.input.ast().attrs)?;
         let root_class = self
             .reflector
             .reflect(&root_path.to_dot_id(), root_path.span)?;
+        if root_class.kind == ClassKind::Enum {
+            return self.try_derive_to_java_java_enum(&root_path, &root_class);
+        }
 
         let selectors = self
             .input
@@ -195,17 +470,26 @@ impl Driver<'_> {
         root_class: &JavapClassInfo,
         variants: impl IntoIterator<Item = &'a VariantInfo<'a>>,
     ) -> Result<proc_macro2::TokenStream, syn::Error> {
-        let root_class_name = root_class.name.to_module_name(Span::call_site());
-
         let to_java_bodies = variants
             .into_iter()
             .map(|v| self.variant_to_java(v))
             .collect::<Result<Vec<_>, _>>()?;
 
+        Ok(self.to_java_impl(root_class, &to_java_bodies))
+    }
+
+    /// Emits the `ToJava` impls given the match arms that convert each variant.
+    fn to_java_impl(
+        &self,
+        root_class: &JavapClassInfo,
+        to_java_bodies: &[TokenStream],
+    ) -> proc_macro2::TokenStream {
+        let root_class_name = root_class.name.to_module_name(Span::call_site());
+
         let self_ty = &self.input.ast().ident;
         let (impl_generics, ty_generics, where_clause) = self.input.ast().generics.split_for_impl();
 
-        Ok(quote_spanned!(self.span() =>
+        quote_spanned!(self.span() =>
             #[allow(unused_imports, unused_variables)]
             impl #impl_generics duchess::JvmOp for & #self_ty #ty_generics #where_clause {
                 type Output<'jvm> = duchess::Local<'jvm, #root_class_name>;
@@ -223,67 +507,237 @@ impl Driver<'_> {
                     Ok(Some(duchess::JvmOp::do_jni(rust, jvm)?))
                 }
             }
+        )
+    }
+
+    /// Emits an `impl ToRust` for a Rust enum that mirrors a Java enum,
+    /// selecting the variant based on the name of the constant.
+    fn try_derive_to_rust_java_enum(
+        &self,
+        root_path: &JavaPath,
+        root_class: &JavapClassInfo,
+    ) -> Result<proc_macro2::TokenStream, syn::Error> {
+        let constants = self.java_enum_constants(root_path, root_class)?;
+
+        // Every constant must map to some variant, or we would not know what to convert it to.
+        if let Some(missing) = root_class
+            .to_class_info(root_path.span)
+            .enum_constants()
+            .find(|f| !constants.iter().any(|(_, c)| *c == f.name))
+        {
+            return Err(syn::Error::new(
+                self.span(),
+                format!(
+                    "no variant for constant `{}` of Java enum `{}`",
+                    missing.name, root_class.name
+                ),
+            ));
+        }
+
+        let constant_names = constants.iter().map(|(_, c)| &c[..]);
+        let constructs = constants.iter().map(|(v, _)| v.construct(|_, _| quote!()));
+        let root_class_name = root_class.name.to_module_name(root_path.span);
+        let unknown_constant =
+            format!("unknown constant `{{}}` of Java enum `{}`", root_class.name);
+
+        let self_ty = &self.input.ast().ident;
+        let (impl_generics, ty_generics, where_clause) = self.input.ast().generics.split_for_impl();
+
+        Ok(quote_spanned!(self.span() =>
+            #[allow(unused_imports, unused_variables)]
+            impl #impl_generics duchess::IntoRust<#self_ty #ty_generics> for &#root_class_name #where_clause {
+                fn into_rust<'jvm>(self, jvm: &mut duchess::Jvm<'jvm>) -> duchess::LocalResult<'jvm, #self_ty #ty_generics> {
+                    use duchess::prelude::*;
+                    let name: String = self.name().assert_not_null().execute_with(jvm)?;
+                    match &name[..] {
+                        #(#constant_names => Ok(#constructs),)*
+                        name => Err(duchess::Error::JvmInternal(format!(#unknown_constant, name))),
+                    }
+                }
+            }
         ))
     }
 
-    fn to_rust_variants(&self) -> Result<Vec<ToRustVariant>, syn::Error> {
+    /// Emits the `ToJava` impls for a Rust enum that mirrors a Java enum,
+    /// converting each variant to the constant of the same name.
+    fn try_derive_to_java_java_enum(
+        &self,
+        root_path: &JavaPath,
+        root_class: &JavapClassInfo,
+    ) -> Result<proc_macro2::TokenStream, syn::Error> {
+        let constants = self.java_enum_constants(root_path, root_class)?;
+        let root_class_name = root_class.name.to_module_name(root_path.span);
+
+        let to_java_bodies: Vec<_> = constants
+            .iter()
+            .map(|(variant, constant)| {
+                let pattern = variant.pat();
+                let constant = constant.to_ident(variant.ast().ident.span());
+                quote_spanned!(self.span() =>
+                    #pattern => #root_class_name::#constant().do_jni(jvm)
+                )
+            })
+            .collect();
+
+        Ok(self.to_java_impl(root_class, &to_java_bodies))
+    }
+
+    /// Pairs each variant of the Rust enum with the Java enum constant of the same name.
+    /// Variants can be named like the constant (`RED`) or in Rust style (`Red`).
+    fn java_enum_constants<'v>(
+        &'v self,
+        root_path: &JavaPath,
+        root_class: &JavapClassInfo,
+    ) -> Result<Vec<(&'v VariantInfo<'v>, Id)>, syn::Error> {
+        let class_info = root_class.to_class_info(root_path.span);
+        let constants: Vec<&Id> = class_info.enum_constants().map(|f| &f.name).collect();
+
         self.input
             .variants()
             .iter()
             .map(|variant| {
-                let selector =
-                    self.find_method_selector(variant.ast().ident.span(), variant.ast().attrs)?;
-                // We're not constructing Java objects in ToRust, so we just need the class name
-                // and shouldn't error if the class has multiple constructors that need
-                // disambiguation!
-                let class = self
-                    .reflector
-                    .reflect(&selector.class_name(), selector.class_span())?;
-                Ok::<_, syn::Error>(ToRustVariant {
-                    variant,
-                    selector,
-                    class,
-                })
+                let ident = &variant.ast().ident;
+                if !variant.ast().fields.is_empty() {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "variants of an enum mirroring Java enum `{}` cannot have fields",
+                            root_class.name
+                        ),
+                    ));
+                }
+
+                let name = ident.to_string();
+                let screaming_name = name.to_screaming_snake_case();
+                match constants
+                    .iter()
+                    .find(|c| c[..] == name || c[..] == screaming_name)
+                {
+                    Some(&c) => Ok((variant, c.clone())),
+                    None => Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "no constant named `{screaming_name}` in Java enum `{}`",
+                            root_class.name
+                        ),
+                    )),
+                }
             })
+            .collect()
+    }
+
+    fn to_rust_variants(&self) -> Result<Vec<ToRustVariant>, syn::Error> {
+        self.input
+            .variants()
+            .iter()
+            .map(|variant| self.to_rust_variant(variant))
             .collect::<Result<Vec<_>, _>>()
     }
 
+    fn to_rust_variant<'v>(
+        &self,
+        variant: &'v VariantInfo<'v>,
+    ) -> Result<ToRustVariant<'v>, syn::Error> {
+        let selector =
+            self.find_method_selector(variant.ast().ident.span(), variant.ast().attrs)?;
+        // We're not constructing Java objects in ToRust, so we just need the class name
+        // and shouldn't error if the class has multiple constructors that need
+        // disambiguation!
+        let class = self
+            .reflector
+            .reflect(&selector.class_name(), selector.class_span())?;
+        Ok(ToRustVariant {
+            variant,
+            selector,
+            class,
+        })
+    }
+
     /// Generates the code to create this variant as part of a `ToRust` impl.
     /// Assumes `self` is the java type and `jvm` is in scope.
     fn variant_to_rust(
         &self,
         obj: TokenStream,
         variant: &VariantInfo,
+        class: &JavapClassInfo,
     ) -> Result<proc_macro2::TokenStream, syn::Error> {
+        if self.is_newtype(variant)? {
+            let field = &variant.ast().fields.iter().next().unwrap();
+            let with = self.newtype_field_attrs(field)?.with;
+            let value = match with {
+                Some(with) => {
+                    quote_spanned!(field.span() => #with::to_rust(#obj.execute_with(jvm)?))
+                }
+                None => quote_spanned!(field.span() => #obj.execute_with(jvm)?),
+            };
+            return Ok(variant.construct(|_, _| value.clone()));
+        }
+
+        let rename_all = self.container_attrs()?.rename_all;
+
+        // Fields of records are read through the accessor of the matching component.
+        let record_class = (class.kind == ClassKind::Record)
+            .then(|| class.to_class_info(variant.ast().ident.span()));
+        let record_components = record_class
+            .as_ref()
+            .map(|class_info| self.record_components(variant, class_info, rename_all))
+            .transpose()?;
+
         // For each field, construct an expression we will use to initialize its value.
         let mut initializers = VecDeque::new();
-        for field in variant.ast().fields {
-            if let Some(name) = &field.ident {
-                if name == "this" {
+        for (index, field) in variant.ast().fields.iter().enumerate() {
+            let span = field.span();
+            let attrs = FieldAttrs::parse(field)?;
+
+            if attrs.skip {
+                initializers.push_back(quote_spanned!(span => ::core::default::Default::default()));
+                continue;
+            }
+
+            let (getter, return_ty) = match &record_components {
+                Some(components) if attrs.getter.is_none() => {
+                    let component = components[index].unwrap();
+                    (
+                        component.name.to_snake_case().to_ident(span),
+                        Some(component.ty.clone()),
+                    )
+                }
+                _ if field.ident.as_ref().is_some_and(|name| name == "this") => {
                     // Special case for fields named this
-                    initializers.push_back(quote_spanned!(name.span() => #obj.execute_with(jvm)?));
-                } else if self.is_option(&field.ty) {
-                    initializers.push_back(quote_spanned!(name.span() =>
-                    #obj
-                        .#name()
-                        .execute_with(jvm)?
-                    ));
-                } else {
-                    initializers.push_back(quote_spanned!(name.span() =>
-                    #obj
-                        .#name()
-                        .assert_not_null()
-                        .execute_with(jvm)?
-                    ));
+                    initializers.push_back(quote_spanned!(span => #obj.execute_with(jvm)?));
+                    continue;
                 }
-            } else {
-                // FIXME: We should probably support something like
-                // `#[duchess::args(foo, bar, bar)]` ?
-                return Err(syn::Error::new(
-                    field.span(),
-                    "tuple structs not yet supported",
-                ));
-            }
+                _ => self.find_getter(class, field, &attrs, rename_all)?,
+            };
+
+            let value = quote_spanned!(span => #obj.#getter());
+            initializers.push_back(match (&return_ty, &attrs.with) {
+                (Some(Type::Scalar(_)), None) => quote_spanned!(span =>
+                    #value.execute_with(jvm)?
+                ),
+                (Some(Type::Scalar(_)), Some(with)) => quote_spanned!(span =>
+                    #with::to_rust(#value.execute_with(jvm)?)
+                ),
+                (_, Some(with)) if attrs.default => quote_spanned!(span =>
+                    ::core::option::Option::map_or_else(
+                        #value.execute_with(jvm)?,
+                        ::core::default::Default::default,
+                        #with::to_rust,
+                    )
+                ),
+                (_, Some(with)) => quote_spanned!(span =>
+                    #with::to_rust(#value.assert_not_null().execute_with(jvm)?)
+                ),
+                (_, None) if self.is_option(&field.ty) => quote_spanned!(span =>
+                    #value.execute_with(jvm)?
+                ),
+                (_, None) if attrs.default => quote_spanned!(span =>
+                    ::core::option::Option::unwrap_or_default(#value.execute_with(jvm)?)
+                ),
+                (_, None) => quote_spanned!(span =>
+                    #value.assert_not_null().execute_with(jvm)?
+                ),
+            });
         }
 
         let mut counter = 0;
@@ -318,6 +772,25 @@ impl Driver<'_> {
             ));
         }
 
+        // A newtype converts its field to the Java class directly.
+        if self.is_newtype(variant)? {
+            let binding = &variant.bindings()[0];
+            let class_name = self
+                .find_method_selector(variant_span, variant.ast().attrs)?
+                .class_name()
+                .to_module_name(variant_span);
+            let value = match self.newtype_field_attrs(binding.ast())?.with {
+                Some(with) => quote_spanned!(binding.span()=> &#with::to_java(#binding)),
+                None => quote_spanned!(binding.span()=> #binding),
+            };
+            let pattern = variant.pat();
+            return Ok(quote_spanned!(self.span() =>
+                #pattern => {
+                    duchess::ToJava::to_java::<#class_name>(#value).assert_not_null().upcast().do_jni(jvm)
+                }
+            ));
+        }
+
         // Otherwise, we will construct a call to `java::package::Class::new` where
         // the arguments are taken from each field. One challenge is that we have to
         // know the constructor so we can find the expected types, since we need
@@ -325,30 +798,25 @@ impl Driver<'_> {
 
         let method_selector = self.find_method_selector(variant_span, variant.ast().attrs)?;
 
-        let reflected_method = self.reflector.reflect_method(&method_selector)?;
-
-        if !reflected_method.is_static() {
-            return Err(syn::Error::new(
-                method_selector.span(),
-                "selected method is not a constructor or a static method",
-            ));
-        }
-
-        // We are going to pass each field as an argument to the method,
-        // so there have to be the same number.
-        //
-        // FIXME: Variadic methods in Java?
+        let (reflected_method, bindings) =
+            match self.record_canonical_constructor(&method_selector, variant)? {
+                Some(r) => r,
+                None => {
+                    // Skipped fields are not passed to the constructor or static method.
+                    let bindings = variant
+                        .bindings()
+                        .iter()
+                        .map(|b| Ok((b, FieldAttrs::parse(b.ast())?)))
+                        .filter(|r| !matches!(r, Ok((_, attrs)) if attrs.skip))
+                        .map(|r| r.map(|(b, _)| b))
+                        .collect::<Result<Vec<_>, syn::Error>>()?;
+                    (
+                        self.reflect_static_method(&method_selector, bindings.len())?,
+                        bindings,
+                    )
+                }
+            };
         let method_arguments = reflected_method.argument_tys();
-        if method_arguments.len() != variant.ast().fields.len() {
-            return Err(syn::Error::new(
-                method_selector.span(),
-                format!(
-                    "selected method or constructor has {} arguments, but there are {} fields",
-                    method_arguments.len(),
-                    variant.ast().fields.len()
-                ),
-            ));
-        }
 
         // We don't (yet?) support methods with generic arguments, because we'd have to figure
         // out what their value should be so that we can specify them as part of the `.to_java::<J>()`
@@ -369,19 +837,12 @@ impl Driver<'_> {
         );
 
         let args = signature.forbid_capture(|signature| {
-            variant
-                .bindings()
+            bindings
                 .iter()
                 .zip(method_arguments.iter())
                 .map(|(binding, t)| {
-                    Ok::<_, syn::Error>(match t {
-                        // deref scalar inputs to bare value
-                        Type::Scalar(_) => quote_spanned!(binding.span()=> *#binding),
-                        Type::Ref(_) | Type::Repeat(_) => {
-                            let java_ty = signature.java_ty_rs(t)?;
-                            quote_spanned!(binding.span()=> duchess::ToJava::to_java::<#java_ty>(#binding))
-                        }
-                    })
+                    let attrs = FieldAttrs::parse(binding.ast())?;
+                    self.argument_to_java(signature, &quote!(#binding), t, &attrs)
                 })
                 .collect::<Result<Vec<_>, _>>()
         })?;
@@ -403,6 +864,493 @@ impl Driver<'_> {
         ))
     }
 
+    /// Converts the (borrowed) Rust `value` of a field into an argument of Java type `ty`.
+    fn argument_to_java(
+        &self,
+        signature: &mut Signature,
+        value: &TokenStream,
+        ty: &Type,
+        attrs: &FieldAttrs,
+    ) -> Result<TokenStream, syn::Error> {
+        // A field with a `with` converter is passed as the value the converter returns.
+        let converted = match &attrs.with {
+            Some(with) => quote_spanned!(value.span()=> &#with::to_java(#value)),
+            None => value.clone(),
+        };
+        Ok(match ty {
+            // deref scalar inputs to bare value
+            Type::Scalar(_) => match &attrs.with {
+                Some(with) => quote_spanned!(value.span()=> #with::to_java(#value)),
+                None => quote_spanned!(value.span()=> *#value),
+            },
+            // varargs of scalars accept a reference to a collection of scalars as is
+            Type::Repeat(e) if e.is_scalar() => converted,
+            // varargs of objects accept a collection of values convertible to Java
+            Type::Repeat(e) => {
+                let java_ty = signature.java_ty_rs(e)?;
+                quote_spanned!(value.span()=>
+                    ::core::iter::IntoIterator::into_iter(#converted)
+                        .map(|e| duchess::ToJava::to_java::<#java_ty>(e))
+                )
+            }
+            Type::Ref(_) => {
+                let java_ty = signature.java_ty_rs(ty)?;
+                quote_spanned!(value.span()=> duchess::ToJava::to_java::<#java_ty>(#converted))
+            }
+        })
+    }
+
+    /// Reflects the constructor or static method selected by `method_selector`,
+    /// which will be passed each of the `field_count` fields that are not skipped as an argument.
+    fn reflect_static_method(
+        &self,
+        method_selector: &MethodSelector,
+        field_count: usize,
+    ) -> Result<ReflectedMethod, syn::Error> {
+        let reflected_method = self.reflector.reflect_method(method_selector)?;
+
+        if !reflected_method.is_static() {
+            return Err(syn::Error::new(
+                method_selector.span(),
+                "selected method is not a constructor or a static method",
+            ));
+        }
+
+        // We are going to pass each field as an argument to the method,
+        // so there have to be the same number. A trailing varargs parameter
+        // is given a single field holding a collection of the arguments.
+        let method_arguments = reflected_method.argument_tys();
+        if method_arguments.len() != field_count {
+            return Err(syn::Error::new(
+                method_selector.span(),
+                format!(
+                    "selected method or constructor has {} arguments, but there are {} fields",
+                    method_arguments.len(),
+                    field_count
+                ),
+            ));
+        }
+
+        Ok(reflected_method)
+    }
+
+    /// If `method_selector` names a record class, selects its canonical constructor
+    /// and orders the fields of `variant` to match the record components it takes.
+    fn record_canonical_constructor<'v>(
+        &self,
+        method_selector: &MethodSelector,
+        variant: &'v VariantInfo<'v>,
+    ) -> Result<Option<(ReflectedMethod, Vec<&'v BindingInfo<'v>>)>, syn::Error> {
+        let MethodSelector::ClassName(class_path) = method_selector else {
+            return Ok(None);
+        };
+        let class = self
+            .reflector
+            .reflect(&class_path.to_dot_id(), class_path.span)?;
+        if class.kind != ClassKind::Record {
+            return Ok(None);
+        }
+
+        let class_info = class.to_class_info(class_path.span);
+        let Some(constructor) = class_info.canonical_constructor() else {
+            return Err(syn::Error::new(
+                class_path.span,
+                format!("no canonical constructor found for record `{}`", class.name),
+            ));
+        };
+
+        let field_components =
+            self.record_components(variant, &class_info, self.container_attrs()?.rename_all)?;
+        let bindings = class_info
+            .record_components()
+            .map(|component| {
+                match field_components
+                    .iter()
+                    .position(|c| c.is_some_and(|c| c.name == component.name))
+                {
+                    Some(index) => Ok(&variant.bindings()[index]),
+                    None => Err(syn::Error::new(
+                        variant.ast().ident.span(),
+                        format!(
+                            "no field for component `{}` of record `{}`",
+                            component.name, class.name
+                        ),
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // `ClassInfo` holds a `Span`, so it is neither `Send` nor `Sync`, but `ReflectedMethod`
+        // requires an `Arc` and it never leaves the thread expanding the derive.
+        #[allow(clippy::arc_with_non_send_sync)]
+        let class_info = Arc::new(class_info);
+        Ok(Some((
+            ReflectedMethod::Constructor(class_info, constructor),
+            bindings,
+        )))
+    }
+
+    /// Pairs each field of `variant` with the record component of the same name.
+    /// Fields can be named like the component (`firstName`) or in Rust style (`first_name`),
+    /// or renamed with `#[java(rename_all = "...")]`. Fields of tuple structs are paired
+    /// with the components in order. Skipped fields have no component.
+    fn record_components<'c>(
+        &self,
+        variant: &VariantInfo,
+        class_info: &'c ClassInfo,
+        rename_all: Option<RenameRule>,
+    ) -> Result<Vec<Option<&'c Field>>, syn::Error> {
+        // Tuple fields are paired with the components in order, not counting skipped fields.
+        let mut position = 0;
+        variant
+            .ast()
+            .fields
+            .iter()
+            .map(|field| {
+                if FieldAttrs::parse(field)?.skip {
+                    return Ok(None);
+                }
+
+                let Some(ident) = &field.ident else {
+                    position += 1;
+                    return match class_info.record_components().nth(position - 1) {
+                        Some(c) => Ok(Some(c)),
+                        None => Err(syn::Error::new(
+                            field.span(),
+                            format!(
+                                "record `{}` has only {} components",
+                                class_info.name,
+                                class_info.record_components().count()
+                            ),
+                        )),
+                    };
+                };
+
+                let name = ident.to_string();
+                let component = match rename_all {
+                    Some(rule) => {
+                        let name = rule.apply(&name);
+                        class_info.record_components().find(|c| c.name[..] == name)
+                    }
+                    None => class_info
+                        .record_components()
+                        .find(|c| c.name[..] == name || c.name.to_snake_case()[..] == name),
+                };
+                match component {
+                    Some(c) => Ok(Some(c)),
+                    None => Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "no component named `{}` in record `{}`",
+                            rename_all.map_or(name.clone(), |rule| rule.apply(&name)),
+                            class_info.name
+                        ),
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    /// Selects the zero-argument method that `ToRust` calls to read `field`,
+    /// returning the name of the Rust method and (if it was reflected) its return type.
+    ///
+    /// An explicit `#[java(getter = "...")]` wins. Otherwise we look, in order, for a method
+    /// named like the field, a bean-style `getX` method and, for `boolean` fields, an `isX`
+    /// method, searching the class and whichever of its superclasses were reflected.
+    /// If none is found we fall back to a method named like the field.
+    /// Fields of tuple structs have no name, so they need an explicit getter.
+    fn find_getter(
+        &self,
+        class: &JavapClassInfo,
+        field: &syn::Field,
+        attrs: &FieldAttrs,
+        rename_all: Option<RenameRule>,
+    ) -> Result<(syn::Ident, Option<Type>), syn::Error> {
+        let classes = self.class_and_superclasses(class);
+        let getters = || {
+            classes
+                .iter()
+                .flat_map(|c| c.methods.iter())
+                .filter(|m| !m.flags.is_static && m.argument_tys.is_empty())
+        };
+
+        if let Some(getter) = &attrs.getter {
+            let getter_name = getter.value();
+            let return_ty = getters()
+                .find(|m| m.name[..] == getter_name)
+                .and_then(|m| m.return_ty.clone());
+            return Ok((
+                Id::from(getter_name)
+                    .to_snake_case()
+                    .to_ident(getter.span()),
+                return_ty,
+            ));
+        }
+
+        let Some(name) = &field.ident else {
+            return Err(syn::Error::new(
+                field.span(),
+                r#"fields of tuple structs need a `#[java(getter = "...")]` to be converted to Rust"#,
+            ));
+        };
+
+        // Without `rename_all`, we compare in snake case, so that `user_id` finds `userId()`,
+        // `getUserId()` or `isUserId()`. Otherwise we compare the Java names exactly.
+        let field_name = name.to_string();
+        let (candidates, normalize): ([String; 3], fn(&Id) -> String) = match rename_all {
+            Some(rule) => {
+                let java_name = rule.apply(&field_name);
+                let capitalized = java_name.to_pascal_case();
+                (
+                    [
+                        java_name,
+                        format!("get{capitalized}"),
+                        format!("is{capitalized}"),
+                    ],
+                    |id| id.to_string(),
+                )
+            }
+            None => (
+                [
+                    field_name.clone(),
+                    format!("get_{field_name}"),
+                    format!("is_{field_name}"),
+                ],
+                |id| id.to_snake_case().to_string(),
+            ),
+        };
+
+        for (index, candidate) in candidates.iter().enumerate() {
+            let is_bean_boolean = index == 2;
+            if let Some(method) = getters().find(|m| {
+                normalize(&m.name) == *candidate
+                    && (!is_bean_boolean
+                        || matches!(m.return_ty, Some(Type::Scalar(ScalarType::Boolean))))
+            }) {
+                return Ok((
+                    method.name.to_snake_case().to_ident(name.span()),
+                    method.return_ty.clone(),
+                ));
+            }
+        }
+
+        Ok((
+            Id::from(candidates[0].as_str())
+                .to_snake_case()
+                .to_ident(name.span()),
+            None,
+        ))
+    }
+
+    /// Selects the setter of the builder `step` for `field`.
+    ///
+    /// An explicit `#[java(setter = "...")]` wins. Otherwise we look, in order, for a
+    /// `withX` method, a `setX` method and a method named like the field.
+    fn find_setter(
+        &self,
+        step: &ClassRef,
+        field: &syn::Field,
+        attrs: &FieldAttrs,
+        rename_all: Option<RenameRule>,
+    ) -> Result<BuilderMethod, syn::Error> {
+        let is_setter = |m: &Method, name: &str, normalize: fn(&Id) -> String| {
+            m.argument_tys.len() == 1 && normalize(&m.name) == name
+        };
+
+        if let Some(setter) = &attrs.setter {
+            return self
+                .find_builder_method(step, setter.span(), |m| {
+                    is_setter(m, &setter.value(), |id| id.to_string())
+                })?
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        setter.span(),
+                        format!(
+                            "no setter `{}` with one argument found on builder `{step}`",
+                            setter.value()
+                        ),
+                    )
+                });
+        }
+
+        let Some(name) = &field.ident else {
+            return Err(syn::Error::new(
+                field.span(),
+                r#"fields of tuple structs need a `#[java(setter = "...")]` to be passed to a builder"#,
+            ));
+        };
+
+        // As for getters, we compare in snake case unless `rename_all` is given.
+        let field_name = name.to_string();
+        let (candidates, normalize): ([String; 3], fn(&Id) -> String) = match rename_all {
+            Some(rule) => {
+                let java_name = rule.apply(&field_name);
+                let capitalized = java_name.to_pascal_case();
+                (
+                    [
+                        format!("with{capitalized}"),
+                        format!("set{capitalized}"),
+                        java_name,
+                    ],
+                    |id| id.to_string(),
+                )
+            }
+            None => (
+                [
+                    format!("with_{field_name}"),
+                    format!("set_{field_name}"),
+                    field_name.clone(),
+                ],
+                |id| id.to_snake_case().to_string(),
+            ),
+        };
+        for candidate in &candidates {
+            if let Some(setter) =
+                self.find_builder_method(step, name.span(), |m| is_setter(m, candidate, normalize))?
+            {
+                return Ok(setter);
+            }
+        }
+
+        Err(syn::Error::new(
+            name.span(),
+            format!(
+                "no setter for field `{field_name}` found on builder `{step}`, \
+                 use `#[java(setter = \"...\")]` to select one"
+            ),
+        ))
+    }
+
+    /// Searches the builder `step` and its supertypes for an instance method matching `filter`,
+    /// substituting the generic arguments of `step` into its signature.
+    /// The builder itself must have been reflected, e.g. by including it in a `java_package!`.
+    fn find_builder_method(
+        &self,
+        step: &ClassRef,
+        span: Span,
+        filter: impl Fn(&Method) -> bool,
+    ) -> Result<Option<BuilderMethod>, syn::Error> {
+        let mut queue = VecDeque::from([step.clone()]);
+        while let Some(class_ref) = queue.pop_front() {
+            let class = match self.reflector.reflect(&class_ref.name, span) {
+                Ok(class) => class,
+                Err(_) if class_ref != *step => continue,
+                Err(_) => {
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                        "no reflected value for builder `{step}`, is it part of a `java_package!`?"
+                    ),
+                    ))
+                }
+            };
+
+            let subst: Substitution<'_> = class
+                .generics
+                .iter()
+                .map(|g| &g.id)
+                .zip(&class_ref.generics)
+                .collect();
+            if let Some(method) = class
+                .methods
+                .iter()
+                .find(|m| !m.flags.is_static && filter(m))
+            {
+                return Ok(Some(BuilderMethod {
+                    name: method.name.clone(),
+                    argument_tys: method.argument_tys.substitute(&subst),
+                    return_ty: method.return_ty.as_ref().map(|t| t.substitute(&subst)),
+                }));
+            }
+            queue.extend(
+                class
+                    .extends
+                    .iter()
+                    .chain(&class.implements)
+                    .map(|c| c.substitute(&subst)),
+            );
+        }
+        Ok(None)
+    }
+
+    /// A tuple struct or variant marked `#[java(transparent)]` is a newtype, which converts
+    /// through the Java class of its single field.
+    fn is_newtype(&self, variant: &VariantInfo) -> Result<bool, syn::Error> {
+        let Some(attr) = variant.ast().attrs.iter().find(|a| is_transparent_attr(a)) else {
+            return Ok(false);
+        };
+        let is_single_field = match variant.ast().fields {
+            syn::Fields::Unnamed(fields) => fields.unnamed.len() == 1,
+            _ => false,
+        };
+        if !is_single_field {
+            return Err(syn::Error::new(
+                attr.meta.require_list()?.tokens.span(),
+                "`transparent` requires a tuple struct or variant with a single field",
+            ));
+        }
+        match self.find_method_selector(variant.ast().ident.span(), variant.ast().attrs)? {
+            MethodSelector::ClassName(_) => Ok(true),
+            selector => Err(syn::Error::new(
+                selector.class_span(),
+                "`transparent` requires a class name, not a method",
+            )),
+        }
+    }
+
+    /// Parses the attributes of the field of a newtype, which can only have a `with` converter.
+    fn newtype_field_attrs(&self, field: &syn::Field) -> Result<FieldAttrs, syn::Error> {
+        let attrs = FieldAttrs::parse(field)?;
+        if attrs.getter.is_some() || attrs.setter.is_some() || attrs.skip || attrs.default {
+            return Err(syn::Error::new(
+                field.span(),
+                "the field of a newtype can only have a `with` attribute",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// Returns `class` followed by its superclasses, stopping at the first one
+    /// that was not reflected.
+    fn class_and_superclasses(&self, class: &JavapClassInfo) -> Vec<Arc<JavapClassInfo>> {
+        let mut classes = vec![Arc::new(class.clone())];
+        while let Some(superclass) = classes.last().unwrap().extends.first() {
+            match self.reflector.reflect(&superclass.name, Span::call_site()) {
+                Ok(superclass) => classes.push(superclass),
+                Err(_) => break,
+            }
+        }
+        classes
+    }
+
+    /// Reads the container-level options given as `#[java(option = ...)]`.
+    fn container_attrs(&self) -> Result<ContainerAttrs, syn::Error> {
+        let mut attrs = ContainerAttrs::default();
+        for attr in &self.input.ast().attrs {
+            if !attr.path().is_ident("java") || !is_java_option_attr(attr) {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    attrs.rename_all = Some(RenameRule::parse(&meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("builder") {
+                    let tokens = parse_until_comma(meta.value()?)?;
+                    attrs.builder = Some(Parser::from(tokens).parse()?);
+                } else if meta.path.is_ident("build") {
+                    attrs.build = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("transparent") {
+                    // Read by `is_newtype`, since enum variants can be newtypes too
+                } else {
+                    return Err(meta.error(
+                        "unsupported option, expected `rename_all`, `builder`, `build` or `transparent`",
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(attrs)
+    }
+
     fn find_method_selector(
         &self,
         span: Span,
@@ -416,7 +1364,7 @@ impl Driver<'_> {
     fn find_java_attr<T: Parse>(&self, span: Span, attrs: &[Attribute]) -> Result<T, syn::Error> {
         for attr in attrs {
             let path = attr.meta.path();
-            if path.is_ident("java") {
+            if path.is_ident("java") && !is_java_option_attr(attr) {
                 let list = attr.meta.require_list()?;
                 if let syn::MacroDelimiter::Paren(_) = list.delimiter {
                     return Ok(Parser::from(list.tokens.clone()).parse()?);
@@ -435,7 +1383,7 @@ impl Driver<'_> {
 
     fn is_option(&self, ty: &syn::Type) -> bool {
         match ty {
-            syn::Type::Path(p) => p.path.is_ident("Option"),
+            syn::Type::Path(p) => p.path.segments.last().is_some_and(|s| s.ident == "Option"),
             _ => false,
         }
     }
@@ -522,6 +1470,165 @@ fn unique_variant_classes<'a, 'i>(
     Ok(classes)
 }
 
+/// Returns true if `attrs` include a `#[java(...)]` attribute naming a class or method.
+fn has_java_class_attr(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|a| a.path().is_ident("java") && !is_java_option_attr(a))
+}
+
+/// Returns true for `#[java(...)]` attributes holding options like `rename_all = "..."`
+/// or `transparent`, as opposed to the class or method selector.
+fn is_java_option_attr(attr: &Attribute) -> bool {
+    let syn::Meta::List(list) = &attr.meta else {
+        return false;
+    };
+    if is_transparent_attr(attr) {
+        return true;
+    }
+    let mut tokens = list.tokens.clone().into_iter();
+    matches!(
+        (tokens.next(), tokens.next()),
+        (Some(proc_macro2::TokenTree::Ident(_)), Some(proc_macro2::TokenTree::Punct(p))) if p.as_char() == '='
+    )
+}
+
+/// Returns true for the `#[java(transparent)]` attribute of a newtype.
+fn is_transparent_attr(attr: &Attribute) -> bool {
+    let syn::Meta::List(list) = &attr.meta else {
+        return false;
+    };
+    let mut tokens = list.tokens.clone().into_iter();
+    attr.path().is_ident("java")
+        && matches!(
+            (tokens.next(), tokens.next()),
+            (Some(proc_macro2::TokenTree::Ident(ident)), None) if ident == "transparent"
+        )
+}
+
+/// Options given in `#[java(...)]` attributes on the struct or enum.
+#[derive(Default)]
+struct ContainerAttrs {
+    /// `rename_all = "camelCase"`: how field names are converted to Java names.
+    rename_all: Option<RenameRule>,
+    /// `builder = class.Name::method`: `ToJava` creates the object through this builder.
+    builder: Option<MethodSelector>,
+    /// `build = method`: the method that ends the builder chain (default: `build`).
+    build: Option<syn::Ident>,
+}
+
+/// Collects the tokens of an option value, up to the next comma.
+fn parse_until_comma(input: syn::parse::ParseStream) -> Result<TokenStream, syn::Error> {
+    let mut tokens = TokenStream::new();
+    while !input.is_empty() && !input.peek(syn::Token![,]) {
+        tokens.extend([input.parse::<proc_macro2::TokenTree>()?]);
+    }
+    Ok(tokens)
+}
+
+/// A method of a builder, with the generic arguments of the builder substituted.
+struct BuilderMethod {
+    name: Id,
+    argument_tys: Vec<Type>,
+    return_ty: Option<Type>,
+}
+
+/// Options given in `#[java(...)]` attributes on a field.
+#[derive(Default)]
+struct FieldAttrs {
+    /// `getter = "getFoo"`: the Java method that `ToRust` calls to read the field.
+    getter: Option<syn::LitStr>,
+    /// `setter = "withFoo"`: the builder method that `ToJava` calls to pass the field.
+    setter: Option<syn::LitStr>,
+    /// `skip`: the field is not passed to Java and `ToRust` initializes it with `Default::default()`.
+    skip: bool,
+    /// `default`: `ToRust` initializes the field with `Default::default()` if the getter returns null.
+    default: bool,
+    /// `with = path`: the field is converted by `path::to_rust` and `path::to_java`.
+    with: Option<syn::Path>,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> Result<Self, syn::Error> {
+        let mut attrs = FieldAttrs::default();
+        for attr in &field.attrs {
+            if !attr.path().is_ident("java") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("getter") {
+                    attrs.getter = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("setter") {
+                    attrs.setter = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                } else if meta.path.is_ident("default") {
+                    attrs.default = true;
+                } else if meta.path.is_ident("with") {
+                    attrs.with = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error(
+                        "unsupported field attribute, expected `getter`, `setter`, `skip`, `default` or `with`",
+                    ));
+                }
+                Ok(())
+            })?;
+        }
+
+        if attrs.skip && (attrs.getter.is_some() || attrs.setter.is_some() || attrs.with.is_some())
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "skipped fields cannot have a `getter`, `setter` or `with` attribute",
+            ));
+        }
+        Ok(attrs)
+    }
+}
+
+/// The case conversions supported by `#[java(rename_all = "...")]`.
+#[derive(Copy, Clone)]
+enum RenameRule {
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+    Lower,
+    Upper,
+}
+
+impl RenameRule {
+    fn parse(lit: &syn::LitStr) -> Result<Self, syn::Error> {
+        match &lit.value()[..] {
+            "camelCase" => Ok(RenameRule::Camel),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            other => Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "unknown rename rule `{other}`, expected one of `camelCase`, `PascalCase`, \
+                     `snake_case`, `SCREAMING_SNAKE_CASE`, `lowercase` or `UPPERCASE`"
+                ),
+            )),
+        }
+    }
+
+    /// Converts the name of a Rust field to the corresponding Java name.
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Camel => name.to_camel_case(),
+            RenameRule::Pascal => name.to_pascal_case(),
+            RenameRule::Snake => name.to_snake_case(),
+            RenameRule::ScreamingSnake => name.to_screaming_snake_case(),
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+        }
+    }
+}
+
 struct ToRustVariant<'i> {
     variant: &'i VariantInfo<'i>,
     selector: MethodSelector,
@@ -56,3 +56,5 @@ pub fn java_function(args: TokenStream, input: TokenStream) -> TokenStream {
 synstructure::decl_derive!([ToRust, attributes(java)] => derive::derive_to_rust);
 
 synstructure::decl_derive!([ToJava, attributes(java)] => derive::derive_to_java);
+
+synstructure::decl_derive!([JavaException, attributes(java)] => derive::derive_java_exception);
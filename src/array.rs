@@ -1,14 +1,14 @@
-use std::{env, marker::PhantomData};
+use std::{env, marker::PhantomData, ops::Range};
 
 use crate::{
     cast::Upcast,
     java::{self, lang::Class},
-    jvm::{self, JavaScalar, JavaView, JvmBuilder},
+    jvm::{self, JavaScalar, JavaView, JvmBuilder, JvmRefOp},
     raw,
     semver_unstable::{FromRef, JavaObjectExt},
     to_java::ToJavaImpl,
-    AsJRef, Error, IntoRust, JDeref, JavaObject, JavaType, Jvm, JvmOp, Local, Nullable,
-    ScalarMethod, TryJDeref, VoidMethod,
+    AsJRef, Error, IntoJava, IntoRust, IntoScalar, JDeref, JavaObject, JavaType, Jvm, JvmOp, Local,
+    Nullable, ScalarMethod, TryJDeref, VoidMethod,
 };
 
 pub struct JavaArray<T> {
@@ -87,6 +87,17 @@ impl<T: JavaType> TryJDeref for JavaArray<T> {
 pub trait JavaArrayExt<T: JavaType>: JvmOp {
     type Length: ScalarMethod<jni_sys::jsize>;
     fn length(self) -> Self::Length;
+
+    /// Reads the element at `index`: a scalar for arrays of primitives,
+    /// and an `Option<Local<T>>` (`None` for `null`) for arrays of objects.
+    fn get(self, index: usize) -> GetElement<Self, T>;
+
+    /// Reads the elements in `range` into a `Vec` (see [`get`](Self::get) for their representation).
+    fn get_region(self, range: Range<usize>) -> GetRegion<Self, T>;
+
+    /// Stores `value` at `index`: an [`IntoScalar`](crate::IntoScalar) value for arrays of primitives,
+    /// and an [`IntoJava`](crate::IntoJava) value for arrays of objects.
+    fn set<V>(self, index: usize, value: V) -> SetElement<Self, T, V>;
 }
 
 pub trait JavaArrayModificationExt<T: JavaType, RT: JavaScalar>: JvmOp {
@@ -107,6 +118,31 @@ where
             element: PhantomData,
         }
     }
+
+    fn get(self, index: usize) -> GetElement<Self, T> {
+        GetElement {
+            this: self,
+            element: PhantomData,
+            index,
+        }
+    }
+
+    fn get_region(self, range: Range<usize>) -> GetRegion<Self, T> {
+        GetRegion {
+            this: self,
+            element: PhantomData,
+            range,
+        }
+    }
+
+    fn set<V>(self, index: usize, value: V) -> SetElement<Self, T, V> {
+        SetElement {
+            this: self,
+            element: PhantomData,
+            index,
+            value,
+        }
+    }
 }
 
 #[derive_where::derive_where(Clone)]
@@ -116,6 +152,30 @@ pub struct Length<This: JvmOp, T> {
     element: PhantomData<T>,
 }
 
+#[derive_where::derive_where(Clone)]
+#[derive_where(Copy; This: Copy)]
+pub struct GetElement<This: JvmOp, T> {
+    this: This,
+    element: PhantomData<T>,
+    index: usize,
+}
+
+#[derive_where::derive_where(Clone)]
+pub struct GetRegion<This: JvmOp, T> {
+    this: This,
+    element: PhantomData<T>,
+    range: Range<usize>,
+}
+
+#[derive_where::derive_where(Clone; This: Clone, V: Clone)]
+#[derive_where(Copy; This: Copy, V: Copy)]
+pub struct SetElement<This: JvmOp, T, V> {
+    this: This,
+    element: PhantomData<T>,
+    index: usize,
+    value: V,
+}
+
 #[derive_where::derive_where(Clone)]
 #[derive_where(Copy; This: Copy)]
 pub struct SetArrayRegion<'a, This: JvmOp, T, RT> {
@@ -145,6 +205,406 @@ where
     }
 }
 
+/// Gives a closure direct access to the elements of a Java primitive array, without copying them.
+///
+/// The elements are pinned with `GetPrimitiveArrayCritical` while the closure runs.
+/// The JVM may block garbage collection in the meantime, so the closure should be short
+/// and must not call back into Java: any operation executed from within it fails.
+/// Like every [`JvmOp`], the resulting operation must be `Clone`, so the closure
+/// usually hands its results back by returning them.
+///
+/// A few JNI calls do not go through a [`JvmOp`], so nothing stops them from happening
+/// within the closure even though JNI forbids them there:
+///
+/// * dropping a [`Java`](crate::Java), [`Local`](crate::Local) or [`JavaWeak`](crate::JavaWeak)
+///   reference (`DeleteGlobalRef`, `DeleteLocalRef` or `DeleteWeakGlobalRef`), for example one
+///   moved into the closure: return it instead, so that it is dropped after the region ends;
+/// * raw JNI calls made by `unsafe` code.
+///
+/// The same applies to [`ByteBufferExt`](crate::java::ByteBufferExt).
+pub trait JavaArrayCriticalExt<RT: JavaScalar>: JvmOp {
+    /// Calls `op` with the elements of the array; changes to the array are not possible.
+    fn with_critical<F, R>(self, op: F) -> WithCritical<Self, RT, F>
+    where
+        F: FnOnce(&[RT]) -> R + Clone;
+
+    /// Calls `op` with the elements of the array; any changes are written back
+    /// to the array once `op` returns.
+    ///
+    /// # Safety
+    ///
+    /// `op` gets exclusive access to the elements, but references to a Java array can be
+    /// shared between threads. While the returned operation executes, the caller must ensure
+    /// that the array is not read or written by anything else, be it Java code or Rust code
+    /// on another thread.
+    unsafe fn with_critical_mut<F, R>(self, op: F) -> WithCriticalMut<Self, RT, F>
+    where
+        F: FnOnce(&mut [RT]) -> R + Clone;
+}
+
+impl<This, RT> JavaArrayCriticalExt<RT> for This
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<RT>>,
+    RT: JavaScalar,
+{
+    fn with_critical<F, R>(self, op: F) -> WithCritical<Self, RT, F>
+    where
+        F: FnOnce(&[RT]) -> R + Clone,
+    {
+        WithCritical {
+            this: self,
+            element: PhantomData,
+            op,
+        }
+    }
+
+    unsafe fn with_critical_mut<F, R>(self, op: F) -> WithCriticalMut<Self, RT, F>
+    where
+        F: FnOnce(&mut [RT]) -> R + Clone,
+    {
+        WithCriticalMut {
+            this: self,
+            element: PhantomData,
+            op,
+        }
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct WithCritical<This: JvmOp, RT, F> {
+    this: This,
+    element: PhantomData<RT>,
+    op: F,
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct WithCriticalMut<This: JvmOp, RT, F> {
+    this: This,
+    element: PhantomData<RT>,
+    op: F,
+}
+
+impl<This, RT, F, R> JvmOp for WithCritical<This, RT, F>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<RT>>,
+    RT: JavaScalar,
+    F: FnOnce(&[RT]) -> R + Clone,
+{
+    type Output<'jvm> = R;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let this = this.as_jref()?;
+        let op = self.op;
+        // Nothing was changed, so there is nothing to copy back
+        critical_region(jvm, this, jni_sys::JNI_ABORT, |elements| op(elements))
+    }
+}
+
+impl<This, RT, F, R> JvmOp for WithCriticalMut<This, RT, F>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<RT>>,
+    RT: JavaScalar,
+    F: FnOnce(&mut [RT]) -> R + Clone,
+{
+    type Output<'jvm> = R;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let this = this.as_jref()?;
+        critical_region(jvm, this, 0, self.op)
+    }
+}
+
+/// Pins the elements of `array` for the duration of `op`, then releases them with `mode`
+/// (`0` to copy back any changes, `JNI_ABORT` to discard them).
+fn critical_region<'jvm, RT, R>(
+    jvm: &mut Jvm<'jvm>,
+    array: &JavaArray<RT>,
+    mode: jni_sys::jint,
+    op: impl FnOnce(&mut [RT]) -> R,
+) -> crate::LocalResult<'jvm, R>
+where
+    RT: JavaScalar,
+{
+    let len = array.length().do_jni(jvm)?;
+    let array = array.as_raw().as_ptr();
+    let env = jvm.env();
+
+    let elements = unsafe {
+        // SAFETY: `array` is a live local reference to a primitive array
+        env.invoke_unchecked(
+            |env| env.GetPrimitiveArrayCritical,
+            |env, f| f(env, array, std::ptr::null_mut()),
+        )
+    };
+    if elements.is_null() {
+        // The JVM couldn't pin or copy the array (e.g. out of memory), which is reported as an exception
+        env.check_exception()?;
+        return Err(Error::JvmInternal(format!(
+            "failed to access the elements of an array of length {len}"
+        )));
+    }
+
+    // Releases the elements even if `op` panics, discarding any changes in that case
+    struct Release<'jvm> {
+        env: raw::EnvPtr<'jvm>,
+        array: jni_sys::jobject,
+        elements: *mut std::ffi::c_void,
+        mode: jni_sys::jint,
+    }
+
+    impl Drop for Release<'_> {
+        fn drop(&mut self) {
+            let mode = if std::thread::panicking() {
+                jni_sys::JNI_ABORT
+            } else {
+                self.mode
+            };
+            unsafe {
+                // SAFETY: `elements` was returned by `GetPrimitiveArrayCritical` for `array`
+                self.env.invoke_unchecked(
+                    |env| env.ReleasePrimitiveArrayCritical,
+                    |env, f| f(env, self.array, self.elements, mode),
+                );
+            }
+        }
+    }
+
+    let release = Release {
+        env,
+        array,
+        elements,
+        mode,
+    };
+    let _critical = crate::thread::enter_critical();
+
+    // SAFETY: the JVM hands out `len` elements of the Java type matching `RT`, which are
+    // pinned until `release` is dropped. Nothing else writes them while `op` runs, as required
+    // by `with_critical_mut`. `op` cannot reach `jvm` while we hold it mutably, and `_critical`
+    // makes any other attempt to call into the JVM on this thread fail, except for the calls
+    // listed on `JavaArrayCriticalExt`.
+    let elements =
+        unsafe { std::slice::from_raw_parts_mut(release.elements.cast::<RT>(), len as usize) };
+    Ok(op(elements))
+}
+
+/// Converts an index into a `jsize`. Indices that do not fit are clamped,
+/// so that the JVM reports them as out of bounds.
+fn jsize(index: usize) -> jni_sys::jsize {
+    index.try_into().unwrap_or(jni_sys::jsize::MAX)
+}
+
+/// Allocates a Java array of `len` objects, all `null`.
+fn new_object_array<'jvm, T>(
+    jvm: &mut Jvm<'jvm>,
+    len: usize,
+) -> crate::LocalResult<'jvm, Local<'jvm, JavaArray<T>>>
+where
+    T: JavaObject,
+{
+    let Ok(len) = len.try_into() else {
+        return Err(Error::SliceTooLong(len));
+    };
+
+    let class = T::class(jvm)?;
+    let array: Option<Local<JavaArray<T>>> = unsafe {
+        jvm.env().invoke(
+            |env| env.NewObjectArray,
+            |env, f| f(env, len, class.as_raw().as_ptr(), std::ptr::null_mut()),
+        )
+    }?;
+    let Some(array) = array else {
+        // NewObjectArray should never return null unless an exception occurred (which we've already checked)
+        return Err(Error::JvmInternal(format!(
+            "failed to allocate object array of length {len}"
+        )));
+    };
+    Ok(array)
+}
+
+fn get_object_element<'jvm, T>(
+    jvm: &mut Jvm<'jvm>,
+    array: &JavaArray<T>,
+    index: usize,
+) -> crate::LocalResult<'jvm, Option<Local<'jvm, T>>>
+where
+    T: JavaObject,
+{
+    unsafe {
+        jvm.env().invoke(
+            |env| env.GetObjectArrayElement,
+            |env, f| f(env, array.as_raw().as_ptr(), jsize(index)),
+        )
+    }
+}
+
+fn set_object_element<'jvm, T>(
+    jvm: &mut Jvm<'jvm>,
+    array: &JavaArray<T>,
+    index: usize,
+    element: Option<&T>,
+) -> crate::LocalResult<'jvm, ()>
+where
+    T: JavaObject,
+{
+    let element = match element {
+        Some(e) => e.as_raw().as_ptr(),
+        None => std::ptr::null_mut(),
+    };
+    unsafe {
+        jvm.env().invoke(
+            |env| env.SetObjectArrayElement,
+            |env, f| f(env, array.as_raw().as_ptr(), jsize(index), element),
+        )
+    }
+}
+
+impl<This, T> JvmOp for GetElement<This, T>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<T>>,
+    T: JavaObject,
+{
+    type Output<'jvm> = Option<Local<'jvm, T>>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        get_object_element(jvm, this.as_jref()?, self.index)
+    }
+}
+
+impl<This, T> JvmOp for GetRegion<This, T>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<T>>,
+    T: JavaObject,
+{
+    type Output<'jvm> = Vec<Option<Local<'jvm, T>>>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let this = this.as_jref()?;
+        self.range
+            .map(|index| get_object_element(jvm, this, index))
+            .collect()
+    }
+}
+
+impl<This, T, V> JvmOp for SetElement<This, T, V>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<T>>,
+    T: JavaObject,
+    V: IntoJava<T> + Clone,
+{
+    type Output<'jvm> = ();
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let value = self.value.into_op().into_as_jref(jvm)?;
+        set_object_element(jvm, this.as_jref()?, self.index, value.as_jref().ok())
+    }
+}
+
+impl<J, R> IntoRust<Vec<R>> for &JavaArray<J>
+where
+    J: JavaObject,
+    for<'jvm> Option<Local<'jvm, J>>: IntoRust<R>,
+{
+    fn into_rust<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Vec<R>> {
+        let len = self.length().do_jni(jvm)?;
+        (0..len as usize)
+            .map(|index| get_object_element(jvm, self, index)?.into_rust(jvm))
+            .collect()
+    }
+}
+
+impl<E, JE> ToJavaImpl<java::Array<JE>> for [E]
+where
+    E: ToJavaImpl<JE>,
+    JE: Upcast<java::lang::Object> + Upcast<JE>,
+{
+    fn to_java_impl<'jvm>(
+        rust: &Self,
+        jvm: &mut Jvm<'jvm>,
+    ) -> crate::LocalResult<'jvm, Option<Local<'jvm, java::Array<JE>>>> {
+        let array = new_object_array(jvm, rust.len())?;
+        for (index, element) in rust.iter().enumerate() {
+            let element = E::to_java_impl(element, jvm)?;
+            set_object_element(jvm, &array, index, element.as_deref())?;
+        }
+        Ok(Some(array))
+    }
+}
+
+impl<E, JE> ToJavaImpl<java::Array<JE>> for Vec<E>
+where
+    E: ToJavaImpl<JE>,
+    JE: Upcast<java::lang::Object> + Upcast<JE>,
+{
+    fn to_java_impl<'jvm>(
+        rust: &Self,
+        jvm: &mut Jvm<'jvm>,
+    ) -> crate::LocalResult<'jvm, Option<Local<'jvm, java::Array<JE>>>> {
+        <[E]>::to_java_impl(rust, jvm)
+    }
+}
+
+/// Creates a Java array of objects from the results of `elements`.
+/// Used to pass a Rust collection as the varargs argument of a Java method.
+#[derive_where::derive_where(Clone; J: Clone)]
+pub struct ObjectArrayOp<T, J> {
+    elements: Vec<J>,
+    element: PhantomData<T>,
+}
+
+impl<T, J> ObjectArrayOp<T, J> {
+    pub(crate) fn new(elements: Vec<J>) -> Self {
+        ObjectArrayOp {
+            elements,
+            element: PhantomData,
+        }
+    }
+}
+
+impl<T, J> JvmOp for ObjectArrayOp<T, J>
+where
+    T: JavaObject,
+    J: JvmRefOp<T>,
+{
+    type Output<'jvm> = Local<'jvm, JavaArray<T>>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let array = new_object_array(jvm, self.elements.len())?;
+
+        for (index, element) in self.elements.into_iter().enumerate() {
+            let element = element.into_as_jref(jvm)?;
+            set_object_element(jvm, &array, index, element.as_jref().ok())?;
+        }
+
+        Ok(array)
+    }
+}
+
+/// Creates a Java array of scalars from `elements`.
+/// Used to pass a Rust collection as the varargs argument of a Java method.
+#[derive(Clone)]
+pub struct ScalarArrayOp<T> {
+    elements: Vec<T>,
+}
+
+impl<T> ScalarArrayOp<T> {
+    pub(crate) fn new(elements: Vec<T>) -> Self {
+        ScalarArrayOp { elements }
+    }
+}
+
 macro_rules! primitive_array {
     ($([$rust:ty]: $java_name:literal $java_ty:ident $new_fn:ident $get_fn:ident $set_fn:ident,)*) => {
         $(
@@ -186,6 +646,14 @@ macro_rules! primitive_array {
                 }
             }
 
+            impl JvmOp for ScalarArrayOp<$rust> {
+                type Output<'jvm> = Local<'jvm, JavaArray<$rust>>;
+
+                fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+                    self.elements[..].do_jni(jvm)
+                }
+            }
+
             impl ToJavaImpl<java::Array<$rust>> for [$rust] {
                 fn to_java_impl<'jvm>(
                     rust: &Self,
@@ -225,6 +693,91 @@ macro_rules! primitive_array {
                 }
             }
 
+            impl<This> JvmOp for GetElement<This, $rust>
+            where
+                This: JvmOp,
+                for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<$rust>>,
+            {
+                type Output<'jvm> = $rust;
+
+                fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+                    let this = self.this.do_jni(jvm)?;
+                    let this = this.as_jref()?.as_raw();
+
+                    let mut element = <$rust>::default();
+                    let () = unsafe {
+                        jvm.env().invoke(|env| env.$get_fn, |env, f| f(
+                            env,
+                            this.as_ptr(),
+                            jsize(self.index),
+                            1,
+                            (&mut element as *mut $rust).cast::<jni_sys::$java_ty>(),
+                        ))
+                    }?;
+                    Ok(element)
+                }
+            }
+
+            impl<This> JvmOp for GetRegion<This, $rust>
+            where
+                This: JvmOp,
+                for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<$rust>>,
+            {
+                type Output<'jvm> = Vec<$rust>;
+
+                fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+                    let this = self.this.do_jni(jvm)?;
+                    let this = this.as_jref()?;
+                    let array_len = this.length().do_jni(jvm)?;
+
+                    let start = jsize(self.range.start);
+                    let len = jsize(self.range.len());
+                    // If the region is out of bounds the JVM throws without writing anything,
+                    // so there is no need to allocate more than the array holds
+                    let mut vec = Vec::<$rust>::with_capacity(len.min(array_len) as usize);
+
+                    unsafe {
+                        // SAFETY: the JVM only writes the `len` elements when they are all in bounds,
+                        // in which case `len <= array_len`
+                        let () = jvm.env().invoke(|env| env.$get_fn, |env, f| f(
+                            env,
+                            this.as_raw().as_ptr(),
+                            start,
+                            len,
+                            vec.as_mut_ptr().cast::<jni_sys::$java_ty>(),
+                        ))?;
+                        vec.set_len(len as usize);
+                    }
+
+                    Ok(vec)
+                }
+            }
+
+            impl<This, V> JvmOp for SetElement<This, $rust, V>
+            where
+                This: JvmOp,
+                for<'jvm> This::Output<'jvm>: AsJRef<JavaArray<$rust>>,
+                V: IntoScalar<$rust> + Clone,
+            {
+                type Output<'jvm> = ();
+
+                fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+                    let this = self.this.do_jni(jvm)?;
+                    let this = this.as_jref()?.as_raw();
+                    let value: $rust = self.value.into_op().do_jni(jvm)?;
+
+                    unsafe {
+                        jvm.env().invoke(|env| env.$set_fn, |env, f| f(
+                            env,
+                            this.as_ptr(),
+                            jsize(self.index),
+                            1,
+                            (&value as *const $rust).cast::<jni_sys::$java_ty>(),
+                        ))
+                    }
+                }
+            }
+
             impl<This> JavaArrayModificationExt<JavaArray<$rust>, $rust> for This
             where
                 This: JvmOp,
@@ -0,0 +1,305 @@
+//! Direct `java.nio.ByteBuffer`s, which let Java and Rust access the same memory without copying it.
+//!
+//! Rust memory is handed to Java with [`DirectByteBuffer`], and the memory of an existing
+//! direct buffer is borrowed from Rust with [`JavaByteBufferExt`].
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    callback::{self, Callback},
+    java::nio::ByteBuffer,
+    jvm::JavaObjectExt,
+    thread,
+    to_java::ToJavaImpl,
+    AsJRef, Error, Jvm, JvmOp, Local, LocalResult,
+};
+
+/// Rust-owned memory that can back a direct `java.nio.ByteBuffer`.
+///
+/// # Safety
+///
+/// The memory described by [`as_mut_ptr`](Self::as_mut_ptr) and [`len`](Self::len) must stay valid,
+/// and at the same address, for as long as the value is alive, even if the value itself is moved.
+/// Unless [`READ_ONLY`](Self::READ_ONLY) is true, Java may write to that memory.
+pub unsafe trait BufferMemory: Send + 'static {
+    /// Whether the memory must not be modified, in which case the `ByteBuffer` is read-only.
+    const READ_ONLY: bool;
+
+    fn as_mut_ptr(&mut self) -> *mut u8;
+
+    fn len(&self) -> usize;
+}
+
+unsafe impl BufferMemory for Box<[u8]> {
+    const READ_ONLY: bool = false;
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        <[u8]>::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+}
+
+unsafe impl BufferMemory for Vec<u8> {
+    const READ_ONLY: bool = false;
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        Vec::as_mut_ptr(self)
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+}
+
+unsafe impl BufferMemory for &'static [u8] {
+    const READ_ONLY: bool = true;
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        <[u8]>::as_ptr(self).cast_mut()
+    }
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl BufferMemory for bytes::Bytes {
+    const READ_ONLY: bool = true;
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        <[u8]>::as_ptr(self).cast_mut()
+    }
+
+    fn len(&self) -> usize {
+        bytes::Bytes::len(self)
+    }
+}
+
+/// Wraps Rust-owned memory so that it can be converted into a direct `java.nio.ByteBuffer`
+/// with [`ToJava::to_java`](crate::ToJava::to_java), without copying it.
+///
+/// The memory is dropped once the `ByteBuffer` (and any view of it, like a slice or a duplicate)
+/// has been garbage collected. Read-only memory, like `&'static [u8]` or `bytes::Bytes`
+/// (with the `bytes` feature), produces a read-only buffer.
+///
+/// A `DirectByteBuffer` can only be converted once; later conversions return [`Error::JvmInternal`].
+pub struct DirectByteBuffer<B> {
+    memory: Mutex<Option<B>>,
+}
+
+impl<B> DirectByteBuffer<B>
+where
+    B: BufferMemory,
+{
+    pub fn new(memory: B) -> Self {
+        DirectByteBuffer {
+            memory: Mutex::new(Some(memory)),
+        }
+    }
+}
+
+impl<B> ToJavaImpl<ByteBuffer> for DirectByteBuffer<B>
+where
+    B: BufferMemory,
+{
+    fn to_java_impl<'jvm>(
+        rust: &Self,
+        jvm: &mut Jvm<'jvm>,
+    ) -> LocalResult<'jvm, Option<Local<'jvm, ByteBuffer>>> {
+        let Some(mut memory) = rust.memory.lock().unwrap().take() else {
+            return Err(Error::JvmInternal(
+                "`DirectByteBuffer` was already converted to a `ByteBuffer`".to_string(),
+            ));
+        };
+
+        let len = memory.len();
+        let Ok(capacity) = jni_sys::jlong::try_from(len) else {
+            return Err(Error::SliceTooLong(len));
+        };
+        let address = memory.as_mut_ptr();
+
+        // If anything below fails, `release` is dropped (and the memory with it) before
+        // the buffer has been handed out to anyone.
+        let release: Arc<dyn Callback> = Arc::new(Release {
+            memory: Mutex::new(Some(memory)),
+        });
+
+        let buffer: Option<Local<'jvm, ByteBuffer>> = unsafe {
+            // SAFETY: `address` is valid for `capacity` bytes until `release` runs
+            jvm.env().invoke(
+                |env| env.NewDirectByteBuffer,
+                |env, f| f(env, address.cast(), capacity),
+            )
+        }?;
+        let Some(buffer) = buffer else {
+            // The JVM does not support direct buffers
+            return Err(Error::JvmInternal(
+                "failed to create a direct `java.nio.ByteBuffer`".to_string(),
+            ));
+        };
+
+        callback::run_when_unreachable(jvm, &*buffer, release)?;
+
+        if B::READ_ONLY {
+            // Views of a direct buffer keep the original buffer reachable,
+            // so the memory lives until the view is collected too
+            let read_only = buffer.as_read_only_buffer().assert_not_null().do_jni(jvm)?;
+            return Ok(Some(read_only));
+        }
+        Ok(Some(buffer))
+    }
+}
+
+/// Drops the memory of a [`DirectByteBuffer`] once Java no longer uses it.
+struct Release<B> {
+    memory: Mutex<Option<B>>,
+}
+
+impl<B> Callback for Release<B>
+where
+    B: BufferMemory,
+{
+    fn run(self: Arc<Self>) {
+        drop(self.memory.lock().unwrap().take());
+    }
+}
+
+/// Gives a closure direct access to the memory of a direct `java.nio.ByteBuffer`.
+///
+/// The slice covers the whole capacity of the buffer, regardless of its position and limit.
+/// Java code running on other threads may access the buffer at the same time, so both methods
+/// are `unsafe`: it is up to the caller to coordinate with it. The closure must not call back
+/// into Java: any operation executed from within it fails, except for the JNI calls listed on
+/// [`ArrayCriticalExt`](crate::java::ArrayCriticalExt). Like every [`JvmOp`], the resulting
+/// operation must be `Clone`, so the closure usually hands its results back by returning them.
+pub trait JavaByteBufferExt: JvmOp {
+    /// Calls `op` with the contents of the buffer.
+    /// Fails if the buffer is not direct.
+    ///
+    /// # Safety
+    ///
+    /// While the returned operation executes, the caller must ensure that the memory of the
+    /// buffer is not written by anything else, be it Java code or Rust code on another thread.
+    unsafe fn with_direct_bytes<F, R>(self, op: F) -> WithDirectBytes<Self, F>
+    where
+        F: FnOnce(&[u8]) -> R + Clone;
+
+    /// Calls `op` with the contents of the buffer, which it may modify.
+    /// Fails if the buffer is not direct or is read-only.
+    ///
+    /// # Safety
+    ///
+    /// While the returned operation executes, the caller must ensure that the memory of the
+    /// buffer is not read or written by anything else, be it Java code or Rust code on another thread.
+    unsafe fn with_direct_bytes_mut<F, R>(self, op: F) -> WithDirectBytesMut<Self, F>
+    where
+        F: FnOnce(&mut [u8]) -> R + Clone;
+}
+
+impl<This> JavaByteBufferExt for This
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<ByteBuffer>,
+{
+    unsafe fn with_direct_bytes<F, R>(self, op: F) -> WithDirectBytes<Self, F>
+    where
+        F: FnOnce(&[u8]) -> R + Clone,
+    {
+        WithDirectBytes { this: self, op }
+    }
+
+    unsafe fn with_direct_bytes_mut<F, R>(self, op: F) -> WithDirectBytesMut<Self, F>
+    where
+        F: FnOnce(&mut [u8]) -> R + Clone,
+    {
+        WithDirectBytesMut { this: self, op }
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct WithDirectBytes<This: JvmOp, F> {
+    this: This,
+    op: F,
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct WithDirectBytesMut<This: JvmOp, F> {
+    this: This,
+    op: F,
+}
+
+impl<This, F, R> JvmOp for WithDirectBytes<This, F>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<ByteBuffer>,
+    F: FnOnce(&[u8]) -> R + Clone,
+{
+    type Output<'jvm> = R;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let (address, capacity) = direct_memory(jvm, this.as_jref()?)?;
+        let _critical = thread::enter_critical();
+
+        // SAFETY: see `direct_memory`; nothing else writes the memory while `op` runs,
+        // as required by `with_direct_bytes`
+        let bytes = unsafe { std::slice::from_raw_parts(address, capacity) };
+        Ok((self.op)(bytes))
+    }
+}
+
+impl<This, F, R> JvmOp for WithDirectBytesMut<This, F>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<ByteBuffer>,
+    F: FnOnce(&mut [u8]) -> R + Clone,
+{
+    type Output<'jvm> = R;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let this = this.as_jref()?;
+        if this.is_read_only().do_jni(jvm)? {
+            return Err(Error::JvmInternal(
+                "cannot modify a read-only `java.nio.ByteBuffer`".to_string(),
+            ));
+        }
+        let (address, capacity) = direct_memory(jvm, this)?;
+        let _critical = thread::enter_critical();
+
+        // SAFETY: see `direct_memory`; nothing else reads or writes the memory while `op` runs,
+        // as required by `with_direct_bytes_mut`
+        let bytes = unsafe { std::slice::from_raw_parts_mut(address, capacity) };
+        Ok((self.op)(bytes))
+    }
+}
+
+/// Returns the address and capacity of the direct buffer `buffer`.
+///
+/// The JVM guarantees that a direct buffer's memory is valid for `capacity` bytes
+/// while the buffer is reachable. Callers hold `buffer` while they use the memory,
+/// and enter a critical region so that operations executed meanwhile fail.
+fn direct_memory<'jvm>(
+    jvm: &mut Jvm<'jvm>,
+    buffer: &ByteBuffer,
+) -> LocalResult<'jvm, (*mut u8, usize)> {
+    let env = jvm.env();
+    let buffer = buffer.as_raw().as_ptr();
+
+    let address =
+        unsafe { env.invoke_unchecked(|env| env.GetDirectBufferAddress, |env, f| f(env, buffer)) };
+    let capacity =
+        unsafe { env.invoke_unchecked(|env| env.GetDirectBufferCapacity, |env, f| f(env, buffer)) };
+    if address.is_null() || capacity < 0 {
+        return Err(Error::JvmInternal(
+            "`java.nio.ByteBuffer` is not a direct buffer".to_string(),
+        ));
+    }
+    Ok((address.cast::<u8>(), capacity as usize))
+}
@@ -0,0 +1,245 @@
+//! Java objects whose behavior is implemented in Rust.
+//!
+//! These are instances of `duchess.RustCallback` (see `java/duchess/RustCallback.java`),
+//! a class that duchess compiles at build time and defines in the JVM the first time it is needed.
+//! Each instance owns an `Arc<dyn Callback>` that is dropped once the Java object is collected.
+
+use std::{
+    ffi::{c_char, c_void, CStr},
+    sync::Arc,
+};
+
+use jni_sys::{jclass, jlong};
+use once_cell::sync::OnceCell;
+
+use crate::{
+    find::{find_constructor, find_method},
+    java::{
+        self,
+        lang::{Class, Object},
+    },
+    jvm::{native_function_returning_unit, JavaObjectExt},
+    raw::{EnvPtr, IntoJniValue, MethodPtr},
+    Java, JavaObject, Jvm, Local, LocalResult,
+};
+
+const CALLBACK_CLASS_NAME: &CStr = c"duchess/RustCallback";
+const CALLBACK_CLASS_BYTES: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/java/duchess/RustCallback.class"));
+
+const DROP_CLASS_NAME: &CStr = c"duchess/RustCallback$Drop";
+const DROP_CLASS_BYTES: &[u8] = include_bytes!(concat!(
+    env!("OUT_DIR"),
+    "/java/duchess/RustCallback$Drop.class"
+));
+
+/// Rust implementation of the Java interfaces implemented by `duchess.RustCallback`.
+///
+/// Methods may be invoked from any Java thread, possibly concurrently. Java interface methods
+/// that are not overridden do nothing.
+pub(crate) trait Callback: Send + Sync + 'static {
+    /// Implements `java.lang.Runnable::run`.
+    fn run(self: Arc<Self>) {}
+
+    /// Implements `java.util.function.BiConsumer::accept`.
+    fn accept(self: Arc<Self>, _t: Option<&Object>, _u: Option<&Object>) {}
+}
+
+/// Creates a new `java.lang.Runnable` that invokes [`Callback::run`].
+pub(crate) fn runnable<'jvm>(
+    jvm: &mut Jvm<'jvm>,
+    callback: Arc<dyn Callback>,
+) -> LocalResult<'jvm, Local<'jvm, java::lang::Runnable>> {
+    // SAFETY: `duchess.RustCallback` implements `java.lang.Runnable`
+    unsafe { new_callback(jvm, callback) }
+}
+
+/// Creates a new `java.util.function.BiConsumer` that invokes [`Callback::accept`].
+pub(crate) fn bi_consumer<'jvm, T, U>(
+    jvm: &mut Jvm<'jvm>,
+    callback: Arc<dyn Callback>,
+) -> LocalResult<'jvm, Local<'jvm, java::util::function::BiConsumer<T, U>>>
+where
+    T: JavaObject,
+    U: JavaObject,
+{
+    // SAFETY: `duchess.RustCallback` implements the erased `java.util.function.BiConsumer`
+    unsafe { new_callback(jvm, callback) }
+}
+
+/// Invokes [`Callback::run`] once `object` has become unreachable, from a `java.lang.ref.Cleaner` thread.
+pub(crate) fn run_when_unreachable<'jvm>(
+    jvm: &mut Jvm<'jvm>,
+    object: &impl JavaObject,
+    callback: Arc<dyn Callback>,
+) -> LocalResult<'jvm, ()> {
+    let action = runnable(jvm, callback)?;
+    let class = callback_class(jvm)?;
+
+    static METHOD: OnceCell<MethodPtr> = OnceCell::new();
+    let method = METHOD.get_or_try_init(|| {
+        find_method(
+            jvm,
+            &class,
+            c"runWhenUnreachable",
+            c"(Ljava/lang/Object;Ljava/lang/Runnable;)V",
+            true,
+        )
+    })?;
+
+    unsafe {
+        jvm.env().invoke(
+            |env| env.CallStaticVoidMethodA,
+            |env, f| {
+                f(
+                    env,
+                    class.as_raw().as_ptr(),
+                    method.as_ptr(),
+                    [object.into_jni_value(), (&*action).into_jni_value()].as_ptr(),
+                )
+            },
+        )
+    }
+}
+
+/// # Safety
+///
+/// `duchess.RustCallback` must be an instance of `J`.
+unsafe fn new_callback<'jvm, J: JavaObject>(
+    jvm: &mut Jvm<'jvm>,
+    callback: Arc<dyn Callback>,
+) -> LocalResult<'jvm, Local<'jvm, J>> {
+    let class = callback_class(jvm)?;
+
+    static CONSTRUCTOR: OnceCell<MethodPtr> = OnceCell::new();
+    let constructor = CONSTRUCTOR.get_or_try_init(|| find_constructor(jvm, &class, c"(J)V"))?;
+
+    // Ownership of the box is passed to the Java object, which releases it in `native_drop`.
+    let pointer = Box::into_raw(Box::new(callback));
+    let obj: LocalResult<'jvm, Option<Local<'jvm, J>>> = jvm.env().invoke(
+        |env| env.NewObjectA,
+        |env, f| {
+            f(
+                env,
+                class.as_raw().as_ptr(),
+                constructor.as_ptr(),
+                [(pointer as jlong).into_jni_value()].as_ptr(),
+            )
+        },
+    );
+
+    match obj {
+        Ok(Some(obj)) => Ok(obj),
+        result => {
+            // The constructor did not complete, so the Java object never took ownership.
+            drop(Box::from_raw(pointer));
+            result?;
+            Err(crate::Error::JvmInternal(
+                "failed to create new `duchess.RustCallback`".to_string(),
+            ))
+        }
+    }
+}
+
+/// Returns the `duchess.RustCallback` class, defining it (and registering its native methods) if needed.
+fn callback_class<'jvm>(jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Local<'jvm, Class>> {
+    static CLASS: OnceCell<Java<Class>> = OnceCell::new();
+    let class = CLASS.get_or_try_init(|| -> LocalResult<'jvm, _> {
+        let class = define_class(jvm, CALLBACK_CLASS_NAME, CALLBACK_CLASS_BYTES)?;
+        define_class(jvm, DROP_CLASS_NAME, DROP_CLASS_BYTES)?;
+
+        let native_methods = [
+            native_method(
+                c"nativeRun",
+                c"(J)V",
+                native_run as unsafe extern "C" fn(_, _, _) as *mut c_void,
+            ),
+            native_method(
+                c"nativeAccept",
+                c"(JLjava/lang/Object;Ljava/lang/Object;)V",
+                native_accept as unsafe extern "C" fn(_, _, _, _, _) as *mut c_void,
+            ),
+            native_method(
+                c"nativeDrop",
+                c"(J)V",
+                native_drop as unsafe extern "C" fn(_, _, _) as *mut c_void,
+            ),
+        ];
+        // SAFETY: the names and signatures above match the native methods declared in `RustCallback.java`
+        unsafe {
+            jvm.env()
+                .register_native_methods(class.as_raw(), &native_methods)?;
+        }
+
+        Ok(jvm.global(&class))
+    })?;
+    Ok(jvm.local(class))
+}
+
+fn define_class<'jvm>(
+    jvm: &mut Jvm<'jvm>,
+    name: &CStr,
+    bytes: &[u8],
+) -> LocalResult<'jvm, Local<'jvm, Class>> {
+    // SAFETY: `name` is nul-terminated and `bytes` is valid for reads of `bytes.len()`
+    let class: Option<Local<'jvm, Class>> = unsafe {
+        jvm.env().invoke(
+            |env| env.DefineClass,
+            |env, f| {
+                f(
+                    env,
+                    name.as_ptr(),
+                    std::ptr::null_mut(), // bootstrap class loader
+                    bytes.as_ptr().cast(),
+                    bytes.len() as i32,
+                )
+            },
+        )
+    }?;
+    class.ok_or_else(|| {
+        crate::Error::JvmInternal(format!(
+            "failed to define class `{}`",
+            name.to_string_lossy()
+        ))
+    })
+}
+
+fn native_method(
+    name: &'static CStr,
+    signature: &'static CStr,
+    pointer: *mut c_void,
+) -> jni_sys::JNINativeMethod {
+    jni_sys::JNINativeMethod {
+        name: name.as_ptr() as *mut c_char,
+        signature: signature.as_ptr() as *mut c_char,
+        fnPtr: pointer,
+    }
+}
+
+/// # Safety
+///
+/// `pointer` must have been created by [`new_callback`] and not yet released by [`native_drop`].
+unsafe fn callback_from_pointer(pointer: jlong) -> Arc<dyn Callback> {
+    let callback = &*(pointer as *const Arc<dyn Callback>);
+    callback.clone()
+}
+
+unsafe extern "C" fn native_run(env: EnvPtr<'_>, _this: Option<&Object>, pointer: jlong) {
+    native_function_returning_unit::<(), ()>(env, || callback_from_pointer(pointer).run())
+}
+
+unsafe extern "C" fn native_accept(
+    env: EnvPtr<'_>,
+    _this: Option<&Object>,
+    pointer: jlong,
+    t: Option<&Object>,
+    u: Option<&Object>,
+) {
+    native_function_returning_unit::<(), ()>(env, || callback_from_pointer(pointer).accept(t, u))
+}
+
+unsafe extern "C" fn native_drop(env: EnvPtr<'_>, _class: jclass, pointer: jlong) {
+    native_function_returning_unit::<(), ()>(env, || {
+        drop(Box::from_raw(pointer as *mut Arc<dyn Callback>))
+    })
+}
@@ -0,0 +1,336 @@
+//! Combinators that compose [`JvmOp`]s, so that a whole workflow runs within a single
+//! crossing into the JVM.
+
+use std::marker::PhantomData;
+
+use crate::{
+    java::{self, lang::Throwable},
+    jvm::JvmRefOp,
+    ops::IntoJava,
+    AsJRef, Error, JavaObject, Jvm, JvmOp, Local, LocalResult, TryJDeref,
+};
+
+/// Combinators on [`JvmOp`]s.
+///
+/// This trait is not part of the [`prelude`](crate::prelude), since its methods would take
+/// precedence over Java methods of the same name, such as `Optional.orElse` or `Map.forEach`.
+/// Import it where the combinators are needed.
+pub trait JvmOpExt: JvmOp {
+    /// Transforms the output of this operation with `op`, which also gets access to the [`Jvm`]
+    /// so that it can run further operations with [`do_jni`](JvmOp::do_jni).
+    fn map<F, R>(self, op: F) -> Map<Self, F>
+    where
+        F: for<'jvm> FnOnce(&mut Jvm<'jvm>, Self::Output<'jvm>) -> R + Clone,
+    {
+        Map::new(self, op)
+    }
+
+    /// Like [`map`](Self::map), but `op` may fail, for example because the operations
+    /// it runs throw an exception.
+    fn and_then<F, R>(self, op: F) -> AndThen<Self, F>
+    where
+        F: for<'jvm> FnOnce(&mut Jvm<'jvm>, Self::Output<'jvm>) -> crate::LocalResult<'jvm, R>
+            + Clone,
+    {
+        AndThen::new(self, op)
+    }
+
+    /// Calls `op` with a reference to the output of this operation, then produces the output unchanged.
+    fn inspect<F>(self, op: F) -> Inspect<Self, F>
+    where
+        F: for<'jvm> FnOnce(&Self::Output<'jvm>) + Clone,
+    {
+        Inspect::new(self, op)
+    }
+
+    /// Produces the output of this operation, or, if it is null, the output of `other`.
+    /// `other` is only executed if needed.
+    fn if_null<T, O>(self, other: O) -> IfNull<Self, O>
+    where
+        T: JavaObject,
+        for<'jvm> Self: JvmOp<Output<'jvm>: TryJDeref<Java = T>>,
+        O: IntoJava<T> + Clone,
+    {
+        IfNull::new(self, other)
+    }
+
+    /// Recovers from a failure of this operation (such as a Java exception) by calling `op`
+    /// with the error and producing its result instead.
+    fn or_else<F>(self, op: F) -> OrElse<Self, F>
+    where
+        F: for<'jvm> FnOnce(
+                &mut Jvm<'jvm>,
+                Error<Local<'jvm, Throwable>>,
+            ) -> crate::LocalResult<'jvm, Self::Output<'jvm>>
+            + Clone,
+    {
+        OrElse::new(self, op)
+    }
+
+    /// Calls `op` for each element of the `java.lang.Iterable` produced by this operation,
+    /// stopping at the first error.
+    fn for_each<T, F>(self, op: F) -> ForEach<Self, T, F>
+    where
+        T: JavaObject,
+        for<'jvm> Self::Output<'jvm>: AsJRef<java::lang::Iterable<T>>,
+        F: for<'jvm> FnMut(&mut Jvm<'jvm>, Option<Local<'jvm, T>>) -> crate::LocalResult<'jvm, ()>
+            + Clone,
+    {
+        ForEach::new(self, op)
+    }
+}
+
+impl<This: JvmOp> JvmOpExt for This {}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct Map<This: JvmOp, F> {
+    this: This,
+    op: F,
+}
+
+impl<This: JvmOp, F> Map<This, F> {
+    pub(crate) fn new(this: This, op: F) -> Self {
+        Self { this, op }
+    }
+}
+
+impl<This, F, R> JvmOp for Map<This, F>
+where
+    This: JvmOp,
+    F: for<'jvm> FnOnce(&mut Jvm<'jvm>, This::Output<'jvm>) -> R + Clone,
+{
+    type Output<'jvm> = R;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let output = self.this.do_jni(jvm)?;
+        Ok((self.op)(jvm, output))
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct AndThen<This: JvmOp, F> {
+    this: This,
+    op: F,
+}
+
+impl<This: JvmOp, F> AndThen<This, F> {
+    pub(crate) fn new(this: This, op: F) -> Self {
+        Self { this, op }
+    }
+}
+
+impl<This, F, R> JvmOp for AndThen<This, F>
+where
+    This: JvmOp,
+    F: for<'jvm> FnOnce(&mut Jvm<'jvm>, This::Output<'jvm>) -> LocalResult<'jvm, R> + Clone,
+{
+    type Output<'jvm> = R;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let output = self.this.do_jni(jvm)?;
+        (self.op)(jvm, output)
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct Inspect<This: JvmOp, F> {
+    this: This,
+    op: F,
+}
+
+impl<This: JvmOp, F> Inspect<This, F> {
+    pub(crate) fn new(this: This, op: F) -> Self {
+        Self { this, op }
+    }
+}
+
+impl<This, F> JvmOp for Inspect<This, F>
+where
+    This: JvmOp,
+    F: for<'jvm> FnOnce(&This::Output<'jvm>) + Clone,
+{
+    type Output<'jvm> = This::Output<'jvm>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let output = self.this.do_jni(jvm)?;
+        (self.op)(&output);
+        Ok(output)
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, O: Clone)]
+#[derive_where(Copy; This: Copy, O: Copy)]
+pub struct IfNull<This: JvmOp, O> {
+    this: This,
+    other: O,
+}
+
+impl<This: JvmOp, O> IfNull<This, O> {
+    pub(crate) fn new(this: This, other: O) -> Self {
+        Self { this, other }
+    }
+}
+
+impl<This, O, T> JvmOp for IfNull<This, O>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: TryJDeref<Java = T>,
+    T: JavaObject,
+    O: IntoJava<T> + Clone,
+{
+    type Output<'jvm> = Option<Local<'jvm, T>>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        if let Ok(this) = this.try_jderef() {
+            return Ok(Some(jvm.local(this)));
+        }
+
+        let other = self.other.into_op().into_as_jref(jvm)?;
+        Ok(other.as_jref().ok().map(|other| jvm.local(other)))
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct OrElse<This: JvmOp, F> {
+    this: This,
+    op: F,
+}
+
+impl<This: JvmOp, F> OrElse<This, F> {
+    pub(crate) fn new(this: This, op: F) -> Self {
+        Self { this, op }
+    }
+}
+
+impl<This, F> JvmOp for OrElse<This, F>
+where
+    This: JvmOp,
+    F: for<'jvm> FnOnce(
+            &mut Jvm<'jvm>,
+            Error<Local<'jvm, Throwable>>,
+        ) -> LocalResult<'jvm, This::Output<'jvm>>
+        + Clone,
+{
+    type Output<'jvm> = This::Output<'jvm>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        match self.this.do_jni(jvm) {
+            Ok(output) => Ok(output),
+            Err(error) => (self.op)(jvm, error),
+        }
+    }
+}
+
+#[derive_where::derive_where(Clone; This: Clone, F: Clone)]
+#[derive_where(Copy; This: Copy, F: Copy)]
+pub struct ForEach<This: JvmOp, T, F> {
+    this: This,
+    op: F,
+    phantom: PhantomData<T>,
+}
+
+impl<This: JvmOp, T, F> ForEach<This, T, F> {
+    pub(crate) fn new(this: This, op: F) -> Self {
+        Self {
+            this,
+            op,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<This, T, F> JvmOp for ForEach<This, T, F>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: AsJRef<java::lang::Iterable<T>>,
+    T: JavaObject,
+    F: for<'jvm> FnMut(&mut Jvm<'jvm>, Option<Local<'jvm, T>>) -> LocalResult<'jvm, ()> + Clone,
+{
+    type Output<'jvm> = ();
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let this = self.this.do_jni(jvm)?;
+        let iterator = this.as_jref()?.iterator().assert_not_null().do_jni(jvm)?;
+
+        let mut op = self.op;
+        while iterator.has_next().do_jni(jvm)? {
+            let element = iterator.next().do_jni(jvm)?;
+            op(jvm, element)?;
+        }
+        Ok(())
+    }
+}
+
+/// Combines several operations into one that executes them in order and produces all of their
+/// outputs. `ops` is either a tuple of operations, possibly of different types, or an array of them:
+///
+/// ```rust,no_run
+/// # use duchess::{java, prelude::*};
+/// # fn example(list: &java::util::List<java::lang::String>) -> duchess::Result<()> {
+/// let (size, empty): (i32, bool) = duchess::join((list.size(), list.is_empty())).execute()?;
+/// let sizes: [i32; 2] = duchess::join([list.size(), list.size()]).execute()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Execution stops at the first operation that fails.
+pub fn join<Ops>(ops: Ops) -> Join<Ops>
+where
+    Join<Ops>: JvmOp,
+{
+    Join { ops }
+}
+
+#[derive(Clone, Copy)]
+pub struct Join<Ops> {
+    ops: Ops,
+}
+
+macro_rules! join_tuple {
+    ($($op:ident),*) => {
+        impl<$($op),*> JvmOp for Join<($($op,)*)>
+        where
+            $($op: JvmOp,)*
+        {
+            type Output<'jvm> = ($($op::Output<'jvm>,)*);
+
+            #[allow(non_snake_case)]
+            fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+                let ($($op,)*) = self.ops;
+                Ok(($($op.do_jni(jvm)?,)*))
+            }
+        }
+    };
+}
+
+join_tuple!(A);
+join_tuple!(A, B);
+join_tuple!(A, B, C);
+join_tuple!(A, B, C, D);
+join_tuple!(A, B, C, D, E);
+join_tuple!(A, B, C, D, E, F);
+join_tuple!(A, B, C, D, E, F, G);
+join_tuple!(A, B, C, D, E, F, G, H);
+
+impl<J, const N: usize> JvmOp for Join<[J; N]>
+where
+    J: JvmOp,
+{
+    type Output<'jvm> = [J::Output<'jvm>; N];
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Self::Output<'jvm>> {
+        let mut outputs = Vec::with_capacity(N);
+        for op in self.ops {
+            outputs.push(op.do_jni(jvm)?);
+        }
+        let Ok(outputs) = outputs.try_into() else {
+            unreachable!("one output per operation")
+        };
+        Ok(outputs)
+    }
+}
@@ -3,10 +3,8 @@ use std::{
     result,
 };
 
-use thiserror::Error;
-
 use crate::AsJRef;
-use crate::{java::lang::Throwable, Java, Jvm, JvmOp, Local};
+use crate::{java::lang::Throwable, Java, JavaStackTrace, Jvm, JvmOp, Local};
 
 /// Result returned by most Java operations that may contain a local reference
 /// to a thrown exception.
@@ -16,32 +14,31 @@ pub type LocalResult<'jvm, T> = result::Result<T, Error<Local<'jvm, Throwable>>>
 /// exception as a global reference.
 pub type Result<T> = result::Result<T, Error<Java<Throwable>>>;
 
-#[derive(Error)]
+/// `{}` formats a thrown exception like Java's `Throwable.toString()`, while `{:#}` and
+/// `{:?}` include its stack trace and causes (see [`Error::stack_trace`]).
 pub enum Error<T: AsJRef<Throwable>> {
     /// A reference to an uncaught Java exception
-    #[error("Java invocation threw: {}", try_extract_message(.0))]
     Thrown(T),
 
-    #[error(
-        "slice was too long (`{0}`) to convert to a Java array, which are limited to `i32::MAX`"
-    )]
     SliceTooLong(usize),
 
-    #[error("attempted to deref a null Java object pointer")]
     NullDeref,
 
-    #[error("attempted to nest `Jvm::with` calls")]
-    NestedUsage,
-
-    #[error("JVM already exists")]
     JvmAlreadyExists,
 
+    /// The JVM was shut down with [`crate::Jvm::shutdown`] and can no longer be used
+    JvmShutDown,
+
     #[cfg(feature = "dylibjvm")]
-    #[error(transparent)]
-    UnableToLoadLibjvm(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    UnableToLoadLibjvm(Box<dyn std::error::Error + Send + Sync + 'static>),
 
-    #[error("{0}")]
     JvmInternal(String),
+
+    /// A value could not be converted by [`crate::serde`]
+    Serde(String),
+
+    /// A JVM configuration file could not be loaded, see [`crate::jvm::JvmBuilder::config_file`]
+    InvalidJvmConfig(String),
 }
 
 fn try_extract_message(exception: &impl AsJRef<Throwable>) -> String {
@@ -50,12 +47,100 @@ fn try_extract_message(exception: &impl AsJRef<Throwable>) -> String {
     result().unwrap_or_else(|err| format!("failed to get message: {}", err))
 }
 
+fn try_extract_stack_trace(exception: &impl AsJRef<Throwable>) -> Option<JavaStackTrace> {
+    let result = || -> crate::Result<_> {
+        let exception = exception.as_jref()?;
+        Jvm::with(|jvm| JavaStackTrace::capture(jvm, exception))
+    };
+    match result() {
+        Ok(trace) => Some(trace),
+        Err(err) => {
+            tracing::warn!(?err, "unable to capture Java stack trace");
+            None
+        }
+    }
+}
+
+impl<T> Display for Error<T>
+where
+    T: AsJRef<Throwable>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Thrown(exception) => {
+                let trace = f
+                    .alternate()
+                    .then(|| try_extract_stack_trace(exception))
+                    .flatten();
+                if let Some(trace) = trace {
+                    return write!(f, "Java invocation threw: {trace:#}");
+                }
+                write!(f, "Java invocation threw: {}", try_extract_message(exception))
+            }
+            Error::SliceTooLong(len) => write!(
+                f,
+                "slice was too long (`{len}`) to convert to a Java array, which are limited to `i32::MAX`"
+            ),
+            Error::NullDeref => write!(f, "attempted to deref a null Java object pointer"),
+            Error::JvmAlreadyExists => write!(f, "JVM already exists"),
+            Error::JvmShutDown => write!(f, "JVM has been shut down"),
+            #[cfg(feature = "dylibjvm")]
+            Error::UnableToLoadLibjvm(e) => Display::fmt(e, f),
+            Error::JvmInternal(m) => write!(f, "{m}"),
+            Error::Serde(m) => write!(f, "{m}"),
+            Error::InvalidJvmConfig(m) => write!(f, "invalid JVM configuration: {m}"),
+        }
+    }
+}
+
 impl<T> Debug for Error<T>
 where
     T: AsJRef<Throwable>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Display::fmt(self, f)
+        write!(f, "{self:#}")
+    }
+}
+
+/// `source()` is `None` for a thrown exception: its causes have to be read from the JVM, and the
+/// error only holds the exception itself, so there is nothing for `source()` to borrow them from.
+/// To report the cause chain, report the [`JavaStackTrace`] returned by [`Error::stack_trace`]
+/// instead, whose `source()` follows the Java causes.
+impl<T> std::error::Error for Error<T>
+where
+    T: AsJRef<Throwable>,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "dylibjvm")]
+            Error::UnableToLoadLibjvm(e) => e.source(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "dylibjvm")]
+impl<T> From<Box<dyn std::error::Error + Send + Sync + 'static>> for Error<T>
+where
+    T: AsJRef<Throwable>,
+{
+    fn from(e: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
+        Error::UnableToLoadLibjvm(e)
+    }
+}
+
+impl<T> Error<T>
+where
+    T: AsJRef<Throwable>,
+{
+    /// Reads the stack trace of a thrown exception from the JVM, along with its causes and
+    /// suppressed exceptions. It is `None` for other errors, or if reading it fails (e.g. because
+    /// the JVM has been shut down).
+    pub fn stack_trace(&self) -> Option<JavaStackTrace> {
+        match self {
+            Error::Thrown(exception) => try_extract_stack_trace(exception),
+            _ => None,
+        }
     }
 }
 
@@ -65,11 +150,61 @@ impl<'jvm> Error<Local<'jvm, Throwable>> {
             Error::Thrown(t) => Error::Thrown(jvm.global(&t)),
             Error::SliceTooLong(s) => Error::SliceTooLong(s),
             Error::NullDeref => Error::NullDeref,
-            Error::NestedUsage => Error::NestedUsage,
             Error::JvmAlreadyExists => Error::JvmAlreadyExists,
+            Error::JvmShutDown => Error::JvmShutDown,
             #[cfg(feature = "dylibjvm")]
             Error::UnableToLoadLibjvm(e) => Error::UnableToLoadLibjvm(e),
             Error::JvmInternal(m) => Error::JvmInternal(m),
+            Error::Serde(m) => Error::Serde(m),
+            Error::InvalidJvmConfig(m) => Error::InvalidJvmConfig(m),
+        }
+    }
+}
+
+/// A Rust error type that thrown Java exceptions are converted into.
+///
+/// Derive it with `#[derive(duchess::JavaException)]` on an enum whose variants are annotated with
+/// Java exception classes. A thrown exception is converted into the variant of the most specific
+/// class it is an instance of, and any other error goes to the one variant without a class.
+/// The derive also implements `From<duchess::Error<..>>`, so that `?` converts the errors
+/// returned by [`JvmOp::execute`]:
+///
+/// ```rust,ignore
+/// #[derive(Debug, duchess::JavaException)]
+/// enum ConfigError {
+///     #[java(java.lang.IllegalArgumentException)]
+///     Invalid { get_message: String },
+///     Other(duchess::Error<duchess::Java<duchess::java::lang::Throwable>>),
+/// }
+///
+/// fn load(config: &Java<Config>) -> Result<String, ConfigError> {
+///     Ok(config.load().assert_not_null().execute()?)
+/// }
+/// ```
+pub trait JavaException: Sized {
+    /// Converts `exception` into the variant of the most specific class it is an instance of,
+    /// or returns `None` if there is no such variant.
+    fn from_exception<'jvm>(
+        jvm: &mut Jvm<'jvm>,
+        exception: &Throwable,
+    ) -> crate::LocalResult<'jvm, Option<Self>>;
+
+    /// Wraps an error that no variant matches.
+    fn from_error(error: Error<Java<Throwable>>) -> Self;
+
+    /// Converts any error returned by [`JvmOp::execute`]. If converting a thrown exception fails
+    /// (e.g. because a getter read by the variant throws), the original error is wrapped instead.
+    fn from_duchess_error(error: Error<Java<Throwable>>) -> Self {
+        let Error::Thrown(exception) = &error else {
+            return Self::from_error(error);
+        };
+        match Jvm::with(|jvm| Self::from_exception(jvm, exception)) {
+            Ok(Some(this)) => this,
+            Ok(None) => Self::from_error(error),
+            Err(err) => {
+                tracing::warn!(?err, "unable to convert Java exception");
+                Self::from_error(error)
+            }
         }
     }
 }
@@ -0,0 +1,188 @@
+//! Local reference frames, which free every local reference created within them at once.
+//!
+//! See [`Jvm::with_local_frame`] and [`JvmOp::in_local_frame`].
+
+use crate::{java::lang::Throwable, raw::EnvPtr, Error, Java, JavaObject, Jvm, JvmOp, Local};
+
+/// A value that can be returned out of a local reference frame: either it holds no local
+/// reference, or it holds exactly one, which is moved into the enclosing frame when the frame is popped.
+///
+/// # Safety
+///
+/// `pop_frame` must pop the innermost local reference frame, and the value it returns must not
+/// hold any local reference created within that frame.
+pub unsafe trait FrameOutput {
+    /// Pops the innermost local reference frame, moving the local reference held by `self`
+    /// (if any) into the enclosing frame.
+    ///
+    /// # Safety
+    ///
+    /// The innermost frame must have been pushed by the caller, and `self` must not hold
+    /// local references created outside of it.
+    #[doc(hidden)]
+    unsafe fn pop_frame(self, env: EnvPtr<'_>) -> Self;
+}
+
+macro_rules! no_local_refs {
+    ($($ty:ty),*) => {
+        $(
+            unsafe impl FrameOutput for $ty {
+                unsafe fn pop_frame(self, env: EnvPtr<'_>) -> Self {
+                    env.pop_local_frame();
+                    self
+                }
+            }
+        )*
+    };
+}
+
+no_local_refs!((), bool, i8, i16, u16, i32, i64, f32, f64, String);
+
+unsafe impl<T: JavaObject> FrameOutput for Java<T> {
+    unsafe fn pop_frame(self, env: EnvPtr<'_>) -> Self {
+        env.pop_local_frame();
+        self
+    }
+}
+
+unsafe impl<T: JavaObject> FrameOutput for Option<Java<T>> {
+    unsafe fn pop_frame(self, env: EnvPtr<'_>) -> Self {
+        env.pop_local_frame();
+        self
+    }
+}
+
+unsafe impl<'jvm, T: JavaObject> FrameOutput for Local<'jvm, T> {
+    unsafe fn pop_frame(self, env: EnvPtr<'_>) -> Self {
+        let local_env = self.env();
+        let obj = env.pop_local_frame_returning(self.into_raw());
+        Local::from_raw(local_env, obj)
+    }
+}
+
+unsafe impl<'jvm, T: JavaObject> FrameOutput for Option<Local<'jvm, T>> {
+    unsafe fn pop_frame(self, env: EnvPtr<'_>) -> Self {
+        match self {
+            Some(local) => Some(local.pop_frame(env)),
+            None => {
+                env.pop_local_frame();
+                None
+            }
+        }
+    }
+}
+
+/// Pops the innermost local reference frame, moving a thrown exception into the enclosing frame.
+///
+/// # Safety
+///
+/// The innermost frame must have been pushed by the caller.
+unsafe fn pop_frame_with_error<'jvm>(
+    env: EnvPtr<'jvm>,
+    error: Error<Local<'jvm, Throwable>>,
+) -> Error<Local<'jvm, Throwable>> {
+    match error {
+        Error::Thrown(exception) => Error::Thrown(exception.pop_frame(env)),
+        error => {
+            env.pop_local_frame();
+            error
+        }
+    }
+}
+
+/// Pops the innermost local reference frame if the code running in it panics,
+/// so that the frames pushed by [`Jvm::with`] stay balanced while unwinding.
+struct PopOnUnwind<'jvm>(EnvPtr<'jvm>);
+
+impl Drop for PopOnUnwind<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the guard is created right after pushing the frame, and forgotten once it is popped
+        unsafe { self.0.pop_local_frame() };
+    }
+}
+
+impl<'jvm> Jvm<'jvm> {
+    /// Runs `op` in a new local reference frame in which at least `capacity` local references
+    /// can be created. Every local reference created by `op` is freed at once when it returns,
+    /// so a loop creating many short-lived locals will not overflow the local reference table.
+    ///
+    /// Locals cannot escape the frame: `op` is given a [`Jvm`] with a fresh lifetime, so only
+    /// values that do not borrow from it may be returned. Use [`JvmOp::in_local_frame`] to
+    /// return a single local reference out of a frame. If `op` fails with a Java exception,
+    /// the exception is moved into the enclosing frame.
+    pub fn with_local_frame<R>(
+        &mut self,
+        capacity: i32,
+        op: impl for<'frame> FnOnce(&mut Jvm<'frame>) -> crate::LocalResult<'frame, R>,
+    ) -> crate::LocalResult<'jvm, R> {
+        let env = self.env();
+        // SAFETY: the frame is popped below (or by `guard` when unwinding), and `op` cannot
+        // let locals escape since it must be valid for any `'frame`.
+        unsafe { env.push_local_frame(capacity)? };
+        let guard = PopOnUnwind(env);
+        let result = op(self);
+        std::mem::forget(guard);
+
+        match result {
+            Ok(output) => {
+                unsafe { env.pop_local_frame() };
+                Ok(output)
+            }
+            Err(error) => Err(unsafe { pop_frame_with_error(env, error) }),
+        }
+    }
+
+    /// Ensures that at least `capacity` more local references can be created in the current
+    /// local reference frame. Fails if the JVM cannot allocate them.
+    pub fn ensure_local_capacity(&mut self, capacity: i32) -> crate::LocalResult<'jvm, ()> {
+        self.env().ensure_local_capacity(capacity)
+    }
+
+    /// Returns the number of [`Local`]s currently alive on this thread, which helps to track
+    /// down operations that hold on to more local references than expected.
+    /// Only available with the `count-local-refs` feature.
+    #[cfg(feature = "count-local-refs")]
+    pub fn live_local_refs() -> usize {
+        crate::ref_::live_locals()
+    }
+}
+
+#[derive_where::derive_where(Clone)]
+#[derive_where(Copy; This: Copy)]
+pub struct LocalFrame<This: JvmOp> {
+    this: This,
+    capacity: i32,
+}
+
+impl<This> LocalFrame<This>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: FrameOutput,
+{
+    pub(crate) fn new(this: This, capacity: i32) -> Self {
+        Self { this, capacity }
+    }
+}
+
+impl<This> JvmOp for LocalFrame<This>
+where
+    This: JvmOp,
+    for<'jvm> This::Output<'jvm>: FrameOutput,
+{
+    type Output<'jvm> = This::Output<'jvm>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        let env = jvm.env();
+        // SAFETY: the frame is popped below (or by `guard` when unwinding), and only the
+        // output of the operation may still hold a local created within it.
+        unsafe { env.push_local_frame(self.capacity)? };
+        let guard = PopOnUnwind(env);
+        let result = self.this.do_jni(jvm);
+        std::mem::forget(guard);
+
+        match result {
+            Ok(output) => Ok(unsafe { output.pop_frame(env) }),
+            Err(error) => Err(unsafe { pop_frame_with_error(env, error) }),
+        }
+    }
+}
@@ -0,0 +1,341 @@
+//! Bridges between Java's `java.util.concurrent.CompletableFuture` and Rust's [`Future`].
+//!
+//! Neither direction depends on a particular Rust executor: Java futures wake the Rust task
+//! from a Java completion callback, and Rust futures are polled on the JVM's
+//! `ForkJoinPool.commonPool()`.
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use crate::{
+    callback::{self, Callback},
+    cast::Upcast,
+    java::{
+        self,
+        lang::{Object, Throwable},
+        util::concurrent::{CompletableFuture, CompletionException, ForkJoinPool},
+    },
+    jvm::{panic_message, JavaObjectExt},
+    to_java::ToJavaImpl,
+    Error, IntoRust, Java, JavaObject, Jvm, JvmOp, Local, ToJava, TryJDeref,
+};
+
+/// Converts [`JvmOp`]s producing a `java.util.concurrent.CompletableFuture` into Rust futures.
+///
+/// Like [`JvmOpExt`](crate::JvmOpExt), this trait is not part of the [`prelude`](crate::prelude);
+/// import it where it is needed.
+pub trait JvmOpFutureExt: JvmOp {
+    /// Converts an operation producing a `java.util.concurrent.CompletableFuture` into a
+    /// Rust [`Future`] that resolves once the Java future completes.
+    ///
+    /// The operation is executed when the future is first polled. The Java result is
+    /// converted into the Rust type `R` (see [`JavaFuture`]).
+    fn into_future<T, R>(self) -> JavaFuture<Self, R>
+    where
+        T: JavaObject,
+        for<'jvm> Self: JvmOp<Output<'jvm>: TryJDeref<Java = CompletableFuture<T>>>,
+        for<'a> Option<&'a T>: IntoRust<R>,
+        R: Send + 'static,
+    {
+        JavaFuture::new(self)
+    }
+}
+
+impl<J: JvmOp> JvmOpFutureExt for J {}
+
+/// A Rust [`Future`] that resolves once a Java `CompletableFuture` completes.
+///
+/// Created by [`JvmOpFutureExt::into_future`]. The output is converted to the Rust type `R` when the
+/// Java future completes normally; if it completes exceptionally, the future resolves to
+/// [`Error::Thrown`] with the exception.
+///
+/// Since a `CompletableFuture` may complete with `null`, `R` is typically an `Option`
+/// (e.g., `Option<String>` or `Option<Java<T>>`).
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct JavaFuture<J, R> {
+    op: Option<J>,
+    state: Arc<Mutex<State<R>>>,
+}
+
+enum State<R> {
+    Pending(Option<Waker>),
+    Ready(crate::Result<R>),
+    Done,
+}
+
+impl<J, R> JavaFuture<J, R> {
+    pub(crate) fn new(op: J) -> Self {
+        JavaFuture {
+            op: Some(op),
+            state: Arc::new(Mutex::new(State::Pending(None))),
+        }
+    }
+}
+
+impl<J, R> Unpin for JavaFuture<J, R> {}
+
+impl<J, T, R> Future for JavaFuture<J, R>
+where
+    J: JvmOp,
+    for<'jvm> J::Output<'jvm>: TryJDeref<Java = CompletableFuture<T>>,
+    T: JavaObject,
+    for<'a> Option<&'a T>: IntoRust<R>,
+    R: Send + 'static,
+{
+    type Output = crate::Result<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        // Register the waker before the completion callback so that a callback that
+        // fires immediately (e.g., the future is already complete) finds it.
+        {
+            let mut state = this.state.lock().unwrap();
+            match &mut *state {
+                State::Pending(waker) => *waker = Some(cx.waker().clone()),
+                State::Ready(_) => {
+                    let State::Ready(result) = std::mem::replace(&mut *state, State::Done) else {
+                        unreachable!()
+                    };
+                    return Poll::Ready(result);
+                }
+                State::Done => panic!("`JavaFuture` polled after completion"),
+            }
+        }
+
+        if let Some(op) = this.op.take() {
+            let completion: Arc<dyn Callback> = Arc::new(Completion::<T, R> {
+                state: this.state.clone(),
+                phantom: PhantomData,
+            });
+            let registered = Jvm::with(|jvm| {
+                let future = op.do_jni(jvm)?;
+                let future = future.try_jderef()?;
+                let action = callback::bi_consumer::<T, Throwable>(jvm, completion)?;
+                future.when_complete(&action).do_jni(jvm)?;
+                Ok(())
+            });
+            if let Err(err) = registered {
+                *this.state.lock().unwrap() = State::Done;
+                return Poll::Ready(Err(err));
+            }
+        }
+
+        let mut state = this.state.lock().unwrap();
+        match std::mem::replace(&mut *state, State::Done) {
+            State::Ready(result) => Poll::Ready(result),
+            pending => {
+                *state = pending;
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// The `BiConsumer` given to `CompletableFuture::whenComplete`.
+struct Completion<T, R> {
+    state: Arc<Mutex<State<R>>>,
+    phantom: PhantomData<fn(&T)>,
+}
+
+impl<T, R> Callback for Completion<T, R>
+where
+    T: JavaObject,
+    for<'a> Option<&'a T>: IntoRust<R>,
+    R: Send + 'static,
+{
+    fn accept(self: Arc<Self>, t: Option<&Object>, u: Option<&Object>) {
+        let result = Jvm::with(|jvm| match u {
+            // SAFETY: `whenComplete` invokes its action with the exception as a `Throwable`
+            Some(u) => Ok(Err(Error::Thrown(
+                jvm.global(unsafe { Throwable::from_raw(u.as_raw()) }),
+            ))),
+            None => {
+                // SAFETY: `whenComplete` invokes its action with the result of the `CompletableFuture<T>`
+                let t: Option<&T> = t.map(|t| unsafe { T::from_raw(t.as_raw()) });
+                Ok(Ok(t.into_rust(jvm)?))
+            }
+        })
+        .and_then(|result| result);
+
+        let mut state = self.state.lock().unwrap();
+        if let State::Pending(waker) = std::mem::replace(&mut *state, State::Ready(result)) {
+            drop(state);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Wraps a Rust [`Future`] so that it can be converted into a Java `CompletableFuture`
+/// with [`ToJava::to_java`].
+///
+/// The future is polled on the JVM's `ForkJoinPool.commonPool()` and the `CompletableFuture`
+/// completes with its output converted to Java. If the conversion throws, or the future panics,
+/// the `CompletableFuture` completes exceptionally. Cancelling the `CompletableFuture` drops the
+/// future the next time it is woken.
+///
+/// Since the future does not run on a Rust executor, futures that depend on a particular
+/// runtime (e.g., tokio I/O or timers) should be spawned on that runtime first, and the
+/// resulting `JoinHandle` wrapped instead.
+///
+/// A `RustFuture` can only be converted once; later conversions return [`Error::JvmInternal`].
+pub struct RustFuture<F> {
+    future: Mutex<Option<F>>,
+}
+
+impl<F> RustFuture<F>
+where
+    F: Future + Send + 'static,
+{
+    pub fn new(future: F) -> Self {
+        RustFuture {
+            future: Mutex::new(Some(future)),
+        }
+    }
+}
+
+impl<F, J> ToJavaImpl<CompletableFuture<J>> for RustFuture<F>
+where
+    F: Future + Send + 'static,
+    F::Output: ToJavaImpl<J>,
+    J: Upcast<Object> + Upcast<J>,
+{
+    fn to_java_impl<'jvm>(
+        rust: &Self,
+        jvm: &mut Jvm<'jvm>,
+    ) -> crate::LocalResult<'jvm, Option<Local<'jvm, CompletableFuture<J>>>> {
+        let Some(future) = rust.future.lock().unwrap().take() else {
+            return Err(Error::JvmInternal(
+                "`RustFuture` was already converted to a `CompletableFuture`".to_string(),
+            ));
+        };
+
+        let java = CompletableFuture::<J>::new().do_jni(jvm)?;
+        let task = Arc::new(Task {
+            future: Mutex::new(Some(Box::pin(future))),
+            java: jvm.global(&java),
+            scheduled: AtomicBool::new(false),
+        });
+        task.schedule(jvm)?;
+        Ok(Some(java))
+    }
+}
+
+/// A Rust future being driven to completion on `ForkJoinPool.commonPool()`.
+struct Task<F: Future, J: JavaObject> {
+    /// `None` once the future has completed or was cancelled.
+    future: Mutex<Option<Pin<Box<F>>>>,
+    java: Java<CompletableFuture<J>>,
+    /// Whether a run of this task is queued but not yet started.
+    scheduled: AtomicBool,
+}
+
+impl<F, J> Task<F, J>
+where
+    F: Future + Send + 'static,
+    F::Output: ToJavaImpl<J>,
+    J: Upcast<Object> + Upcast<J>,
+{
+    fn schedule<'jvm>(self: &Arc<Self>, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, ()> {
+        if self.scheduled.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+
+        let runnable = callback::runnable(jvm, self.clone())?;
+        // Deref to reach `ForkJoinPool::execute` rather than `JvmOp::execute`.
+        (*ForkJoinPool::common_pool())
+            .execute(&runnable)
+            .do_jni(jvm)
+    }
+
+    fn complete(&self, output: std::thread::Result<F::Output>) -> crate::Result<()> {
+        Jvm::with(|jvm| {
+            let message = match output {
+                Ok(output) => match output.to_java::<J>().do_jni(jvm) {
+                    Ok(value) => {
+                        self.java.complete(&value).do_jni(jvm)?;
+                        return Ok(());
+                    }
+                    Err(Error::Thrown(exception)) => {
+                        self.java.complete_exceptionally(&exception).do_jni(jvm)?;
+                        return Ok(());
+                    }
+                    Err(err) => err.to_string(),
+                },
+                Err(panic) => panic_message(&*panic),
+            };
+
+            let message = message.to_java::<java::lang::String>().do_jni(jvm)?;
+            let exception = CompletionException::new(&message).do_jni(jvm)?;
+            self.java.complete_exceptionally(&exception).do_jni(jvm)?;
+            Ok(())
+        })
+    }
+}
+
+impl<F, J> Callback for Task<F, J>
+where
+    F: Future + Send + 'static,
+    F::Output: ToJavaImpl<J>,
+    J: Upcast<Object> + Upcast<J>,
+{
+    fn run(self: Arc<Self>) {
+        self.scheduled.store(false, Ordering::Release);
+
+        let mut slot = self.future.lock().unwrap();
+        let Some(future) = slot.as_mut() else {
+            return;
+        };
+
+        // A `CompletableFuture` that is already done was cancelled (or completed) from Java.
+        match self.java.is_done().execute() {
+            Ok(false) => {}
+            Ok(true) => {
+                *slot = None;
+                return;
+            }
+            Err(err) => {
+                tracing::warn!(?err, "unable to check whether `CompletableFuture` is done");
+            }
+        }
+
+        let waker = Waker::from(self.clone());
+        let mut cx = Context::from_waker(&waker);
+        let output =
+            match std::panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx))) {
+                Ok(Poll::Pending) => return,
+                Ok(Poll::Ready(output)) => Ok(output),
+                Err(panic) => Err(panic),
+            };
+        *slot = None;
+        drop(slot);
+
+        if let Err(err) = self.complete(output) {
+            tracing::warn!(?err, "unable to complete `CompletableFuture`");
+        }
+    }
+}
+
+impl<F, J> Wake for Task<F, J>
+where
+    F: Future + Send + 'static,
+    F::Output: ToJavaImpl<J>,
+    J: Upcast<Object> + Upcast<J>,
+{
+    fn wake(self: Arc<Self>) {
+        if let Err(err) = Jvm::with(|jvm| self.schedule(jvm)) {
+            tracing::warn!(?err, "unable to schedule Rust future on the JVM");
+        }
+    }
+}
@@ -30,6 +30,8 @@ identity_rust_op! {
     i16,
     i32,
     i64,
+    f32,
+    f64,
 }
 
 impl<O, E, JO, JE> IntoRust<Result<O, E>> for Result<JO, JE>
@@ -45,6 +47,46 @@ where
     }
 }
 
+macro_rules! tuple_rust_op {
+    ($($r:ident: $j:ident),*) => {
+        impl<$($r, $j),*> IntoRust<($($r,)*)> for ($($j,)*)
+        where
+            $($j: IntoRust<$r>,)*
+        {
+            #[allow(non_snake_case)]
+            fn into_rust<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, ($($r,)*)> {
+                let ($($j,)*) = self;
+                Ok(($($j.into_rust(jvm)?,)*))
+            }
+        }
+    };
+}
+
+tuple_rust_op!(RA: JA);
+tuple_rust_op!(RA: JA, RB: JB);
+tuple_rust_op!(RA: JA, RB: JB, RC: JC);
+tuple_rust_op!(RA: JA, RB: JB, RC: JC, RD: JD);
+tuple_rust_op!(RA: JA, RB: JB, RC: JC, RD: JD, RE: JE);
+tuple_rust_op!(RA: JA, RB: JB, RC: JC, RD: JD, RE: JE, RF: JF);
+tuple_rust_op!(RA: JA, RB: JB, RC: JC, RD: JD, RE: JE, RF: JF, RG: JG);
+tuple_rust_op!(RA: JA, RB: JB, RC: JC, RD: JD, RE: JE, RF: JF, RG: JG, RH: JH);
+
+impl<R, J, const N: usize> IntoRust<[R; N]> for [J; N]
+where
+    J: IntoRust<R>,
+{
+    fn into_rust<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, [R; N]> {
+        let mut outputs = Vec::with_capacity(N);
+        for j in self {
+            outputs.push(j.into_rust(jvm)?);
+        }
+        let Ok(outputs) = outputs.try_into() else {
+            unreachable!("one output per element")
+        };
+        Ok(outputs)
+    }
+}
+
 impl<O, JO> IntoRust<Option<O>> for Option<JO>
 where
     JO: IntoRust<O>,
@@ -57,6 +99,15 @@ where
     }
 }
 
+impl<R, J> IntoRust<Vec<R>> for Vec<J>
+where
+    J: IntoRust<R>,
+{
+    fn into_rust<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Vec<R>> {
+        self.into_iter().map(|j| j.into_rust(jvm)).collect()
+    }
+}
+
 impl<J> IntoRust<Java<J>> for &J
 where
     J: JavaObject,
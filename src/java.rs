@@ -58,6 +58,18 @@ mod auto {
             public java.lang.NullPointerException();
         }
 
+        public interface java.lang.Runnable {
+            public abstract void run();
+        }
+
+        public final class java.lang.System {
+            public static java.lang.String getProperty(java.lang.String);
+        }
+
+        public interface java.lang.Iterable<T> {
+            public abstract java.util.Iterator<T> iterator();
+        }
+
         // NB: In Java, this is `Class<T>`, but we model it as the erased version
         // `Class`. This is beacuse there are a lot of methods, including some that we would
         // like to model such as `arrayType()`, that return a `Class<?>`, and we cannot model
@@ -90,6 +102,16 @@ mod auto {
             public java.lang.String(byte[]);
             public int length();
             public boolean isEmpty();
+            public static java.lang.String format(java.lang.String, java.lang.Object...);
+        }
+
+        public abstract class java.lang.Enum<E extends java.lang.Enum<E>> {
+            public final java.lang.String name();
+            public final int ordinal();
+            public java.lang.String toString();
+            public final boolean equals(java.lang.Object);
+            public final int hashCode();
+            public final int compareTo(E);
         }
 
         public abstract class java.lang.Record {
@@ -98,7 +120,42 @@ mod auto {
             public abstract java.lang.String toString();
         }
 
-        public final class java.lang.Long {
+        public abstract class java.lang.Number {
+            public java.lang.Number();
+            public abstract int intValue();
+            public abstract long longValue();
+            public abstract float floatValue();
+            public abstract double doubleValue();
+            public byte byteValue();
+            public short shortValue();
+        }
+
+        public final class java.lang.Boolean {
+            public static java.lang.Boolean valueOf(boolean);
+            public boolean booleanValue();
+        }
+
+        public final class java.lang.Character {
+            public static java.lang.Character valueOf(char);
+            public char charValue();
+        }
+
+        public final class java.lang.Byte extends java.lang.Number {
+            public static java.lang.Byte valueOf(byte);
+            public byte byteValue();
+        }
+
+        public final class java.lang.Short extends java.lang.Number {
+            public static java.lang.Short valueOf(short);
+            public short shortValue();
+        }
+
+        public final class java.lang.Integer extends java.lang.Number {
+            public static java.lang.Integer valueOf(int);
+            public int intValue();
+        }
+
+        public final class java.lang.Long extends java.lang.Number {
             public static long parseLong(java.lang.String) throws java.lang.NumberFormatException;
             public static long parseUnsignedLong(java.lang.String) throws java.lang.NumberFormatException;
             public static java.lang.Long valueOf(java.lang.String) throws java.lang.NumberFormatException;
@@ -111,11 +168,21 @@ mod auto {
             public float floatValue();
             public double doubleValue();
             public static java.lang.Long getLong(java.lang.String);
-          }
+        }
+
+        public final class java.lang.Float extends java.lang.Number {
+            public static java.lang.Float valueOf(float);
+            public float floatValue();
+        }
+
+        public final class java.lang.Double extends java.lang.Number {
+            public static java.lang.Double valueOf(double);
+            public double doubleValue();
+        }
 
         package java.util;
 
-        public interface java.util.List<E> {
+        public interface java.util.List<E> extends java.util.Collection<E> {
             public abstract int size();
             public abstract boolean isEmpty();
             public abstract boolean contains(java.lang.Object);
@@ -130,12 +197,7 @@ mod auto {
             public abstract int indexOf(java.lang.Object);
             public abstract int lastIndexOf(java.lang.Object);
             public abstract java.util.List<E> subList(int, int);
-
-            // FIXME: Java generics from static methods are totally
-            // disjoint from the enclosing Self type, but not in Rust.
-            // How do we bridge this gap most ergonomically?
-            //
-            // public static <E> java.util.List<E> of(E...);
+            public static <E> java.util.List<E> of(E...);
         }
 
         public class java.util.ArrayList<E> implements java.util.List<E> {
@@ -169,24 +231,25 @@ mod auto {
             public abstract V remove(java.lang.Object);
             public abstract void putAll(java.util.Map<? extends K, ? extends V>);
             public abstract void clear();
-            // public abstract java.util.Set<K> keySet();
-            // public abstract java.util.Collection<V> values();
-            // public abstract java.util.Set<java.util.Map$Entry<K, V>> entrySet();
+            public abstract java.util.Set<K> keySet();
+            public abstract java.util.Collection<V> values();
+            public abstract java.util.Set<java.util.Map$Entry<K, V>> entrySet();
             public abstract boolean equals(java.lang.Object);
             public abstract int hashCode();
             public default V getOrDefault(java.lang.Object, V);
-            // public default void forEach(java.util.function.BiConsumer<? super K, ? super V>);
-            // public default void replaceAll(java.util.function.BiFunction<? super K, ? super V, ? extends V>);
+            public default void forEach(java.util.function.BiConsumer<? super K, ? super V>);
+            public default void replaceAll(java.util.function.BiFunction<? super K, ? super V, ? extends V>);
             public default V putIfAbsent(K, V);
             // public default boolean remove(java.lang.Object, java.lang.Object);
             // public default boolean replace(K, V, V);
             // public default V replace(K, V);
-            // // public default V computeIfAbsent(K, java.util.function.Function<? super K, ? extends V>);
-            // public default V computeIfPresent(K, java.util.function.BiFunction<? super K, ? super V, ? extends V>);
-            // public default V compute(K, java.util.function.BiFunction<? super K, ? super V, ? extends V>);
-            // public default V merge(K, V, java.util.function.BiFunction<? super V, ? super V, ? extends V>);
+            public default V computeIfAbsent(K, java.util.function.Function<? super K, ? extends V>);
+            public default V computeIfPresent(K, java.util.function.BiFunction<? super K, ? super V, ? extends V>);
+            public default V compute(K, java.util.function.BiFunction<? super K, ? super V, ? extends V>);
+            public default V merge(K, V, java.util.function.BiFunction<? super V, ? super V, ? extends V>);
+            // FIXME: overloaded methods are not supported, so only one `of` can be mirrored
             // public static <K, V> java.util.Map<K, V> of();
-            // public static <K, V> java.util.Map<K, V> of(K, V);
+            public static <K, V> java.util.Map<K, V> of(K, V);
             // public static <K, V> java.util.Map<K, V> of(K, V, K, V);
             // public static <K, V> java.util.Map<K, V> of(K, V, K, V, K, V);
             // public static <K, V> java.util.Map<K, V> of(K, V, K, V, K, V, K, V);
@@ -201,6 +264,39 @@ mod auto {
             // public static <K, V> java.util.Map<K, V> copyOf(java.util.Map<? extends K, ? extends V>);
         }
 
+        public interface java.util.Map$Entry<K, V> {
+            public abstract K getKey();
+            public abstract V getValue();
+            public abstract V setValue(V);
+        }
+
+        public interface java.util.Iterator<E> {
+            public abstract boolean hasNext();
+            public abstract E next();
+        }
+
+        public interface java.util.Collection<E> extends java.lang.Iterable<E> {
+            public abstract java.util.Iterator<E> iterator();
+            public abstract int size();
+            public abstract boolean isEmpty();
+            public abstract boolean contains(java.lang.Object);
+            public abstract boolean add(E);
+            public abstract boolean remove(java.lang.Object);
+            public abstract boolean containsAll(java.util.Collection<?>);
+            public abstract boolean addAll(java.util.Collection<? extends E>);
+            public abstract void clear();
+        }
+
+        public interface java.util.Set<E> extends java.util.Collection<E> {
+            public abstract java.util.Iterator<E> iterator();
+            public abstract int size();
+            public abstract boolean isEmpty();
+            public abstract boolean contains(java.lang.Object);
+            public abstract boolean add(E);
+            public abstract boolean remove(java.lang.Object);
+            public abstract void clear();
+        }
+
         public class java.util.HashMap<K, V>
             // extends java.util.AbstractMap<K, V>
             implements java.util.Map<K, V> // , java.lang.Cloneable, java.io.Serializable
@@ -235,6 +331,23 @@ mod auto {
             public java.lang.Object clone();
         }
 
+        public final class java.util.Optional<T> {
+            public static <T> java.util.Optional<T> empty();
+            public static <T> java.util.Optional<T> of(T);
+            public static <T> java.util.Optional<T> ofNullable(T);
+            public T get();
+            public boolean isPresent();
+            public boolean isEmpty();
+            public T orElse(T);
+        }
+
+        public class java.util.Collections {
+            public static <T> java.util.List<T> emptyList();
+            public static <T> java.util.List<T> singletonList(T);
+            public static <K, V> java.util.Map<K, V> emptyMap();
+            public static <K, V> java.util.Map<K, V> singletonMap(K, V);
+        }
+
         public class java.util.Date {
             public java.util.Date();
             //   public java.util.Date(long);
@@ -276,6 +389,86 @@ mod auto {
             //   static {};
         }
 
+        package java.util.function;
+
+        public interface java.util.function.BiConsumer<T, U> {
+            public abstract void accept(T, U);
+            // public default java.util.function.BiConsumer<T, U> andThen(java.util.function.BiConsumer<? super T, ? super U>);
+        }
+
+        public interface java.util.function.Function<T, R> {
+            public abstract R apply(T);
+        }
+
+        public interface java.util.function.BiFunction<T, U, R> {
+            public abstract R apply(T, U);
+        }
+
+        package java.util.concurrent;
+
+        public interface java.util.concurrent.Future<V> {
+            public abstract boolean cancel(boolean);
+            public abstract boolean isCancelled();
+            public abstract boolean isDone();
+            public abstract V get() throws java.lang.InterruptedException, java.util.concurrent.ExecutionException;
+        }
+
+        public interface java.util.concurrent.CompletionStage<T> {
+            public abstract java.util.concurrent.CompletionStage<T> whenComplete(java.util.function.BiConsumer<? super T, ? super java.lang.Throwable>);
+            public abstract java.util.concurrent.CompletableFuture<T> toCompletableFuture();
+        }
+
+        public class java.util.concurrent.CompletableFuture<T> implements java.util.concurrent.Future<T>, java.util.concurrent.CompletionStage<T> {
+            public java.util.concurrent.CompletableFuture();
+            public static <U> java.util.concurrent.CompletableFuture<U> completedFuture(U);
+            public static <U> java.util.concurrent.CompletableFuture<U> failedFuture(java.lang.Throwable);
+            public boolean isDone();
+            public T get() throws java.lang.InterruptedException, java.util.concurrent.ExecutionException;
+            public T join();
+            public T getNow(T);
+            public boolean complete(T);
+            public boolean completeExceptionally(java.lang.Throwable);
+            public java.util.concurrent.CompletableFuture<T> whenComplete(java.util.function.BiConsumer<? super T, ? super java.lang.Throwable>);
+            public java.util.concurrent.CompletableFuture<T> toCompletableFuture();
+            public boolean cancel(boolean);
+            public boolean isCancelled();
+            public boolean isCompletedExceptionally();
+            public int getNumberOfDependents();
+            public java.lang.String toString();
+        }
+
+        public class java.util.concurrent.CompletionException extends java.lang.RuntimeException {
+            public java.util.concurrent.CompletionException(java.lang.String);
+        }
+
+        public class java.util.concurrent.ForkJoinPool {
+            public static java.util.concurrent.ForkJoinPool commonPool();
+            public void execute(java.lang.Runnable);
+        }
+
+        package java.nio;
+
+        public abstract class java.nio.Buffer {
+            public final int capacity();
+            public final int position();
+            public final int limit();
+            public final int remaining();
+            public final boolean hasRemaining();
+            public abstract boolean isReadOnly();
+            public abstract boolean isDirect();
+        }
+
+        public abstract class java.nio.ByteBuffer extends java.nio.Buffer {
+            public static java.nio.ByteBuffer allocateDirect(int);
+            public static java.nio.ByteBuffer allocate(int);
+            public static java.nio.ByteBuffer wrap(byte[]);
+            public abstract java.nio.ByteBuffer asReadOnlyBuffer();
+            public abstract byte get(int);
+            public abstract java.nio.ByteBuffer put(int, byte);
+            public final boolean hasArray();
+            public abstract boolean isDirect();
+        }
+
         package java.time;
 
         public final class java.time.Instant {
@@ -381,12 +574,12 @@ mod auto {
             public java.lang.String toString();
         }
 
-        public final class java.lang.management.MemoryType {
+        public final class java.lang.management.MemoryType extends java.lang.Enum<java.lang.management.MemoryType> {
             public static final java.lang.management.MemoryType HEAP;
             public static final java.lang.management.MemoryType NON_HEAP;
             private final java.lang.String description;
             //private static final java.lang.management.MemoryType[] $VALUES;
-            //public static java.lang.management.MemoryType[] values();
+            public static java.lang.management.MemoryType[] values();
             public static java.lang.management.MemoryType valueOf(java.lang.String);
             private java.lang.management.MemoryType(java.lang.String);
             public java.lang.String toString();
@@ -401,5 +594,7 @@ pub use auto::java::*;
 // XX this isn't a real class in the JVM, since each array type (e.g. Foo[] and int[]) is just a subclass of Object.
 // Should it go somewhere outside of the JDK core classes?
 pub use crate::array::JavaArray as Array;
+pub use crate::array::JavaArrayCriticalExt as ArrayCriticalExt;
 pub use crate::array::JavaArrayExt as ArrayExt;
 pub use crate::array::JavaArrayModificationExt as ArrayModificationExt;
+pub use crate::buffer::JavaByteBufferExt as ByteBufferExt;
@@ -1,6 +1,7 @@
 use crate::{
     cast::{AsUpcast, TryDowncast, Upcast},
     find::find_class,
+    frame::{FrameOutput, LocalFrame},
     into_rust::ToRustOp,
     java::lang::{Class, Throwable},
     link::{IntoJavaFns, JavaFunction},
@@ -9,15 +10,20 @@ use crate::{
     semver_unstable::{FromRef, ToJavaImpl, ToJavaScalar},
     thread,
     try_catch::TryCatch,
-    AsJRef, Error, IntoRust, Java, Local, Result, ToJava, TryJDeref,
+    AsJRef, Error, IntoRust, Java, JavaWeak, Local, Result, ToJava, TryJDeref,
 };
 
 use std::{
     any::Any,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     ffi::{c_char, c_void, CString},
     fmt::Display,
     panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError,
+    },
+    time::Duration,
 };
 
 use once_cell::sync::OnceCell;
@@ -82,6 +88,17 @@ pub trait JvmOp: Clone {
         TryCatch::new(self)
     }
 
+    /// Executes this operation in a new local reference frame with room for at least `capacity`
+    /// local references. Every local reference the operation creates is freed at once when it
+    /// completes, except for the one held by its output (or by the exception it throws),
+    /// which is moved into the enclosing frame.
+    fn in_local_frame(self, capacity: i32) -> LocalFrame<Self>
+    where
+        for<'jvm> Self::Output<'jvm>: FrameOutput,
+    {
+        LocalFrame::new(self, capacity)
+    }
+
     /// Execute on the JVM, starting a JVM instance if necessary.
     ///
     /// Depending on the type parameter `R`,
@@ -161,7 +178,76 @@ where
 
 static GLOBAL_JVM: OnceCell<JvmPtr> = OnceCell::new();
 
-fn get_or_default_init_jvm() -> crate::Result<JvmPtr> {
+/// The JVM can be used.
+const RUNNING: u8 = 0;
+/// [`Jvm::shutdown`] is running `DestroyJavaVM`, which runs Java code (e.g. shutdown hooks)
+/// that may call back into Rust.
+const DESTROYING: u8 = 1;
+/// `DestroyJavaVM` has returned.
+const DESTROYED: u8 = 2;
+
+/// Whether the JVM in `GLOBAL_JVM` is [`RUNNING`]. Once it isn't, `GLOBAL_JVM` must not be
+/// used anymore (any JNI call would be undefined behavior).
+static JVM_STATE: AtomicU8 = AtomicU8::new(RUNNING);
+
+/// Code using `GLOBAL_JVM` holds the read lock through a [`JvmLock`], so that [`Jvm::shutdown`],
+/// which takes the write lock while it changes `JVM_STATE`, cannot destroy the JVM under it.
+/// The lock is released before `DestroyJavaVM` runs, so that Java code calling back into Rust
+/// during shutdown gets [`Error::JvmShutDown`] instead of deadlocking.
+static IN_USE: RwLock<()> = RwLock::new(());
+
+thread_local! {
+    /// Whether the current thread holds the read lock of `IN_USE`. Taking it again could
+    /// deadlock if the write lock is taken in the meantime.
+    static LOCKED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Keeps the JVM from being shut down while live, see [`lock_jvm`].
+pub(crate) struct JvmLock {
+    /// `None` if an enclosing `JvmLock` on this thread holds the lock.
+    guard: Option<RwLockReadGuard<'static, ()>>,
+}
+
+impl Drop for JvmLock {
+    fn drop(&mut self) {
+        if self.guard.take().is_some() {
+            LOCKED.with(|locked| locked.set(false));
+        }
+    }
+}
+
+/// Keeps the JVM from being shut down until the returned lock is dropped.
+/// Fails if it already was.
+pub(crate) fn lock_jvm() -> crate::Result<JvmLock> {
+    if LOCKED.with(|locked| locked.get()) {
+        return Ok(JvmLock { guard: None });
+    }
+    // Checked before taking the lock, so that late callers never wait for a shutdown
+    if JVM_STATE.load(Ordering::SeqCst) != RUNNING {
+        return Err(Error::JvmShutDown);
+    }
+    let guard = IN_USE.read().unwrap_or_else(PoisonError::into_inner);
+    if JVM_STATE.load(Ordering::SeqCst) != RUNNING {
+        return Err(Error::JvmShutDown);
+    }
+    LOCKED.with(|locked| locked.set(true));
+    Ok(JvmLock { guard: Some(guard) })
+}
+
+/// Takes the write lock of `IN_USE` once no [`JvmLock`] is live. It is polled rather than
+/// waited for, since std's `RwLock` makes new readers wait behind a waiting writer: a thread
+/// holding a `JvmLock` while waiting for another thread that needs one would deadlock.
+fn lock_jvm_exclusive() -> RwLockWriteGuard<'static, ()> {
+    loop {
+        match IN_USE.try_write() {
+            Ok(guard) => return guard,
+            Err(TryLockError::Poisoned(err)) => return err.into_inner(),
+            Err(TryLockError::WouldBlock) => std::thread::sleep(Duration::from_millis(1)),
+        }
+    }
+}
+
+fn get_or_default_init_jvm(_lock: &JvmLock) -> crate::Result<JvmPtr> {
     match GLOBAL_JVM.get() {
         Some(jvm) => Ok(*jvm),
         None => {
@@ -343,16 +429,19 @@ unsafe fn init_jvm_from_native_function(env: EnvPtr<'_>) -> Jvm<'_> {
 }
 
 fn rust_panic_to_java_exception(env: EnvPtr<'_>, panic: Box<dyn Any + Send + 'static>) {
+    throw_java_runtime_exception(env, &panic_message(&*panic));
+}
+
+/// Extracts the message from the payload of a Rust panic.
+pub(crate) fn panic_message(panic: &(dyn Any + Send + 'static)) -> String {
     // The documentation suggests that it will *usually* be a str or String.
-    let message = if let Some(s) = panic.downcast_ref::<&'static str>() {
+    if let Some(s) = panic.downcast_ref::<&'static str>() {
         (*s).to_string()
     } else if let Some(s) = panic.downcast_ref::<String>() {
         s.clone()
     } else {
         "Unknown panic!".to_string()
-    };
-
-    throw_java_runtime_exception(env, &message);
+    }
 }
 
 /// Get the global [`JvmPtr`] assuming that the JVM has already been initialized. Expected to be used with values
@@ -375,20 +464,64 @@ impl<'jvm> Jvm<'jvm> {
         JvmBuilder::new()
     }
 
+    /// Returns the JNI version negotiated with the JVM (the newest one it supports, e.g.
+    /// `0x00150000` for JNI 21), or `None` if no JVM has been used yet.
+    pub fn jni_version() -> Option<i32> {
+        raw::jni_version()
+    }
+
+    /// Attaches the current thread to the JVM until the thread exits, so that operations
+    /// executed on it do not need to attach and detach it each time.
     pub fn attach_thread_permanently() -> crate::Result<()> {
-        thread::attach_permanently(get_or_default_init_jvm()?)?;
-        Ok(())
+        let lock = lock_jvm()?;
+        thread::attach_permanently(get_or_default_init_jvm(&lock)?)
+    }
+
+    /// Shuts down the JVM: waits for the operations running on other threads, detaches the
+    /// current thread if it was attached permanently, then destroys the JVM with `DestroyJavaVM`.
+    /// This waits for all other non-daemon threads (including threads attached to the JVM
+    /// by Java or by other code) to finish and runs the Java shutdown hooks.
+    ///
+    /// Once `DestroyJavaVM` starts, executing operations or launching a new JVM fails with
+    /// [`Error::JvmShutDown`], and dropping [`Java`] references does nothing. This includes
+    /// Rust code called back from those threads and shutdown hooks. Fails if
+    /// called from within a JVM operation, while other threads are attached with
+    /// [`attach_thread_permanently`](Self::attach_thread_permanently), or if the JVM
+    /// was already shut down.
+    pub fn shutdown() -> crate::Result<()> {
+        // Checked first, since this thread would otherwise wait for itself to release the lock
+        thread::check_can_shut_down()?;
+
+        let in_use = lock_jvm_exclusive();
+        if JVM_STATE.load(Ordering::SeqCst) != RUNNING {
+            return Err(Error::JvmShutDown);
+        }
+        let Some(&jvm) = GLOBAL_JVM.get() else {
+            JVM_STATE.store(DESTROYED, Ordering::SeqCst);
+            return Ok(());
+        };
+        thread::detach_for_shutdown(jvm)?;
+        JVM_STATE.store(DESTROYING, Ordering::SeqCst);
+        drop(in_use);
+
+        // SAFETY: the JVM was not in use while we held the write lock, and `JVM_STATE` keeps
+        // anything from using it afterwards
+        let result = unsafe { jvm.destroy() };
+        JVM_STATE.store(DESTROYED, Ordering::SeqCst);
+        result
     }
 
     /// Call the callback with access to a `Jvm`.
-    /// This cannot be invoked recursively.
+    /// This may be invoked recursively; each invocation gets its own
+    /// local reference frame that is popped when the callback returns.
     /// It is crate-local because it is only usd from within
     /// the `execute` method on [`JvmOp`][].
     pub(crate) fn with<R>(
         op: impl for<'a> FnOnce(&mut Jvm<'a>) -> crate::LocalResult<'a, R>,
     ) -> crate::Result<R> {
-        let jvm = get_or_default_init_jvm()?;
-        // SAFTEY: we won't deinitialize the JVM while the guard is live
+        let lock = lock_jvm()?;
+        let jvm = get_or_default_init_jvm(&lock)?;
+        // SAFTEY: `lock` keeps the JVM from being deinitialized while the guard is live
         let mut guard = unsafe { thread::attach(jvm)? };
 
         let mut jvm = Jvm(guard.env());
@@ -409,6 +542,13 @@ impl<'jvm> Jvm<'jvm> {
         Java::new(self.0, r)
     }
 
+    pub fn weak<R>(&mut self, r: &R) -> JavaWeak<R>
+    where
+        R: JavaObject,
+    {
+        JavaWeak::new(self.0, r)
+    }
+
     /// Plumbing method that should only be used by generated and internal code.
     #[doc(hidden)]
     pub fn env(&self) -> EnvPtr<'jvm> {
@@ -447,8 +587,18 @@ impl<'jvm> Jvm<'jvm> {
 
 pub struct JvmBuilder {
     options: Vec<String>,
+    classpath: Vec<String>,
+    module_path: Vec<String>,
+    add_modules: Vec<String>,
+    system_properties: BTreeMap<String, String>,
+    initial_heap_size: Option<String>,
+    max_heap_size: Option<String>,
+    thread_stack_size: Option<String>,
+    ignore_unrecognized: bool,
     #[cfg(feature = "dylibjvm")]
     libjvm_path: Option<std::path::PathBuf>,
+    #[cfg(feature = "dylibjvm")]
+    min_java_version: Option<u32>,
     java_functions: Vec<JavaFunction>,
 }
 
@@ -456,8 +606,18 @@ impl JvmBuilder {
     fn new() -> Self {
         let mut this = Self {
             options: vec![],
+            classpath: vec![],
+            module_path: vec![],
+            add_modules: vec![],
+            system_properties: BTreeMap::new(),
+            initial_heap_size: None,
+            max_heap_size: None,
+            thread_stack_size: None,
+            ignore_unrecognized: false,
             #[cfg(feature = "dylibjvm")]
             libjvm_path: None,
+            #[cfg(feature = "dylibjvm")]
+            min_java_version: None,
             java_functions: vec![],
         };
 
@@ -471,8 +631,69 @@ impl JvmBuilder {
         this
     }
 
-    pub fn add_classpath(self, classpath: impl Display) -> Self {
-        self.custom(format!("-Djava.class.path={classpath}"))
+    /// Adds an entry (or several, separated by the platform's path separator) to the classpath.
+    /// All entries are merged into a single `-Djava.class.path` option.
+    pub fn add_classpath(mut self, classpath: impl Display) -> Self {
+        self.classpath.push(classpath.to_string());
+        self
+    }
+
+    /// Adds an entry to the module path, like `--module-path`.
+    /// All entries are merged into a single option.
+    pub fn add_module_path(mut self, module_path: impl Display) -> Self {
+        self.module_path.push(module_path.to_string());
+        self
+    }
+
+    /// Adds a root module to resolve, like `--add-modules`.
+    pub fn add_modules(mut self, modules: impl Display) -> Self {
+        self.add_modules.push(modules.to_string());
+        self
+    }
+
+    /// Opens a package to other modules, like `--add-opens java.base/java.lang=ALL-UNNAMED`.
+    pub fn add_opens(self, opens: impl Display) -> Self {
+        self.custom(format!("--add-opens={opens}"))
+    }
+
+    /// Sets a system property, like `-Dkey=value`. Setting the same property twice keeps the last value.
+    pub fn system_property(mut self, key: impl Display, value: impl Display) -> Self {
+        self.system_properties
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Sets the initial heap size, like `-Xms`. The size is in bytes, unless it has a
+    /// `k`, `m` or `g` suffix (e.g. `"512m"`).
+    pub fn initial_heap_size(mut self, size: impl Display) -> Self {
+        self.initial_heap_size = Some(size.to_string());
+        self
+    }
+
+    /// Sets the maximum heap size, like `-Xmx`. The size is in bytes, unless it has a
+    /// `k`, `m` or `g` suffix (e.g. `"2g"`).
+    pub fn max_heap_size(mut self, size: impl Display) -> Self {
+        self.max_heap_size = Some(size.to_string());
+        self
+    }
+
+    /// Sets the stack size of Java threads, like `-Xss`. The size is in bytes, unless it has a
+    /// `k`, `m` or `g` suffix (e.g. `"1m"`).
+    pub fn thread_stack_size(mut self, size: impl Display) -> Self {
+        self.thread_stack_size = Some(size.to_string());
+        self
+    }
+
+    /// Loads a Java agent, like `-javaagent:agent.jar=options`.
+    pub fn java_agent(self, agent: impl Display) -> Self {
+        self.custom(format!("-javaagent:{agent}"))
+    }
+
+    /// Whether the JVM should ignore options it does not recognize instead of failing to launch.
+    /// Only applies to non-standard options, like those starting with `-X`.
+    pub fn ignore_unrecognized(mut self, ignore: bool) -> Self {
+        self.ignore_unrecognized = ignore;
+        self
     }
 
     pub fn custom(mut self, opt_string: impl Into<String>) -> Self {
@@ -480,6 +701,48 @@ impl JvmBuilder {
         self
     }
 
+    /// Applies the configuration in the TOML file at `path`, so that JVMs can be tuned without
+    /// recompiling. All keys are optional:
+    ///
+    /// ```toml
+    /// classpath = ["app.jar", "lib/*"]
+    /// module-path = ["mods"]
+    /// add-modules = ["com.example"]
+    /// add-opens = ["java.base/java.lang=ALL-UNNAMED"]
+    /// initial-heap-size = "256m"
+    /// max-heap-size = "2g"
+    /// thread-stack-size = "1m"
+    /// java-agents = ["agent.jar=verbose"]
+    /// ignore-unrecognized = false
+    /// options = ["-XX:+UseG1GC"]
+    ///
+    /// [system-properties]
+    /// "file.encoding" = "UTF-8"
+    /// ```
+    ///
+    /// Lists are added to what was already configured, while other values replace it.
+    #[cfg(feature = "toml")]
+    pub fn config_file(self, path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path).map_err(|e| {
+            Error::InvalidJvmConfig(format!("failed to read `{}`: {e}", path.display()))
+        })?;
+        let config: JvmConfig = toml::from_str(&config).map_err(|e| {
+            Error::InvalidJvmConfig(format!("failed to parse `{}`: {e}", path.display()))
+        })?;
+        Ok(config.apply(self))
+    }
+
+    /// Applies the TOML file named by the `DUCHESS_JVM_CONFIG` environment variable, if it is set.
+    /// See [`config_file`](Self::config_file) for its format.
+    #[cfg(feature = "toml")]
+    pub fn config_from_env(self) -> Result<Self> {
+        match std::env::var_os("DUCHESS_JVM_CONFIG") {
+            Some(path) => self.config_file(path),
+            None => Ok(self),
+        }
+    }
+
     pub fn link(mut self, fns: impl IntoJavaFns) -> Self {
         self.java_functions.extend(fns.into_java_fns());
         self
@@ -491,18 +754,81 @@ impl JvmBuilder {
         self
     }
 
+    /// Requires a JVM for Java `version` or newer. Installations found during discovery that are
+    /// older (or whose version is unknown) are skipped, and launching fails if libjvm was already
+    /// loaded for an older Java version.
+    #[cfg(feature = "dylibjvm")]
+    pub fn min_java_version(mut self, version: u32) -> Self {
+        self.min_java_version = Some(version);
+        self
+    }
+
+    /// Loads libjvm, unless it's loaded already, from the path given to
+    /// [`load_libjvm_at`](Self::load_libjvm_at) or else from the first suitable JVM discovered.
+    #[cfg(feature = "dylibjvm")]
+    fn load_libjvm(&self) -> Result<()> {
+        match &self.libjvm_path {
+            Some(path) => crate::libjvm::libjvm_or_load_at(path, self.min_java_version)?,
+            None => crate::libjvm::libjvm_or_discover(self.min_java_version)?,
+        };
+        Ok(())
+    }
+
+    /// The options given to the JVM. Custom options come last, so they override the others.
+    fn jvm_options(&self) -> Vec<String> {
+        let separator = if cfg!(windows) { ";" } else { ":" };
+
+        let mut options = vec![];
+        for (key, value) in &self.system_properties {
+            options.push(format!("-D{key}={value}"));
+        }
+        if !self.classpath.is_empty() {
+            options.push(format!(
+                "-Djava.class.path={}",
+                self.classpath.join(separator)
+            ));
+        }
+        if !self.module_path.is_empty() {
+            options.push(format!(
+                "--module-path={}",
+                self.module_path.join(separator)
+            ));
+        }
+        if !self.add_modules.is_empty() {
+            options.push(format!("--add-modules={}", self.add_modules.join(",")));
+        }
+        if let Some(size) = &self.initial_heap_size {
+            options.push(format!("-Xms{size}"));
+        }
+        if let Some(size) = &self.max_heap_size {
+            options.push(format!("-Xmx{size}"));
+        }
+        if let Some(size) = &self.thread_stack_size {
+            options.push(format!("-Xss{size}"));
+        }
+        options.extend(self.options.iter().cloned());
+        options
+    }
+
+    /// Like [`try_launch`](Self::try_launch), but returns a guard that shuts the JVM down
+    /// (see [`Jvm::shutdown`]) when dropped.
+    pub fn try_launch_guarded(self) -> Result<ShutdownGuard> {
+        self.try_launch()?;
+        Ok(ShutdownGuard { _private: () })
+    }
+
     /// Launch a new JVM, returning [`Error::JvmAlreadyExists`] if one already exists.
     pub fn try_launch(self) -> Result<()> {
+        let _lock = lock_jvm()?;
+
         #[cfg(feature = "dylibjvm")]
-        if let Some(path) = self.libjvm_path {
-            crate::libjvm::libjvm_or_load_at(&path)?;
-        }
+        self.load_libjvm()?;
 
         let mut already_exists = true;
         GLOBAL_JVM.get_or_try_init(|| {
             // SAFETY: we're behind the GLOBAL_JVM lock and we won't race with other threads creating or finding an
             // existing JVM.
-            let jvm = unsafe { raw::try_create_jvm(self.options.into_iter()) }?;
+            let jvm = unsafe { raw::try_create_jvm(self.jvm_options(), self.ignore_unrecognized) }?;
             already_exists = false;
             Result::Ok(jvm)
         })?;
@@ -519,10 +845,14 @@ impl JvmBuilder {
     }
 
     pub fn launch_or_use_existing(self) -> Result<()> {
+        let _lock = lock_jvm()?;
+
         // The following code was added to address what appears to be a bug in jdk-17.0.15+6-LTS
         // If try_launch is called before existing_jvm, then existing_jvm does not find an already running
         // jvm. If existing_jvm is called before try_launch, then existing_jvm does find an already running
         // jvm.
+        #[cfg(feature = "dylibjvm")]
+        self.load_libjvm()?;
         let existing_jvm = unsafe { raw::existing_jvm() }?;
 
         if let Some(jvm) = existing_jvm {
@@ -546,6 +876,63 @@ impl JvmBuilder {
     }
 }
 
+/// Shuts the JVM down when dropped, see [`JvmBuilder::try_launch_guarded`].
+/// Errors are logged, since they can't be reported from a destructor.
+#[must_use = "the JVM is shut down as soon as the guard is dropped"]
+pub struct ShutdownGuard {
+    _private: (),
+}
+
+impl Drop for ShutdownGuard {
+    fn drop(&mut self) {
+        if let Err(err) = Jvm::shutdown() {
+            tracing::warn!(?err, "failed to shut down the JVM");
+        }
+    }
+}
+
+/// The contents of a configuration file loaded by [`JvmBuilder::config_file`].
+#[cfg(feature = "toml")]
+#[derive(serde::Deserialize, Default)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct JvmConfig {
+    classpath: Vec<String>,
+    module_path: Vec<String>,
+    add_modules: Vec<String>,
+    add_opens: Vec<String>,
+    system_properties: BTreeMap<String, String>,
+    initial_heap_size: Option<String>,
+    max_heap_size: Option<String>,
+    thread_stack_size: Option<String>,
+    java_agents: Vec<String>,
+    ignore_unrecognized: Option<bool>,
+    options: Vec<String>,
+}
+
+#[cfg(feature = "toml")]
+impl JvmConfig {
+    fn apply(self, mut builder: JvmBuilder) -> JvmBuilder {
+        builder.classpath.extend(self.classpath);
+        builder.module_path.extend(self.module_path);
+        builder.add_modules.extend(self.add_modules);
+        builder.system_properties.extend(self.system_properties);
+        builder.initial_heap_size = self.initial_heap_size.or(builder.initial_heap_size);
+        builder.max_heap_size = self.max_heap_size.or(builder.max_heap_size);
+        builder.thread_stack_size = self.thread_stack_size.or(builder.thread_stack_size);
+        if let Some(ignore) = self.ignore_unrecognized {
+            builder.ignore_unrecognized = ignore;
+        }
+        for opens in self.add_opens {
+            builder = builder.add_opens(opens);
+        }
+        for agent in self.java_agents {
+            builder = builder.java_agent(agent);
+        }
+        builder.options.extend(self.options);
+        builder
+    }
+}
+
 /// A trait for zero-sized dummy types that represent Java object types.
 ///
 /// # Safety
@@ -1,11 +1,201 @@
-use crate::Jvm;
+use crate::{java, thread, Jvm, JvmOp, ToJava};
 
 #[test]
 fn nested_jvm_with() {
     Jvm::with(|_jvm| {
-        let err = Jvm::with(|_jvm| Ok(())).expect_err("nested JVMs are illegal");
-        assert!(matches!(err, crate::Error::NestedUsage));
+        Jvm::with(|_jvm| Ok(())).expect("nested JVMs are allowed");
         Ok(())
     })
     .expect("returns Ok")
 }
+
+#[test]
+fn nested_execute_at_depth() {
+    fn nest(depth: usize) -> crate::Result<String> {
+        Jvm::with(|_jvm| {
+            assert_eq!(thread::depth(), depth + 1);
+            if depth < 8 {
+                Ok(nest(depth + 1))
+            } else {
+                Ok(depth
+                    .to_string()
+                    .to_java::<java::lang::String>()
+                    .assert_not_null()
+                    .execute())
+            }
+        })?
+    }
+
+    assert_eq!(nest(0).unwrap(), "8");
+    assert_eq!(thread::depth(), 0);
+}
+
+#[test]
+fn nested_frames_keep_outer_locals() {
+    Jvm::with(|jvm| {
+        let outer = java::lang::Object::new().do_jni(jvm)?;
+
+        // Create many locals in an inner frame; they must not invalidate `outer`.
+        for _ in 0..1000 {
+            Jvm::with(|jvm| {
+                let _inner = java::lang::Object::new().do_jni(jvm)?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        let hash: i32 = outer.hash_code().execute().unwrap();
+        let again: i32 = outer.hash_code().do_jni(jvm)?;
+        assert_eq!(hash, again);
+        Ok(())
+    })
+    .expect("returns Ok")
+}
+
+#[test]
+fn local_frame_frees_its_locals() {
+    Jvm::with(|jvm| {
+        let outer = java::lang::Object::new().do_jni(jvm)?;
+
+        // Leak far more locals than a single frame has room for; popping the frames frees them.
+        for _ in 0..100 {
+            jvm.with_local_frame(16, |jvm| {
+                jvm.ensure_local_capacity(1000)?;
+                for _ in 0..1000 {
+                    std::mem::forget(java::lang::Object::new().do_jni(jvm)?);
+                }
+                Ok(())
+            })?;
+        }
+
+        let hash: i32 = outer.hash_code().do_jni(jvm)?;
+        assert_eq!(hash, outer.hash_code().do_jni(jvm)?);
+        Ok(())
+    })
+    .expect("returns Ok")
+}
+
+#[test]
+fn local_frame_output_escapes() {
+    Jvm::with(|jvm| {
+        let list = java::util::ArrayList::<java::lang::Object>::new()
+            .in_local_frame(4)
+            .do_jni(jvm)?;
+        assert_eq!(list.size().do_jni(jvm)?, 0);
+        Ok(())
+    })
+    .expect("returns Ok");
+
+    let string: Option<String> = "frame"
+        .to_java::<java::lang::String>()
+        .in_local_frame(4)
+        .execute()
+        .unwrap();
+    assert_eq!(string.as_deref(), Some("frame"));
+}
+
+#[test]
+fn local_frame_exception_escapes() {
+    let list = java::util::ArrayList::<java::lang::Object>::new();
+    let result: crate::Result<Option<crate::Java<java::lang::Object>>> =
+        list.get(3).in_local_frame(4).execute();
+    match result {
+        Err(crate::Error::Thrown(exception)) => {
+            let message: String = exception.to_string().assert_not_null().execute().unwrap();
+            assert!(message.contains("IndexOutOfBoundsException"), "{message}");
+        }
+        _ => panic!("expected an exception"),
+    }
+}
+
+#[cfg(feature = "count-local-refs")]
+#[test]
+fn live_local_refs_are_counted() {
+    Jvm::with(|jvm| {
+        let before = Jvm::live_local_refs();
+        let object = java::lang::Object::new().do_jni(jvm)?;
+        assert_eq!(Jvm::live_local_refs(), before + 1);
+        let escaped = java::lang::Object::new().in_local_frame(4).do_jni(jvm)?;
+        assert_eq!(Jvm::live_local_refs(), before + 2);
+        drop((object, escaped));
+        assert_eq!(Jvm::live_local_refs(), before);
+        Ok(())
+    })
+    .expect("returns Ok")
+}
+
+#[test]
+fn builder_merges_options() {
+    let options = Jvm::builder()
+        .add_classpath("a")
+        .add_classpath("b")
+        .add_module_path("mods")
+        .add_modules("m1")
+        .add_modules("m2")
+        .system_property("key", "first")
+        .system_property("key", "second")
+        .max_heap_size("1g")
+        .custom("-Dkey=custom")
+        .jvm_options();
+
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    let classpaths: Vec<_> = options
+        .iter()
+        .filter(|option| option.starts_with("-Djava.class.path="))
+        .collect();
+    assert_eq!(classpaths.len(), 1);
+    assert!(classpaths[0].ends_with(&format!("a{separator}b")));
+
+    assert!(options.contains(&"--module-path=mods".to_string()));
+    assert!(options.contains(&"--add-modules=m1,m2".to_string()));
+    assert!(options.contains(&"-Xmx1g".to_string()));
+    assert!(!options.contains(&"-Dkey=first".to_string()));
+
+    // Custom options come last so that they take precedence
+    let property = options.iter().position(|o| o == "-Dkey=second").unwrap();
+    let custom = options.iter().position(|o| o == "-Dkey=custom").unwrap();
+    assert!(property < custom);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn builder_loads_config_file() {
+    let path = std::env::temp_dir().join(format!("duchess-jvm-{}.toml", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"
+            classpath = ["from-config.jar"]
+            add-opens = ["java.base/java.lang=ALL-UNNAMED"]
+            max-heap-size = "512m"
+            java-agents = ["agent.jar=verbose"]
+            ignore-unrecognized = true
+
+            [system-properties]
+            "config.key" = "value"
+        "#,
+    )
+    .unwrap();
+
+    let builder = Jvm::builder()
+        .add_classpath("from-code.jar")
+        .config_file(&path)
+        .unwrap();
+    assert!(builder.ignore_unrecognized);
+    let options = builder.jvm_options();
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    let classpath = format!("from-code.jar{separator}from-config.jar");
+    assert!(options
+        .iter()
+        .any(|o| o.starts_with("-Djava.class.path=") && o.ends_with(&classpath)));
+    assert!(options.contains(&"--add-opens=java.base/java.lang=ALL-UNNAMED".to_string()));
+    assert!(options.contains(&"-Xmx512m".to_string()));
+    assert!(options.contains(&"-javaagent:agent.jar=verbose".to_string()));
+    assert!(options.contains(&"-Dconfig.key=value".to_string()));
+
+    std::fs::write(&path, "unknown-key = 1").unwrap();
+    assert!(matches!(
+        Jvm::builder().config_file(&path),
+        Err(crate::Error::InvalidJvmConfig(_))
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
@@ -1,10 +1,15 @@
 //! Experiments with Java-Rust interop.
 
 mod array;
+mod buffer;
+mod callback;
 mod cast;
+mod combinators;
 mod error;
 mod find;
+mod frame;
 mod from_ref;
+mod future;
 mod into_rust;
 mod jvm;
 mod libjvm;
@@ -15,6 +20,7 @@ mod ops;
 mod raw;
 mod ref_;
 mod refs;
+mod stack_trace;
 mod str;
 mod thread;
 mod to_java;
@@ -23,16 +29,25 @@ mod try_catch;
 /// Contains reusable declarations for classes distributed by the JDK under the `java.*` packages.
 pub mod java;
 
-pub use duchess_macro::{java_function, java_package, ToJava, ToRust};
-pub use error::{Error, LocalResult, Result};
+/// Converts any `serde` type to and from a tree of Java maps, lists, strings and boxed primitives.
+pub mod serde;
+
+pub use buffer::{BufferMemory, DirectByteBuffer};
+pub use combinators::{join, JvmOpExt};
+pub use duchess_macro::{java_function, java_package, JavaException, ToJava, ToRust};
+pub use error::{Error, JavaException, LocalResult, Result};
+pub use frame::{FrameOutput, LocalFrame};
+pub use future::{JavaFuture, JvmOpFutureExt, RustFuture};
 pub use into_rust::IntoRust;
 pub use jvm::JavaObject;
 pub use jvm::JavaType;
 pub use jvm::Jvm;
+pub use jvm::ShutdownGuard;
 pub use link::JavaFunction;
 pub use null::Null;
-pub use ref_::{Java, Local};
+pub use ref_::{Java, JavaWeak, Local};
 pub use refs::{AsJRef, JDeref, NullJRef, Nullable, TryJDeref};
+pub use stack_trace::{JavaStackFrame, JavaStackTrace};
 pub use try_catch::TryCatch;
 
 pub use prelude::*;
@@ -43,8 +58,8 @@ pub mod prelude {
     pub use crate::jvm::JvmOp;
     pub use crate::link::JavaFn;
     pub use crate::ops::{
-        IntoJava, IntoScalar, IntoVoid, JavaConstructor, JavaField, JavaMethod, ScalarField,
-        ScalarMethod, VoidMethod,
+        IntoJava, IntoScalar, IntoScalarVarargs, IntoVarargs, IntoVoid, JavaConstructor, JavaField,
+        JavaMethod, ScalarField, ScalarMethod, VoidMethod,
     };
     pub use crate::refs::{AsJRef, JDeref, TryJDeref};
     pub use crate::to_java::ToJava;
@@ -71,11 +86,13 @@ pub mod semver_unstable {
     pub use crate::refs::NullJRef;
     pub use crate::to_java::{ToJavaImpl, ToJavaScalar};
     pub use duchess_macro_rules::{
-        argument_impl_trait, field_output_trait, jni_call_fn, jni_static_call_fn,
-        jni_static_field_get_fn, macro_if, mro, output_trait, output_type, prepare_input, rust_ty,
-        setup_class, setup_constructor, setup_inherent_object_method, setup_java_function,
-        setup_obj_method, setup_op_method, setup_static_field_getter, setup_static_method,
-        view_of_obj, view_of_op,
+        argument_impl_trait, field_output_trait, jni_call_fn, jni_field_get_fn, jni_field_set_fn,
+        jni_field_value, jni_static_call_fn, jni_static_field_get_fn, jni_static_field_set_fn,
+        macro_if, mro, output_trait, output_type, prepare_input, rust_ty, setup_class,
+        setup_constructor, setup_enum_constant, setup_inherent_object_field_getter,
+        setup_inherent_object_field_setter, setup_inherent_object_method, setup_java_function,
+        setup_obj_method, setup_op_method, setup_static_field_getter, setup_static_field_setter,
+        setup_static_method, view_of_obj, view_of_op,
     };
     pub use jni_sys;
     pub use once_cell;
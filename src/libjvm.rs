@@ -15,9 +15,17 @@ pub(crate) struct Libjvm {
     ) -> jni_sys::jint,
 }
 
+/// Environment variable that overrides JVM discovery. It holds the path of either a `libjvm` library or a Java
+/// installation.
+#[cfg(feature = "dylibjvm")]
+pub(crate) const LIBJVM_VAR: &str = "DUCHESS_LIBJVM";
+
 #[cfg(feature = "dylibjvm")]
 mod dynlib {
-    use std::path::{Path, PathBuf};
+    use std::{
+        fmt,
+        path::{Path, PathBuf},
+    };
 
     use libloading::Library;
     use once_cell::sync::OnceCell;
@@ -25,44 +33,318 @@ mod dynlib {
     use super::*;
     use crate::Error;
 
-    static LIBJVM: OnceCell<Libjvm> = OnceCell::new();
+    /// The loaded libjvm, along with where it came from.
+    struct Loaded {
+        libjvm: Libjvm,
+        path: PathBuf,
+        java_version: Option<u32>,
+    }
+
+    static LIBJVM: OnceCell<Loaded> = OnceCell::new();
 
     #[allow(non_snake_case)]
-    fn load_libjvm_at(path: &Path) -> Result<Libjvm> {
+    fn load_libjvm_at(path: &Path, java_version: Option<u32>) -> Result<Loaded> {
         (|| {
             let lib = unsafe { Library::new(path) }?;
             let JNI_CreateJavaVM = *unsafe { lib.get(b"JNI_CreateJavaVM\0") }?;
             let JNI_GetCreatedJavaVMs = *unsafe { lib.get(b"JNI_GetCreatedJavaVMs\0") }?;
             std::mem::forget(lib); // We keep the JVM (and therefore libjvm) around through the end of the process
-            Ok(Libjvm {
-                JNI_CreateJavaVM,
-                JNI_GetCreatedJavaVMs,
+            Ok(Loaded {
+                libjvm: Libjvm {
+                    JNI_CreateJavaVM,
+                    JNI_GetCreatedJavaVMs,
+                },
+                path: path.to_owned(),
+                java_version,
             })
         })()
         .map_err(|e: libloading::Error| Error::UnableToLoadLibjvm(Box::new(e)))
     }
 
     pub(crate) fn libjvm_or_load() -> Result<&'static Libjvm> {
-        LIBJVM.get_or_try_init(|| {
-            let path: PathBuf = [
-                &java_locator::locate_jvm_dyn_library()
-                    .map_err(|e| Error::UnableToLoadLibjvm(Box::new(e)))?,
-                java_locator::get_jvm_dyn_lib_file_name(),
-            ]
-            .into_iter()
+        libjvm_or_discover(None)
+    }
+
+    /// Loads the first libjvm found by [`discover`] whose Java version is at least `min_version`.
+    /// If libjvm is already loaded, checks that it meets `min_version`.
+    pub(crate) fn libjvm_or_discover(min_version: Option<u32>) -> Result<&'static Libjvm> {
+        let loaded = LIBJVM.get_or_try_init(|| {
+            let found = discover(candidates(), min_version)
+                .map_err(|e| Error::UnableToLoadLibjvm(Box::new(e)))?;
+            tracing::debug!(path = ?found.libjvm, java_version = ?found.java_version, "loading libjvm");
+            load_libjvm_at(&found.libjvm, found.java_version)
+        })?;
+        check_loaded(loaded, min_version)
+    }
+
+    pub(crate) fn libjvm_or_load_at(
+        path: &Path,
+        min_version: Option<u32>,
+    ) -> Result<&'static Libjvm> {
+        let loaded = LIBJVM
+            .get_or_try_init(|| load_libjvm_at(path, java_home_of(path).and_then(java_version)))?;
+        check_loaded(loaded, min_version)
+    }
+
+    /// An explicitly chosen libjvm whose Java version is unknown is given the benefit of the doubt.
+    fn check_loaded(loaded: &'static Loaded, min_version: Option<u32>) -> Result<&'static Libjvm> {
+        match (loaded.java_version, min_version) {
+            (Some(version), Some(min_version)) if version < min_version => {
+                Err(Error::UnableToLoadLibjvm(Box::new(NoSuitableJvm {
+                    min_version: Some(min_version),
+                    rejected: vec![Rejected {
+                        path: loaded.path.clone(),
+                        reason: format!("already loaded, but it is Java {version}"),
+                    }],
+                })))
+            }
+            _ => Ok(&loaded.libjvm),
+        }
+    }
+
+    /// A libjvm chosen by [`discover`].
+    #[derive(Debug, PartialEq, Eq)]
+    pub(super) struct Found {
+        pub(super) libjvm: PathBuf,
+        pub(super) java_version: Option<u32>,
+    }
+
+    /// A candidate that [`discover`] passed over.
+    #[derive(Debug)]
+    pub(super) struct Rejected {
+        pub(super) path: PathBuf,
+        pub(super) reason: String,
+    }
+
+    /// Returned when no candidate is suitable, listing all of them with the reason they were rejected.
+    #[derive(Debug)]
+    pub(super) struct NoSuitableJvm {
+        pub(super) min_version: Option<u32>,
+        pub(super) rejected: Vec<Rejected>,
+    }
+
+    impl fmt::Display for NoSuitableJvm {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.min_version {
+                Some(min_version) => {
+                    write!(f, "unable to find a JVM for Java {min_version} or newer")?
+                }
+                None => write!(f, "unable to find a JVM")?,
+            }
+            if self.rejected.is_empty() {
+                write!(
+                    f,
+                    "; set `JAVA_HOME` or `{LIBJVM_VAR}`, or put `java` on the `PATH`"
+                )?;
+            }
+            for rejected in &self.rejected {
+                write!(f, "\n  {}: {}", rejected.path.display(), rejected.reason)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl std::error::Error for NoSuitableJvm {}
+
+    /// Picks the first candidate (a libjvm library or a Java installation) that has a libjvm and whose Java version
+    /// is at least `min_version`.
+    pub(super) fn discover(
+        candidates: impl IntoIterator<Item = PathBuf>,
+        min_version: Option<u32>,
+    ) -> std::result::Result<Found, NoSuitableJvm> {
+        let mut rejected = vec![];
+        let mut seen = vec![];
+        for path in candidates {
+            let reason = match resolve(&path) {
+                Ok(found) => {
+                    // The same installation is often reachable in several ways (e.g. `JAVA_HOME` and `PATH`)
+                    let canonical = found
+                        .libjvm
+                        .canonicalize()
+                        .unwrap_or_else(|_| found.libjvm.clone());
+                    if seen.contains(&canonical) {
+                        continue;
+                    }
+                    seen.push(canonical);
+
+                    match (found.java_version, min_version) {
+                        (_, None) => return Ok(found),
+                        (Some(version), Some(min_version)) if version >= min_version => {
+                            return Ok(found)
+                        }
+                        (Some(version), Some(_)) => format!("Java {version} is too old"),
+                        (None, Some(_)) => "unable to determine its Java version".to_string(),
+                    }
+                }
+                Err(reason) => reason,
+            };
+            tracing::debug!(?path, reason, "skipping JVM candidate");
+            rejected.push(Rejected { path, reason });
+        }
+
+        Err(NoSuitableJvm {
+            min_version,
+            rejected,
+        })
+    }
+
+    /// Finds the libjvm and the Java version of a candidate.
+    fn resolve(path: &Path) -> std::result::Result<Found, String> {
+        if path.is_file() {
+            Ok(Found {
+                libjvm: path.to_owned(),
+                java_version: java_home_of(path).and_then(java_version),
+            })
+        } else if path.is_dir() {
+            let libjvm = libjvm_in(path).ok_or_else(|| "no libjvm found in it".to_string())?;
+            Ok(Found {
+                libjvm,
+                java_version: java_version(path),
+            })
+        } else {
+            Err("does not exist".to_string())
+        }
+    }
+
+    /// The places to look for a JVM, in order of preference.
+    fn candidates() -> Vec<PathBuf> {
+        let mut candidates = vec![];
+        if let Some(path) = std::env::var_os(LIBJVM_VAR) {
+            candidates.push(PathBuf::from(path));
+        }
+        if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+            candidates.push(PathBuf::from(java_home));
+        }
+        candidates.extend(java_home_on_path());
+        candidates.extend(installed_java_homes());
+        candidates
+    }
+
+    /// The Java installation containing the `java` executable on the `PATH`, if any.
+    fn java_home_on_path() -> Option<PathBuf> {
+        let java = if cfg!(windows) { "java.exe" } else { "java" };
+        let path = std::env::var_os("PATH")?;
+        let java = std::env::split_paths(&path)
+            .map(|dir| dir.join(java))
+            .find(|java| java.is_file())?;
+        // `java` is usually a symlink, e.g. to `/usr/lib/jvm/<jdk>/bin/java`
+        let java = java.canonicalize().ok()?;
+        Some(java.parent()?.parent()?.to_owned())
+    }
+
+    /// Java installations in the usual directories of the platform, newest first.
+    fn installed_java_homes() -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = vec![];
+        let mut home_suffix = None;
+        if cfg!(target_os = "macos") {
+            roots.push("/Library/Java/JavaVirtualMachines".into());
+            if let Some(home) = std::env::var_os("HOME") {
+                roots.push(Path::new(&home).join("Library/Java/JavaVirtualMachines"));
+            }
+            home_suffix = Some("Contents/Home");
+        } else if cfg!(windows) {
+            for var in ["ProgramFiles", "ProgramW6432"] {
+                if let Some(program_files) = std::env::var_os(var) {
+                    let program_files = Path::new(&program_files);
+                    for vendor in [
+                        "Java",
+                        "Eclipse Adoptium",
+                        "Microsoft",
+                        "Zulu",
+                        "Amazon Corretto",
+                    ] {
+                        roots.push(program_files.join(vendor));
+                    }
+                }
+            }
+        } else {
+            for root in [
+                "/usr/lib/jvm",
+                "/usr/lib64/jvm",
+                "/usr/java",
+                "/opt/java",
+                "/opt/jdk",
+            ] {
+                roots.push(root.into());
+            }
+        }
+
+        let mut homes: Vec<(Option<u32>, PathBuf)> = roots
+            .iter()
+            .filter_map(|root| std::fs::read_dir(root).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| match home_suffix {
+                Some(suffix) => entry.path().join(suffix),
+                None => entry.path(),
+            })
+            .filter(|home| home.is_dir())
+            .map(|home| (java_version(&home), home))
             .collect();
+        // Installations of unknown version come last
+        homes.sort_by(|(a, _), (b, _)| b.cmp(a));
+        homes.into_iter().map(|(_, home)| home).collect()
+    }
 
-            load_libjvm_at(&path)
+    /// Finds libjvm within a Java installation, trying the layouts of current JDKs and of Java 8 JDKs and JREs.
+    pub(super) fn libjvm_in(java_home: &Path) -> Option<PathBuf> {
+        let file_name = libloading::library_filename("jvm");
+        // Java 8 puts libraries in an architecture-specific directory
+        let arch = match std::env::consts::ARCH {
+            "x86_64" => "amd64",
+            "x86" => "i386",
+            arch => arch,
+        };
+        let lib_dirs = if cfg!(windows) {
+            vec!["bin".to_string(), "jre/bin".to_string()]
+        } else {
+            vec![
+                "lib".to_string(),
+                "jre/lib".to_string(),
+                format!("lib/{arch}"),
+                format!("jre/lib/{arch}"),
+            ]
+        };
+        lib_dirs
+            .iter()
+            .flat_map(|lib_dir| ["server", "client"].map(|vm| java_home.join(lib_dir).join(vm)))
+            .map(|dir| dir.join(&file_name))
+            .find(|libjvm| libjvm.is_file())
+    }
+
+    /// The Java installation a libjvm library belongs to, i.e. the closest ancestor with a `release` file.
+    fn java_home_of(libjvm: &Path) -> Option<&Path> {
+        libjvm
+            .ancestors()
+            .skip(1)
+            .take(5)
+            .find(|dir| dir.join("release").is_file())
+    }
+
+    /// Reads the Java version of an installation from its `release` file.
+    pub(super) fn java_version(java_home: &Path) -> Option<u32> {
+        let release = std::fs::read_to_string(java_home.join("release")).ok()?;
+        release.lines().find_map(|line| {
+            let version = line.strip_prefix("JAVA_VERSION=")?;
+            parse_java_version(version.trim().trim_matches('"'))
         })
     }
 
-    pub(crate) fn libjvm_or_load_at(path: &Path) -> Result<&'static Libjvm> {
-        LIBJVM.get_or_try_init(|| load_libjvm_at(path))
+    /// Parses the feature version out of a Java version string, e.g. 8 out of `1.8.0_392` and 17 out of `17.0.2`.
+    pub(super) fn parse_java_version(version: &str) -> Option<u32> {
+        let mut parts = version.split(|c: char| !c.is_ascii_digit());
+        match parts.next()?.parse().ok()? {
+            1 => parts.next()?.parse().ok(),
+            version => Some(version),
+        }
     }
 }
 
 #[cfg(feature = "dylibjvm")]
-pub(crate) use dynlib::{libjvm_or_load, libjvm_or_load_at};
+pub(crate) use dynlib::{libjvm_or_discover, libjvm_or_load, libjvm_or_load_at};
+
+#[cfg(all(test, feature = "dylibjvm"))]
+mod test;
 
 #[cfg(not(feature = "dylibjvm"))]
 pub(crate) fn libjvm_or_load() -> Result<&'static Libjvm> {
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+use super::dynlib::{discover, java_version, libjvm_in, parse_java_version};
+
+/// Creates a fake Java installation with a libjvm at `lib_dir` and, if given, a `release` file.
+fn fake_java_home(name: &str, lib_dir: &str, release_version: Option<&str>) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("duchess-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&home);
+    std::fs::create_dir_all(home.join(lib_dir)).unwrap();
+    std::fs::write(
+        home.join(lib_dir).join(libloading::library_filename("jvm")),
+        "",
+    )
+    .unwrap();
+    if let Some(version) = release_version {
+        std::fs::write(
+            home.join("release"),
+            format!("IMPLEMENTOR=\"Duchess\"\nJAVA_VERSION=\"{version}\"\n"),
+        )
+        .unwrap();
+    }
+    home
+}
+
+fn server_dir() -> &'static str {
+    if cfg!(windows) {
+        "bin/server"
+    } else {
+        "lib/server"
+    }
+}
+
+#[test]
+fn parses_java_versions() {
+    assert_eq!(parse_java_version("1.8.0_392"), Some(8));
+    assert_eq!(parse_java_version("17.0.15"), Some(17));
+    assert_eq!(parse_java_version("21"), Some(21));
+    assert_eq!(parse_java_version("22-ea"), Some(22));
+    assert_eq!(parse_java_version("unknown"), None);
+}
+
+#[test]
+fn finds_libjvm_in_java_home() {
+    let home = fake_java_home("layout", server_dir(), Some("17.0.2"));
+    assert_eq!(java_version(&home), Some(17));
+    assert_eq!(
+        libjvm_in(&home),
+        Some(
+            home.join(server_dir())
+                .join(libloading::library_filename("jvm"))
+        )
+    );
+    assert_eq!(libjvm_in(Path::new("/does/not/exist")), None);
+}
+
+#[test]
+fn discovers_first_suitable_jvm() {
+    let old = fake_java_home("old", server_dir(), Some("1.8.0_392"));
+    let unknown = fake_java_home("unknown", server_dir(), None);
+    let new = fake_java_home("new", server_dir(), Some("21.0.1"));
+    let candidates = || {
+        vec![
+            PathBuf::from("/does/not/exist"),
+            old.clone(),
+            unknown.clone(),
+            new.clone(),
+        ]
+    };
+
+    let found = discover(candidates(), None).unwrap();
+    assert_eq!(found.libjvm, libjvm_in(&old).unwrap());
+    assert_eq!(found.java_version, Some(8));
+
+    let found = discover(candidates(), Some(11)).unwrap();
+    assert_eq!(found.libjvm, libjvm_in(&new).unwrap());
+    assert_eq!(found.java_version, Some(21));
+
+    // A libjvm library given directly has the version of the installation around it
+    let found = discover([libjvm_in(&new).unwrap()], Some(21)).unwrap();
+    assert_eq!(found.java_version, Some(21));
+
+    let error = discover(candidates(), Some(25)).unwrap_err();
+    let reasons: Vec<_> = error
+        .rejected
+        .iter()
+        .map(|rejected| rejected.reason.as_str())
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            "does not exist",
+            "Java 8 is too old",
+            "unable to determine its Java version",
+            "Java 21 is too old",
+        ]
+    );
+    let message = error.to_string();
+    assert!(message.starts_with("unable to find a JVM for Java 25 or newer"));
+    assert!(message.contains(&format!("{}: Java 21 is too old", new.display())));
+}
@@ -1,3 +1,7 @@
+use std::borrow::Borrow;
+
+use crate::array::{ObjectArrayOp, ScalarArrayOp};
+use crate::java;
 use crate::jvm::JavaScalar;
 use crate::jvm::Jvm;
 use crate::jvm::JvmOp;
@@ -96,6 +100,84 @@ where
     }
 }
 
+/// Value that can be given as the trailing varargs argument (`T...`) of a Java method.
+///
+/// This is implemented for anything that can be iterated to produce arguments of type `T`,
+/// like a Rust slice, array, or `Vec`, in which case the Java array is created as part of the call.
+/// An existing Java array can also be given.
+pub trait IntoVarargs<T: JavaObject> {
+    type JvmOp: JvmRefOp<java::Array<T>>;
+
+    fn into_op(self) -> Self::JvmOp;
+}
+
+impl<I, T> IntoVarargs<T> for I
+where
+    T: JavaObject,
+    I: IntoIterator,
+    I::Item: IntoJava<T>,
+{
+    type JvmOp = ObjectArrayOp<T, <I::Item as IntoJava<T>>::JvmOp>;
+
+    fn into_op(self) -> Self::JvmOp {
+        ObjectArrayOp::new(self.into_iter().map(|e| e.into_op()).collect())
+    }
+}
+
+impl<T: JavaObject> IntoVarargs<T> for &Java<java::Array<T>> {
+    type JvmOp = Self;
+
+    fn into_op(self) -> Self::JvmOp {
+        self
+    }
+}
+
+impl<T: JavaObject> IntoVarargs<T> for &Local<'_, java::Array<T>> {
+    type JvmOp = Self;
+
+    fn into_op(self) -> Self::JvmOp {
+        self
+    }
+}
+
+/// Value that can be given as the trailing varargs argument of a Java method expecting
+/// scalar values of type `T` (e.g., `int...`). See [`IntoVarargs`][] for more details.
+pub trait IntoScalarVarargs<T: JavaScalar> {
+    type JvmOp: JvmRefOp<java::Array<T>>;
+
+    fn into_op(self) -> Self::JvmOp;
+}
+
+impl<I, T> IntoScalarVarargs<T> for I
+where
+    T: JavaScalar + Copy,
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+    ScalarArrayOp<T>: JvmRefOp<java::Array<T>>,
+{
+    type JvmOp = ScalarArrayOp<T>;
+
+    fn into_op(self) -> Self::JvmOp {
+        ScalarArrayOp::new(self.into_iter().map(|e| *e.borrow()).collect())
+    }
+}
+
+impl<T: JavaScalar> IntoScalarVarargs<T> for &Java<java::Array<T>> {
+    type JvmOp = Self;
+
+    fn into_op(self) -> Self::JvmOp {
+        self
+    }
+}
+
+impl<T: JavaScalar> IntoScalarVarargs<T> for &Local<'_, java::Array<T>> {
+    type JvmOp = Self;
+
+    fn into_op(self) -> Self::JvmOp {
+        self
+    }
+}
+
 /// A [`JvmOp`] that produces a [`Local`] reference to a `T` object.
 /// Local references are values that are only valid in this JNI call.
 /// They can be converted to [`Global`] references.
@@ -9,25 +9,36 @@ use std::{
     marker::PhantomData,
     mem::MaybeUninit,
     ptr::{self, NonNull},
+    sync::atomic::{AtomicI32, Ordering},
 };
 
 use jni_sys::jvalue;
 
-use crate::{jvm::JavaObjectExt, semver_unstable::ToJavaScalar, Error, JavaObject, Jvm, Local};
+use crate::{
+    java::lang::Throwable, jvm::JavaObjectExt, semver_unstable::ToJavaScalar, AsJRef, Error,
+    JavaObject, Jvm, Local,
+};
 
-// Set the JNI API version
-// JNI 1.8 is the default not in android
-// JNI 1.6 is the default for android
-// If the JNI version is specified through features, use the newest API version
-// specified in the features
-#[cfg(any(
-    all(
-        not(any(feature = "jni_1_6", feature = "jni_1_8",)),
-        not(target_os = "android")
-    ),
-    feature = "jni_1_8"
+// JNI versions duchess can request, newest first. The version actually used is negotiated with the JVM at runtime:
+// the newest one it supports is picked.
+//
+// The `jni_1_6` and `jni_1_8` features cap the negotiated version, and so does Android (which supports JNI 1.6 and
+// below). If several features are enabled, the newest version wins.
+const VERSIONS: [jni_sys::jint; 8] = [
+    jni_sys::JNI_VERSION_24,
+    jni_sys::JNI_VERSION_21,
+    jni_sys::JNI_VERSION_20,
+    jni_sys::JNI_VERSION_19,
+    jni_sys::JNI_VERSION_10,
+    jni_sys::JNI_VERSION_9,
+    jni_sys::JNI_VERSION_1_8,
+    jni_sys::JNI_VERSION_1_6,
+];
+#[cfg(all(
+    not(any(feature = "jni_1_6", feature = "jni_1_8")),
+    not(target_os = "android")
 ))]
-const VERSION: jni_sys::jint = jni_sys::JNI_VERSION_1_8;
+const MAX_VERSION: jni_sys::jint = jni_sys::JNI_VERSION_24;
 #[cfg(any(
     all(
         not(any(feature = "jni_1_6", feature = "jni_1_8",)),
@@ -35,10 +46,35 @@ const VERSION: jni_sys::jint = jni_sys::JNI_VERSION_1_8;
     ),
     all(feature = "jni_1_6", not(feature = "jni_1_8"))
 ))]
-const VERSION: jni_sys::jint = jni_sys::JNI_VERSION_1_6;
+const MAX_VERSION: jni_sys::jint = jni_sys::JNI_VERSION_1_6;
+#[cfg(feature = "jni_1_8")]
+const MAX_VERSION: jni_sys::jint = jni_sys::JNI_VERSION_1_8;
 #[cfg(all(target_os = "android", feature = "jni_1_8"))]
 std::compile_error!("Set to use JNI API 1.8+ when compiling for Android, invalid. (Android supports JNI 1.6 and below)");
 
+/// The JNI version negotiated with the JVM, or 0 until then.
+static VERSION: AtomicI32 = AtomicI32::new(0);
+
+/// Returns the JNI version negotiated with the JVM, if it has been used already.
+pub(crate) fn jni_version() -> Option<jni_sys::jint> {
+    match VERSION.load(Ordering::Relaxed) {
+        0 => None,
+        version => Some(version),
+    }
+}
+
+/// Returns the JNI versions worth requesting from the JVM, newest first: the negotiated one if any, otherwise every
+/// version up to [`MAX_VERSION`].
+fn candidate_versions() -> impl Iterator<Item = jni_sys::jint> {
+    let negotiated = jni_version();
+    VERSIONS
+        .into_iter()
+        .filter(move |&version| match negotiated {
+            Some(negotiated) => version == negotiated,
+            None => version <= MAX_VERSION,
+        })
+}
+
 /// Get a [`JvmPtr`] to an already initialized JVM (if one exists).
 ///
 /// If the `dynlibjvm` feature is enabled and `libjvm` isn't already loaded, it will first force it to be loaded.
@@ -86,6 +122,7 @@ pub(crate) unsafe fn existing_jvm() -> crate::Result<Option<JvmPtr>> {
 /// Caller must ensure that no two threads race to call this fn or [`jvm()`].
 pub(crate) unsafe fn try_create_jvm<'a>(
     options: impl IntoIterator<Item = String>,
+    ignore_unrecognized: bool,
 ) -> crate::Result<JvmPtr> {
     let libjvm = crate::libjvm::libjvm_or_load()?;
 
@@ -102,25 +139,40 @@ pub(crate) unsafe fn try_create_jvm<'a>(
         })
         .collect::<Vec<_>>();
 
-    let mut args = jni_sys::JavaVMInitArgs {
-        version: VERSION,
-        nOptions: options.len().try_into().unwrap(),
-        options: option_ptrs.as_mut_ptr(),
-        ignoreUnrecognized: jni_sys::JNI_FALSE,
-    };
-
+    let mut code = jni_sys::JNI_EVERSION;
     let mut jvm = std::ptr::null_mut::<jni_sys::JavaVM>();
-    let mut env = std::ptr::null_mut::<ffi::c_void>();
-
-    // SAFETY: the C strings pointed to be options are valid and non-null through the end of the call. They're not
-    // needed once it returns.
-    let code = unsafe {
-        (libjvm.JNI_CreateJavaVM)(
-            &mut jvm as *mut _,
-            &mut env as *mut _,
-            &mut args as *mut _ as *mut ffi::c_void,
-        )
-    };
+    for version in candidate_versions() {
+        let mut args = jni_sys::JavaVMInitArgs {
+            version,
+            nOptions: options.len().try_into().unwrap(),
+            options: option_ptrs.as_mut_ptr(),
+            ignoreUnrecognized: if ignore_unrecognized {
+                jni_sys::JNI_TRUE
+            } else {
+                jni_sys::JNI_FALSE
+            },
+        };
+
+        let mut env = std::ptr::null_mut::<ffi::c_void>();
+
+        // SAFETY: the C strings pointed to be options are valid and non-null through the end of the call. They're not
+        // needed once it returns.
+        code = unsafe {
+            (libjvm.JNI_CreateJavaVM)(
+                &mut jvm as *mut _,
+                &mut env as *mut _,
+                &mut args as *mut _ as *mut ffi::c_void,
+            )
+        };
+
+        // Older JVMs reject newer versions before doing anything else, so we can just retry with the next one
+        if code != jni_sys::JNI_EVERSION {
+            if code == jni_sys::JNI_OK {
+                VERSION.store(version, Ordering::Relaxed);
+            }
+            break;
+        }
+    }
 
     match code {
         jni_sys::JNI_OK => {
@@ -134,12 +186,30 @@ pub(crate) unsafe fn try_create_jvm<'a>(
             Ok(jvm)
         }
         jni_sys::JNI_EEXIST => Err(Error::JvmAlreadyExists),
+        jni_sys::JNI_EVERSION => Err(unsupported_versions()),
         _ => Err(Error::JvmInternal(format!(
             "CreateJavaVM failed with code `{code}`"
         ))),
     }
 }
 
+fn unsupported_versions() -> Error<crate::Java<Throwable>> {
+    Error::JvmInternal(format!(
+        "the JVM supports none of the JNI versions up to {}",
+        version_name(MAX_VERSION)
+    ))
+}
+
+/// Formats a JNI version as it is usually written, e.g. `1.8` or `21`.
+fn version_name(version: jni_sys::jint) -> String {
+    let (major, minor) = (version >> 16, version & 0xffff);
+    if major == 1 {
+        format!("{major}.{minor}")
+    } else {
+        format!("{major}")
+    }
+}
+
 #[doc(hidden)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct JvmPtr(NonNull<jni_sys::JavaVM>);
@@ -158,17 +228,26 @@ impl JvmPtr {
     /// JVM.
     pub(crate) unsafe fn env<'jvm>(self) -> crate::Result<Option<EnvPtr<'jvm>>> {
         let mut env_ptr = std::ptr::null_mut::<ffi::c_void>();
-        match fn_table_call(
-            self.0,
-            |jvm| jvm.GetEnv,
-            |jvm, f| f(jvm, &mut env_ptr as *mut _, VERSION),
-        ) {
-            jni_sys::JNI_OK => Ok(Some(EnvPtr::new(env_ptr.cast()).unwrap())),
-            jni_sys::JNI_EDETACHED => Ok(None),
-            code => Err(Error::JvmInternal(format!(
-                "GetEnv failed with code `{code}`"
-            ))),
+        for version in candidate_versions() {
+            match fn_table_call(
+                self.0,
+                |jvm| jvm.GetEnv,
+                |jvm, f| f(jvm, &mut env_ptr as *mut _, version),
+            ) {
+                jni_sys::JNI_OK => {
+                    VERSION.store(version, Ordering::Relaxed);
+                    return Ok(Some(EnvPtr::new(env_ptr.cast()).unwrap()));
+                }
+                jni_sys::JNI_EDETACHED => return Ok(None),
+                jni_sys::JNI_EVERSION => continue,
+                code => {
+                    return Err(Error::JvmInternal(format!(
+                        "GetEnv failed with code `{code}`"
+                    )))
+                }
+            }
         }
+        Err(unsupported_versions())
     }
 
     /// Attaches the current thread to the JVM and returns an [`EnvPtr`] that can be used to invoke JNI methods.
@@ -191,7 +270,13 @@ impl JvmPtr {
                 )
             },
         ) {
-            jni_sys::JNI_OK => Ok(EnvPtr::new(env_ptr.cast()).unwrap()),
+            jni_sys::JNI_OK => {
+                // Attaching does not tell which JNI version the JVM supports, GetEnv does
+                if jni_version().is_none() {
+                    self.env()?;
+                }
+                Ok(EnvPtr::new(env_ptr.cast()).unwrap())
+            }
             code => Err(Error::JvmInternal(format!(
                 "AttachCurrentThread failed with code `{code}`"
             ))),
@@ -211,6 +296,21 @@ impl JvmPtr {
             ))),
         }
     }
+
+    /// Unloads the JVM, waiting until the current thread is the only non-daemon thread left and
+    /// running the Java shutdown hooks.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the JVM is not used in any way afterwards.
+    pub(crate) unsafe fn destroy(self) -> crate::Result<()> {
+        match fn_table_call(self.0, |jvm| jvm.DestroyJavaVM, |jvm, f| f(jvm)) {
+            jni_sys::JNI_OK => Ok(()),
+            code => Err(Error::JvmInternal(format!(
+                "DestroyJavaVM failed with code `{code}`"
+            ))),
+        }
+    }
 }
 
 /// Invokes a JNI function through a virtual table interface
@@ -220,7 +320,7 @@ unsafe fn fn_table_call<T, F, R>(
     call: impl FnOnce(*mut *const T, F) -> R,
 ) -> R {
     let fn_field = fn_field(&**table_ptr.as_ptr());
-    // SAFETY: We specify VERSION when accessing the JNI interfaces and libjvm promises these fn pointers will be
+    // SAFETY: We specify a negotiated VERSION when accessing the JNI interfaces and libjvm promises these fn pointers will be
     // non-null
     let fn_field = fn_field.unwrap_unchecked();
     call(table_ptr.as_ptr(), fn_field)
@@ -336,6 +436,78 @@ impl<'jvm> EnvPtr<'jvm> {
         }
     }
 
+    /// Creates a new local reference frame in which at least `capacity` local references can be created.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the frame is popped with [`Self::pop_local_frame()`] before any frame that
+    /// encloses it, and that no local refs created within the frame are used after it is popped.
+    pub(crate) unsafe fn push_local_frame<T>(self, capacity: i32) -> Result<(), Error<T>>
+    where
+        T: AsJRef<Throwable>,
+    {
+        let result = self.invoke_unchecked(|env| env.PushLocalFrame, |env, f| f(env, capacity));
+        if result == jni_sys::JNI_OK {
+            Ok(())
+        } else {
+            // PushLocalFrame leaves an OutOfMemoryError pending, which we don't want leaking into later calls
+            self.invoke_unchecked(|env| env.ExceptionClear, |env, f| f(env));
+            Err(Error::JvmInternal(format!(
+                "PushLocalFrame failed with code `{result}`"
+            )))
+        }
+    }
+
+    /// Pops the innermost local reference frame, freeing all local refs created within it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have previously pushed the frame with [`Self::push_local_frame()`] and ensure that no local
+    /// refs created within it are used afterwards.
+    pub(crate) unsafe fn pop_local_frame(self) {
+        self.invoke_unchecked(|env| env.PopLocalFrame, |env, f| f(env, ptr::null_mut()));
+    }
+
+    /// Pops the innermost local reference frame like [`Self::pop_local_frame()`], but moves `result`, a local
+    /// ref created within the frame, into the enclosing frame. Returns the new local ref to the same object.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`Self::pop_local_frame()`]. In addition, `result` must be a live local ref that is not used (or
+    /// deleted) afterwards.
+    pub(crate) unsafe fn pop_local_frame_returning(self, result: ObjectPtr) -> ObjectPtr {
+        let obj = self.invoke_unchecked(|env| env.PopLocalFrame, |env, f| f(env, result.as_ptr()));
+        // PopLocalFrame only returns null when given null
+        ObjectPtr::new(obj).unwrap()
+    }
+
+    /// Ensures that at least `capacity` local references can be created in the current frame.
+    pub(crate) fn ensure_local_capacity(self, capacity: i32) -> crate::LocalResult<'jvm, ()> {
+        // SAFETY: EnsureLocalCapacity has no preconditions
+        let result = unsafe {
+            self.invoke_unchecked(|env| env.EnsureLocalCapacity, |env, f| f(env, capacity))
+        };
+        if result == jni_sys::JNI_OK {
+            Ok(())
+        } else {
+            // Like PushLocalFrame, a failed EnsureLocalCapacity leaves an OutOfMemoryError pending
+            self.check_exception()?;
+            Err(Error::JvmInternal(format!(
+                "EnsureLocalCapacity failed with code `{result}`"
+            )))
+        }
+    }
+
+    /// Returns whether `a` and `b` refer to the same Java object, where `None` stands for null.
+    pub(crate) fn is_same_object(self, a: Option<ObjectPtr>, b: Option<ObjectPtr>) -> bool {
+        let ptr = |obj: Option<ObjectPtr>| obj.map_or(ptr::null_mut(), ObjectPtr::as_ptr);
+        // SAFETY: IsSameObject accepts null and (possibly cleared) weak refs, and can't throw
+        let same = unsafe {
+            self.invoke_unchecked(|env| env.IsSameObject, |env, f| f(env, ptr(a), ptr(b)))
+        };
+        same == jni_sys::JNI_TRUE
+    }
+
     pub fn check_exception(self) -> crate::LocalResult<'jvm, ()> {
         // SAFETY: we don't hold on to the return env ptr
         let thrown = unsafe { self.invoke_unchecked(|env| env.ExceptionOccurred, |env, f| f(env)) };
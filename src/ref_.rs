@@ -1,8 +1,11 @@
 use std::{marker::PhantomData, ops::Deref, ptr::NonNull};
 
-use crate::jvm::JavaObjectExt;
+use crate::from_ref::FromRef;
+use crate::jvm::{JavaObjectExt, JavaView};
 use crate::thread;
-use crate::{cast::Upcast, raw::EnvPtr, semver_unstable::ObjectPtr, JavaObject};
+use crate::{
+    cast::Upcast, java, raw::EnvPtr, semver_unstable::ObjectPtr, AsJRef, JavaObject, Jvm, JvmOp,
+};
 
 /// An owned local reference to a non-null Java object of type `T`. The reference will be freed when
 /// dropped. Cannot be shared across threads or [`Jvm::with`] invocations.
@@ -32,6 +35,8 @@ impl<'jvm, T: JavaObject> Local<'jvm, T> {
     /// `from_raw()`), and will not dereferenced after the returned [`Local`] is dropped.
     #[doc(hidden)]
     pub unsafe fn from_raw(env: EnvPtr<'jvm>, obj: ObjectPtr) -> Self {
+        #[cfg(feature = "count-local-refs")]
+        LIVE_LOCALS.with(|live| live.set(live.get() + 1));
         Self {
             obj,
             env,
@@ -60,8 +65,19 @@ impl<'jvm, T: JavaObject> Local<'jvm, T> {
     pub unsafe fn into_raw(self) -> ObjectPtr {
         let p = self.as_raw();
         std::mem::forget(self);
+        #[cfg(feature = "count-local-refs")]
+        LIVE_LOCALS.with(|live| live.set(live.get() - 1));
         p
     }
+
+    /// Creates a weak reference to the object, which does not keep it from being collected.
+    pub fn downgrade(&self) -> JavaWeak<T> {
+        JavaWeak::new(self.env, self)
+    }
+
+    pub(crate) fn env(&self) -> EnvPtr<'jvm> {
+        self.env
+    }
 }
 
 impl<T: JavaObject> Drop for Local<'_, T> {
@@ -71,9 +87,23 @@ impl<T: JavaObject> Drop for Local<'_, T> {
             self.env
                 .invoke_unchecked(|jni| jni.DeleteLocalRef, |jni, f| f(jni, self.obj.as_ptr()));
         }
+        #[cfg(feature = "count-local-refs")]
+        LIVE_LOCALS.with(|live| live.set(live.get() - 1));
     }
 }
 
+#[cfg(feature = "count-local-refs")]
+thread_local! {
+    /// The number of [`Local`]s currently alive on this thread.
+    static LIVE_LOCALS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Returns the number of [`Local`]s currently alive on this thread.
+#[cfg(feature = "count-local-refs")]
+pub(crate) fn live_locals() -> usize {
+    LIVE_LOCALS.with(|live| live.get())
+}
+
 impl<T: JavaObject> Deref for Local<'_, T> {
     type Target = T;
 
@@ -117,36 +147,50 @@ impl<T: JavaObject> Java<T> {
             Self::from_raw(NonNull::new(new_ref).unwrap().into())
         }
     }
+
+    /// Creates a weak reference to the object, which does not keep it from being collected.
+    pub fn downgrade(&self) -> crate::Result<JavaWeak<T>> {
+        Jvm::with(|jvm| Ok(jvm.weak::<T>(self)))
+    }
 }
 
 impl<T: JavaObject> Drop for Java<T> {
     fn drop(&mut self) {
-        let jvm = crate::jvm::unwrap_global_jvm();
-
         // SAFETY: Global owns the global ref and it's no longer possible to dereference the object pointer.
-        let delete = |env: EnvPtr<'_>| unsafe {
+        with_global_env("delete global ref", |env| unsafe {
             env.invoke_unchecked(
                 |jni| jni.DeleteGlobalRef,
                 |jni, f| f(jni, self.obj.as_ptr()),
             )
-        };
-
-        match unsafe { jvm.env() } {
-            Ok(Some(env)) => delete(env),
-            Ok(None) => {
-                // SAFETY: jvm is a valid pointer since duchess will not deinitialize a JVM once created
-                match unsafe { thread::attach(jvm) } {
-                    Ok(mut attached) => delete(attached.env()),
-                    Err(err) => {
-                        tracing::warn!(?err, "unable to attach current thread to delete global ref")
-                    }
+        });
+    }
+}
+
+/// Calls `op` with the env of the current thread, attaching the thread to the JVM if necessary.
+/// Used to release global refs, which may be dropped from any thread. Failures are logged, since
+/// they can't be reported from a destructor.
+fn with_global_env(what: &str, op: impl FnOnce(EnvPtr<'_>)) {
+    let Ok(_lock) = crate::jvm::lock_jvm() else {
+        // The JVM released every reference when it was destroyed
+        return;
+    };
+    let jvm = crate::jvm::unwrap_global_jvm();
+
+    match unsafe { jvm.env() } {
+        Ok(Some(env)) => op(env),
+        Ok(None) => {
+            // SAFETY: jvm is a valid pointer since duchess will not deinitialize a JVM once created
+            match unsafe { thread::attach(jvm) } {
+                Ok(mut attached) => op(attached.env()),
+                Err(err) => {
+                    tracing::warn!(?err, "unable to attach current thread to {what}")
                 }
             }
-            Err(err) => tracing::warn!(
-                ?err,
-                "unable to get JNI interface for local thread to delete global ref"
-            ),
         }
+        Err(err) => tracing::warn!(
+            ?err,
+            "unable to get JNI interface for local thread to {what}"
+        ),
     }
 }
 
@@ -163,6 +207,117 @@ impl<T: JavaObject> Deref for Java<T> {
     }
 }
 
+/// A weak reference to a Java object of type `T`, which does not keep the object from being
+/// garbage collected. Like [`Java`], it can be shared across threads.
+///
+/// Use [`upgrade`](Self::upgrade) to access the object, for example to get a strong reference
+/// with `weak.upgrade().execute::<Option<Java<T>>>()`.
+///
+/// Unlike [`Java`] and [`Local`], `JavaWeak` does not implement [`AsJRef`] or [`TryJDeref`](crate::TryJDeref):
+/// the object may be collected at any time, so it can only be used through a reference
+/// obtained by upgrading it. Comparing weak references needs the JVM and can fail, so
+/// they are compared with [`is_same`](Self::is_same) rather than `PartialEq`, and do not
+/// implement `Hash`.
+#[derive_where::derive_where(Debug)]
+pub struct JavaWeak<T: JavaObject> {
+    obj: ObjectPtr,
+    _marker: PhantomData<T>,
+}
+
+impl<T: JavaObject> JavaWeak<T> {
+    /// Creates a new weak global reference to `obj` via a `NewWeakGlobalRef` JNI call.
+    pub(crate) fn new(env: EnvPtr<'_>, obj: &T) -> Self {
+        // SAFETY: The JavaObject trait contract ensures that &T points to a Java object that is an instance of T.
+        unsafe {
+            let new_ref =
+                env.invoke_unchecked(|e| e.NewWeakGlobalRef, |e, f| f(e, obj.as_raw().as_ptr()));
+            Self {
+                obj: NonNull::new(new_ref).unwrap().into(),
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    /// Returns a [`JvmOp`] producing a reference to the object, or null if it has been collected.
+    /// Java methods can be invoked on the result directly, failing with
+    /// [`Error::NullDeref`](crate::Error::NullDeref) if the object is gone.
+    pub fn upgrade(&self) -> Upgrade<'_, T> {
+        Upgrade { weak: self }
+    }
+
+    /// Returns a new local reference to the object, or `None` if it has been collected.
+    fn new_local<'jvm>(&self, env: EnvPtr<'jvm>) -> Option<Local<'jvm, T>> {
+        // SAFETY: NewLocalRef returns null if the weak ref was cleared, and otherwise a new local ref
+        // to the object, which is an instance of T
+        unsafe {
+            let new_ref = env.invoke_unchecked(|e| e.NewLocalRef, |e, f| f(e, self.obj.as_ptr()));
+            ObjectPtr::new(new_ref).map(|obj| Local::from_raw(env, obj))
+        }
+    }
+
+    /// Returns whether the object has been garbage collected.
+    pub fn is_collected(&self) -> crate::Result<bool> {
+        Jvm::with(|jvm| Ok(jvm.env().is_same_object(Some(self.obj), None)))
+    }
+
+    /// Returns whether both weak references refer to the same object, as determined by the JNI
+    /// `IsSameObject` function. All weak references to collected objects are the same.
+    pub fn is_same(&self, other: &Self) -> crate::Result<bool> {
+        Jvm::with(|jvm| Ok(jvm.env().is_same_object(Some(self.obj), Some(other.obj))))
+    }
+
+    /// Returns whether this weak reference refers to `object`. If the object has been
+    /// collected, this is only true if `object` is null.
+    pub fn refers_to<U>(&self, object: &U) -> crate::Result<bool>
+    where
+        U: AsJRef<T>,
+    {
+        let object = object.as_jref().ok().map(|object| object.as_raw());
+        Jvm::with(|jvm| Ok(jvm.env().is_same_object(Some(self.obj), object)))
+    }
+}
+
+impl<T: JavaObject> Drop for JavaWeak<T> {
+    fn drop(&mut self) {
+        // SAFETY: JavaWeak owns the weak global ref and it's no longer possible to use the object pointer.
+        with_global_env("delete weak global ref", |env| unsafe {
+            env.invoke_unchecked(
+                |jni| jni.DeleteWeakGlobalRef,
+                |jni, f| f(jni, self.obj.as_ptr()),
+            )
+        });
+    }
+}
+
+// SAFETY: Weak global refs, like global refs, are valid on any thread
+unsafe impl<T: JavaObject> Send for JavaWeak<T> {}
+unsafe impl<T: JavaObject> Sync for JavaWeak<T> {}
+
+/// Upgrades a [`JavaWeak`] to a (possibly null) reference to its object, see [`JavaWeak::upgrade`].
+#[derive_where::derive_where(Copy, Clone)]
+pub struct Upgrade<'a, T: JavaObject> {
+    weak: &'a JavaWeak<T>,
+}
+
+impl<T: JavaObject> JvmOp for Upgrade<'_, T> {
+    type Output<'jvm> = Option<Local<'jvm, T>>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> crate::LocalResult<'jvm, Self::Output<'jvm>> {
+        Ok(self.weak.new_local(jvm.env()))
+    }
+}
+
+impl<T> Deref for Upgrade<'_, T>
+where
+    T: Upcast<java::lang::Object> + Upcast<T>,
+{
+    type Target = <T as JavaView>::OfOp<Self>;
+
+    fn deref(&self) -> &Self::Target {
+        <Self::Target as FromRef<_>>::from_ref(self)
+    }
+}
+
 impl<'a, R, S> AsRef<S> for Local<'a, R>
 where
     R: Upcast<S>,
@@ -180,9 +335,10 @@ impl<'a, R: JavaObject> Local<'a, R> {
         R: Upcast<S>,
         S: JavaObject + 'a,
     {
-        // SAFETY: From the Upcast trait contract, we know R is also an instance of S
-        let upcast = unsafe { Local::<S>::from_raw(self.env, self.obj) };
-        upcast
+        let env = self.env;
+        // SAFETY: From the Upcast trait contract, we know R is also an instance of S,
+        // and `into_raw` transfers ownership of the local ref to the new `Local`
+        unsafe { Local::<S>::from_raw(env, self.into_raw()) }
     }
 }
 
@@ -203,8 +359,10 @@ impl<R: JavaObject> Java<R> {
         R: Upcast<S>,
         S: JavaObject + 'static,
     {
+        let obj = self.obj;
+        // Ownership of the global ref is transferred to the new `Java`
+        std::mem::forget(self);
         // SAFETY: From the Upcast trait contract, we know R is also an instance of S
-        let upcast = unsafe { Java::<S>::from_raw(self.obj) };
-        upcast
+        unsafe { Java::<S>::from_raw(obj) }
     }
 }
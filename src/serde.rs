@@ -0,0 +1,825 @@
+//! Conversions between any [`Serialize`]/[`Deserialize`](::serde::Deserialize) Rust type and
+//! a tree of "JSON-like" Java objects.
+//!
+//! The Java side of the conversion uses the following classes:
+//!
+//! | Rust (serde data model)               | Java                                              |
+//! | ------------------------------------- | ------------------------------------------------- |
+//! | `bool`                                | `java.lang.Boolean`                               |
+//! | `i8`, `i16`, `i32`, `i64`             | `java.lang.Byte`, `Short`, `Integer`, `Long`      |
+//! | `u8`, `u16`, `u32`, `u64`             | `java.lang.Short`, `Integer`, `Long`, `Long`      |
+//! | `f32`, `f64`                          | `java.lang.Float`, `java.lang.Double`             |
+//! | `char`, strings, unit variants        | `java.lang.String`                                |
+//! | `None`, `()`, unit structs            | `null`                                            |
+//! | sequences, tuples, bytes              | `java.util.ArrayList`                             |
+//! | maps, structs                         | `java.util.HashMap`                               |
+//! | newtype, tuple and struct variants    | `java.util.HashMap` with a single entry `{variant: value}` |
+//!
+//! When converting back to Rust, any `java.util.Map`, `java.util.List`, `java.lang.Number`,
+//! `java.lang.String`, `java.lang.Boolean` or `java.lang.Character` is accepted.
+//!
+//! The simplest way to use these conversions is through the [`Serde`] wrapper:
+//!
+//! ```rust,ignore
+//! use duchess::{java, prelude::*, serde::Serde};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Settings {
+//!     name: String,
+//!     retries: Vec<u32>,
+//! }
+//!
+//! let java: Java<java::lang::Object> = Serde(&settings).to_java().assert_not_null().execute()?;
+//! let Serde(settings): Serde<Settings> = Serde::from_java(&java).execute()?;
+//! ```
+
+use std::{fmt::Display, marker::PhantomData};
+
+use ::serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::ser::{self, Serialize};
+
+use once_cell::sync::OnceCell;
+
+use crate::{
+    find::find_constructor,
+    into_rust::IntoRust,
+    java,
+    jvm::{JavaObjectExt, JvmRefOp},
+    raw::{IntoJniValue, MethodPtr},
+    to_java::ToJavaImpl,
+    AsJRef, Error, Java, JavaObject, Jvm, JvmOp, Local, LocalResult,
+};
+
+type Object = java::lang::Object;
+
+/// Wraps a Rust value so that it is converted to and from Java with [`serde`](::serde).
+///
+/// `Serde<T>` implements [`ToJava`](crate::ToJava) (producing a `java.lang.Object`) when `T: Serialize`,
+/// and [`Serde::from_java`] deserializes a `java.lang.Object` when `T: DeserializeOwned`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Serde<T>(pub T);
+
+impl<T> ToJavaImpl<Object> for Serde<T>
+where
+    T: Serialize,
+{
+    fn to_java_impl<'jvm>(
+        rust: &Self,
+        jvm: &mut Jvm<'jvm>,
+    ) -> LocalResult<'jvm, Option<Local<'jvm, Object>>> {
+        to_java(jvm, &rust.0)
+    }
+}
+
+impl<T> Serde<T>
+where
+    T: DeserializeOwned,
+{
+    /// Returns an operation that deserializes the (possibly null) object produced by `op`.
+    pub fn from_java<J>(op: J) -> FromJavaOp<J, T>
+    where
+        J: JvmRefOp<Object>,
+    {
+        FromJavaOp {
+            op,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> IntoRust<Serde<T>> for Serde<T> {
+    fn into_rust<'jvm>(self, _jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Serde<T>> {
+        Ok(self)
+    }
+}
+
+/// Operation returned by [`Serde::from_java`].
+#[derive_where::derive_where(Clone)]
+#[derive_where(Copy; J: Copy)]
+pub struct FromJavaOp<J, T>
+where
+    J: JvmRefOp<Object>,
+{
+    op: J,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<J, T> JvmOp for FromJavaOp<J, T>
+where
+    J: JvmRefOp<Object>,
+    T: DeserializeOwned,
+{
+    type Output<'jvm> = Serde<T>;
+
+    fn do_jni<'jvm>(self, jvm: &mut Jvm<'jvm>) -> LocalResult<'jvm, Serde<T>> {
+        let object = self.op.into_as_jref(jvm)?;
+        let object = object.as_jref().ok().map(|object| jvm.local(object));
+        Ok(Serde(from_java(jvm, object)?))
+    }
+}
+
+/// Serializes `value` into a tree of Java objects (see the [module documentation](self)).
+/// Returns `None` for values that are represented by `null`.
+pub fn to_java<'jvm, T>(
+    jvm: &mut Jvm<'jvm>,
+    value: &T,
+) -> LocalResult<'jvm, Option<Local<'jvm, Object>>>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer::new(jvm))
+}
+
+/// Deserializes a tree of Java objects (see the [module documentation](self)) into a `T`.
+/// `None` stands for `null`.
+pub fn from_java<'jvm, T>(
+    jvm: &mut Jvm<'jvm>,
+    object: Option<Local<'jvm, Object>>,
+) -> LocalResult<'jvm, T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(jvm, object))
+}
+
+macro_rules! serde_error {
+    ($($ty:ty),*) => {
+        $(
+            impl<'jvm> ser::Error for $ty {
+                fn custom<M: Display>(msg: M) -> Self {
+                    Error::Serde(msg.to_string())
+                }
+            }
+
+            impl<'jvm> de::Error for $ty {
+                fn custom<M: Display>(msg: M) -> Self {
+                    Error::Serde(msg.to_string())
+                }
+            }
+        )*
+    };
+}
+
+serde_error!(
+    Error<Local<'jvm, java::lang::Throwable>>,
+    Error<Java<java::lang::Throwable>>
+);
+
+/// A [`serde::Serializer`](::serde::Serializer) producing Java objects.
+pub struct Serializer<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+}
+
+impl<'a, 'jvm> Serializer<'a, 'jvm> {
+    pub fn new(jvm: &'a mut Jvm<'jvm>) -> Self {
+        Serializer { jvm }
+    }
+
+    fn string(self, value: &str) -> LocalResult<'jvm, Option<Local<'jvm, Object>>> {
+        let string = value.do_jni(self.jvm)?;
+        Ok(Some(string.upcast()))
+    }
+
+    /// Wraps `value` into a map with a single entry, keyed by the name of the enum variant.
+    fn variant(
+        self,
+        variant: &'static str,
+        value: Option<Local<'jvm, Object>>,
+    ) -> LocalResult<'jvm, Option<Local<'jvm, Object>>> {
+        let map: Local<'jvm, java::util::HashMap<Object, Object>> =
+            java::util::HashMap::new().do_jni(self.jvm)?;
+        let key = variant.do_jni(self.jvm)?;
+        map.put(&key, &value).do_jni(self.jvm)?;
+        Ok(Some(map.upcast()))
+    }
+}
+
+macro_rules! serialize_boxed {
+    ($($method:ident($rust:ty) => $java:ident($java_ty:ty),)*) => {
+        $(
+            fn $method(self, value: $rust) -> LocalResult<'jvm, Self::Ok> {
+                let boxed = java::lang::$java::value_of(value as $java_ty)
+                    .assert_not_null()
+                    .do_jni(self.jvm)?;
+                Ok(Some(boxed.upcast()))
+            }
+        )*
+    };
+}
+
+impl<'a, 'jvm> ser::Serializer for Serializer<'a, 'jvm> {
+    type Ok = Option<Local<'jvm, Object>>;
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+    type SerializeSeq = SerializeList<'a, 'jvm>;
+    type SerializeTuple = SerializeList<'a, 'jvm>;
+    type SerializeTupleStruct = SerializeList<'a, 'jvm>;
+    type SerializeTupleVariant = SerializeList<'a, 'jvm>;
+    type SerializeMap = SerializeMap<'a, 'jvm>;
+    type SerializeStruct = SerializeMap<'a, 'jvm>;
+    type SerializeStructVariant = SerializeMap<'a, 'jvm>;
+
+    serialize_boxed! {
+        serialize_bool(bool) => Boolean(bool),
+        serialize_i8(i8) => Byte(i8),
+        serialize_i16(i16) => Short(i16),
+        serialize_i32(i32) => Integer(i32),
+        serialize_u8(u8) => Short(i16),
+        serialize_u16(u16) => Integer(i32),
+        serialize_f32(f32) => Float(f32),
+        serialize_f64(f64) => Double(f64),
+    }
+
+    fn serialize_i64(self, value: i64) -> LocalResult<'jvm, Self::Ok> {
+        let boxed = new_long(self.jvm, value)?;
+        Ok(Some(boxed.upcast()))
+    }
+
+    fn serialize_u32(self, value: u32) -> LocalResult<'jvm, Self::Ok> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> LocalResult<'jvm, Self::Ok> {
+        match i64::try_from(value) {
+            Ok(value) => self.serialize_i64(value),
+            Err(_) => Err(Error::Serde(format!(
+                "`{value}` does not fit in a `java.lang.Long`"
+            ))),
+        }
+    }
+
+    fn serialize_char(self, value: char) -> LocalResult<'jvm, Self::Ok> {
+        self.string(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> LocalResult<'jvm, Self::Ok> {
+        self.string(value)
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> LocalResult<'jvm, Self::Ok> {
+        let mut list = ser::Serializer::serialize_seq(self, Some(value.len()))?;
+        for byte in value {
+            ser::SerializeSeq::serialize_element(&mut list, byte)?;
+        }
+        ser::SerializeSeq::end(list)
+    }
+
+    fn serialize_none(self) -> LocalResult<'jvm, Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> LocalResult<'jvm, Self::Ok>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> LocalResult<'jvm, Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> LocalResult<'jvm, Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> LocalResult<'jvm, Self::Ok> {
+        self.string(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> LocalResult<'jvm, Self::Ok>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> LocalResult<'jvm, Self::Ok>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(Serializer::new(self.jvm))?;
+        self.variant(variant, value)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> LocalResult<'jvm, Self::SerializeSeq> {
+        let list = java::util::ArrayList::new().do_jni(self.jvm)?;
+        Ok(SerializeList {
+            jvm: self.jvm,
+            list,
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> LocalResult<'jvm, Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> LocalResult<'jvm, Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> LocalResult<'jvm, Self::SerializeTupleVariant> {
+        let mut list = self.serialize_seq(Some(len))?;
+        list.variant = Some(variant);
+        Ok(list)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> LocalResult<'jvm, Self::SerializeMap> {
+        let map = java::util::HashMap::new().do_jni(self.jvm)?;
+        Ok(SerializeMap {
+            jvm: self.jvm,
+            map,
+            key: None,
+            variant: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> LocalResult<'jvm, Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> LocalResult<'jvm, Self::SerializeStructVariant> {
+        let mut map = self.serialize_map(Some(len))?;
+        map.variant = Some(variant);
+        Ok(map)
+    }
+}
+
+/// Serializes sequences, tuples and tuple variants into a `java.util.ArrayList`.
+pub struct SerializeList<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+    list: Local<'jvm, java::util::ArrayList<Object>>,
+    variant: Option<&'static str>,
+}
+
+impl<'jvm> SerializeList<'_, 'jvm> {
+    fn push<T>(&mut self, value: &T) -> LocalResult<'jvm, ()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let element = value.serialize(Serializer::new(self.jvm))?;
+        self.list.add(&element).do_jni(self.jvm)?;
+        Ok(())
+    }
+
+    fn finish(self) -> LocalResult<'jvm, Option<Local<'jvm, Object>>> {
+        let list = Some(self.list.upcast());
+        match self.variant {
+            Some(variant) => Serializer::new(self.jvm).variant(variant, list),
+            None => Ok(list),
+        }
+    }
+}
+
+macro_rules! serialize_list {
+    ($($trait_:ident::$method:ident,)*) => {
+        $(
+            impl<'jvm> ser::$trait_ for SerializeList<'_, 'jvm> {
+                type Ok = Option<Local<'jvm, Object>>;
+                type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+                fn $method<T>(&mut self, value: &T) -> LocalResult<'jvm, ()>
+                where
+                    T: Serialize + ?Sized,
+                {
+                    self.push(value)
+                }
+
+                fn end(self) -> LocalResult<'jvm, Self::Ok> {
+                    self.finish()
+                }
+            }
+        )*
+    };
+}
+
+serialize_list! {
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+}
+
+/// Serializes maps, structs and struct variants into a `java.util.HashMap`.
+pub struct SerializeMap<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+    map: Local<'jvm, java::util::HashMap<Object, Object>>,
+    key: Option<Option<Local<'jvm, Object>>>,
+    variant: Option<&'static str>,
+}
+
+impl<'jvm> SerializeMap<'_, 'jvm> {
+    fn put<T>(&mut self, key: Option<Local<'jvm, Object>>, value: &T) -> LocalResult<'jvm, ()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(Serializer::new(self.jvm))?;
+        self.map.put(&key, &value).do_jni(self.jvm)?;
+        Ok(())
+    }
+
+    fn finish(self) -> LocalResult<'jvm, Option<Local<'jvm, Object>>> {
+        let map = Some(self.map.upcast());
+        match self.variant {
+            Some(variant) => Serializer::new(self.jvm).variant(variant, map),
+            None => Ok(map),
+        }
+    }
+}
+
+impl<'jvm> ser::SerializeMap for SerializeMap<'_, 'jvm> {
+    type Ok = Option<Local<'jvm, Object>>;
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+    fn serialize_key<T>(&mut self, key: &T) -> LocalResult<'jvm, ()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(Serializer::new(self.jvm))?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> LocalResult<'jvm, ()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self.key.take().ok_or_else(|| {
+            Error::Serde("`serialize_value` called before `serialize_key`".to_string())
+        })?;
+        self.put(key, value)
+    }
+
+    fn end(self) -> LocalResult<'jvm, Self::Ok> {
+        self.finish()
+    }
+}
+
+macro_rules! serialize_struct {
+    ($($trait_:ident,)*) => {
+        $(
+            impl<'jvm> ser::$trait_ for SerializeMap<'_, 'jvm> {
+                type Ok = Option<Local<'jvm, Object>>;
+                type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+                fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> LocalResult<'jvm, ()>
+                where
+                    T: Serialize + ?Sized,
+                {
+                    let key = key.do_jni(self.jvm)?;
+                    self.put(Some(key.upcast()), value)
+                }
+
+                fn end(self) -> LocalResult<'jvm, Self::Ok> {
+                    self.finish()
+                }
+            }
+        )*
+    };
+}
+
+serialize_struct! {
+    SerializeStruct,
+    SerializeStructVariant,
+}
+
+/// A [`serde::Deserializer`](::serde::Deserializer) reading a graph of Java objects.
+pub struct Deserializer<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+    object: Option<Local<'jvm, Object>>,
+}
+
+impl<'a, 'jvm> Deserializer<'a, 'jvm> {
+    /// Creates a deserializer for `object`, where `None` stands for `null`.
+    pub fn new(jvm: &'a mut Jvm<'jvm>, object: Option<Local<'jvm, Object>>) -> Self {
+        Deserializer { jvm, object }
+    }
+}
+
+/// Boxes `value` with the `Long(long)` constructor: `java.lang.Long` mirrors
+/// `valueOf(String)`, and overloads of `valueOf` cannot be mirrored alongside it.
+fn new_long<'jvm>(
+    jvm: &mut Jvm<'jvm>,
+    value: i64,
+) -> LocalResult<'jvm, Local<'jvm, java::lang::Long>> {
+    let class = <java::lang::Long as JavaObject>::class(jvm)?;
+
+    static CONSTRUCTOR: OnceCell<MethodPtr> = OnceCell::new();
+    let constructor = CONSTRUCTOR.get_or_try_init(|| find_constructor(jvm, &class, c"(J)V"))?;
+
+    let obj: Option<Local<'jvm, java::lang::Long>> = unsafe {
+        // SAFETY: the constructor was looked up on `class` with a single `long` argument
+        jvm.env().invoke(
+            |env| env.NewObjectA,
+            |env, f| {
+                f(
+                    env,
+                    class.as_raw().as_ptr(),
+                    constructor.as_ptr(),
+                    [value.into_jni_value()].as_ptr(),
+                )
+            },
+        )
+    }?;
+    obj.ok_or_else(|| Error::JvmInternal("failed to create new `java.lang.Long`".to_string()))
+}
+
+/// Returns `object` as a `J` if it is an instance of `J`.
+fn downcast<'jvm, J>(
+    jvm: &mut Jvm<'jvm>,
+    object: &Local<'jvm, Object>,
+) -> LocalResult<'jvm, Option<Local<'jvm, J>>>
+where
+    J: crate::semver_unstable::Upcast<Object>,
+{
+    Ok(object.try_downcast::<J>().do_jni(jvm)?.ok())
+}
+
+impl<'de, 'jvm> de::Deserializer<'de> for Deserializer<'_, 'jvm> {
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+    fn deserialize_any<V>(self, visitor: V) -> LocalResult<'jvm, V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let jvm = self.jvm;
+        let Some(object) = self.object else {
+            return visitor.visit_unit();
+        };
+
+        if let Some(string) = downcast::<java::lang::String>(jvm, &object)? {
+            let string: String = string.into_rust(jvm)?;
+            return visitor.visit_string(string);
+        }
+        if let Some(boolean) = downcast::<java::lang::Boolean>(jvm, &object)? {
+            return visitor.visit_bool(boolean.boolean_value().do_jni(jvm)?);
+        }
+        if let Some(number) = downcast::<java::lang::Byte>(jvm, &object)? {
+            return visitor.visit_i8(number.byte_value().do_jni(jvm)?);
+        }
+        if let Some(number) = downcast::<java::lang::Short>(jvm, &object)? {
+            return visitor.visit_i16(number.short_value().do_jni(jvm)?);
+        }
+        if let Some(number) = downcast::<java::lang::Integer>(jvm, &object)? {
+            return visitor.visit_i32(number.int_value().do_jni(jvm)?);
+        }
+        if let Some(number) = downcast::<java::lang::Long>(jvm, &object)? {
+            return visitor.visit_i64(number.long_value().do_jni(jvm)?);
+        }
+        if let Some(number) = downcast::<java::lang::Float>(jvm, &object)? {
+            return visitor.visit_f32(number.float_value().do_jni(jvm)?);
+        }
+        // Other numbers (`Double`, `BigDecimal`, ...) are read as a `double`
+        if let Some(number) = downcast::<java::lang::Number>(jvm, &object)? {
+            return visitor.visit_f64(number.double_value().do_jni(jvm)?);
+        }
+        if let Some(character) = downcast::<java::lang::Character>(jvm, &object)? {
+            let unit = character.char_value().do_jni(jvm)?;
+            return match char::from_u32(unit.into()) {
+                Some(c) => visitor.visit_char(c),
+                None => Err(Error::Serde(format!(
+                    "`java.lang.Character` `{unit:#x}` is not a valid `char`"
+                ))),
+            };
+        }
+        if let Some(list) = downcast::<java::util::List<Object>>(jvm, &object)? {
+            let len = list.size().do_jni(jvm)?;
+            return visitor.visit_seq(ListAccess {
+                jvm,
+                list,
+                index: 0,
+                len,
+            });
+        }
+        if let Some(map) = downcast::<java::util::Map<Object, Object>>(jvm, &object)? {
+            let entries = map.entry_set().assert_not_null().do_jni(jvm)?;
+            let entries = entries.iterator().assert_not_null().do_jni(jvm)?;
+            return visitor.visit_map(MapAccess {
+                jvm,
+                entries,
+                value: None,
+            });
+        }
+
+        Err(Error::Serde(
+            "expected a `java.util.Map`, `java.util.List`, `java.lang.Number`, `java.lang.String`, `java.lang.Boolean`, `java.lang.Character` or `null`".to_string(),
+        ))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> LocalResult<'jvm, V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.object {
+            None => visitor.visit_none(),
+            Some(_) => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> LocalResult<'jvm, V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> LocalResult<'jvm, V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let jvm = self.jvm;
+        let Some(object) = self.object else {
+            return Err(Error::Serde(
+                "expected an enum variant, found `null`".to_string(),
+            ));
+        };
+
+        // Unit variants are represented by their name...
+        if let Some(string) = downcast::<java::lang::String>(jvm, &object)? {
+            let variant: String = string.into_rust(jvm)?;
+            return visitor.visit_enum(variant.into_deserializer());
+        }
+
+        // ...and other variants by a map with a single entry `{variant: value}`
+        if let Some(map) = downcast::<java::util::Map<Object, Object>>(jvm, &object)? {
+            if map.size().do_jni(jvm)? == 1 {
+                let entries = map.entry_set().assert_not_null().do_jni(jvm)?;
+                let entry = entries.iterator().next().assert_not_null().do_jni(jvm)?;
+                let variant = entry.get_key().do_jni(jvm)?;
+                let value = entry.get_value().do_jni(jvm)?;
+                return visitor.visit_enum(EnumAccess {
+                    jvm,
+                    variant,
+                    value,
+                });
+            }
+        }
+
+        Err(Error::Serde(
+            "expected an enum variant, i.e. a `java.lang.String` or a `java.util.Map` with a single entry".to_string(),
+        ))
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct ListAccess<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+    list: Local<'jvm, java::util::List<Object>>,
+    index: i32,
+    len: i32,
+}
+
+impl<'de, 'jvm> de::SeqAccess<'de> for ListAccess<'_, 'jvm> {
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> LocalResult<'jvm, Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        let element = self.list.get(self.index).do_jni(self.jvm)?;
+        self.index += 1;
+        seed.deserialize(Deserializer::new(self.jvm, element))
+            .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        usize::try_from(self.len - self.index).ok()
+    }
+}
+
+struct MapAccess<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+    entries: Local<'jvm, java::util::Iterator<java::util::map::Entry<Object, Object>>>,
+    value: Option<Option<Local<'jvm, Object>>>,
+}
+
+impl<'de, 'jvm> de::MapAccess<'de> for MapAccess<'_, 'jvm> {
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> LocalResult<'jvm, Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if !self.entries.has_next().do_jni(self.jvm)? {
+            return Ok(None);
+        }
+        let entry = self.entries.next().assert_not_null().do_jni(self.jvm)?;
+        let key = entry.get_key().do_jni(self.jvm)?;
+        self.value = Some(entry.get_value().do_jni(self.jvm)?);
+        seed.deserialize(Deserializer::new(self.jvm, key)).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> LocalResult<'jvm, V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self.value.take().ok_or_else(|| {
+            Error::Serde("`next_value_seed` called before `next_key_seed`".to_string())
+        })?;
+        seed.deserialize(Deserializer::new(self.jvm, value))
+    }
+}
+
+struct EnumAccess<'a, 'jvm> {
+    jvm: &'a mut Jvm<'jvm>,
+    variant: Option<Local<'jvm, Object>>,
+    value: Option<Local<'jvm, Object>>,
+}
+
+impl<'de, 'a, 'jvm> de::EnumAccess<'de> for EnumAccess<'a, 'jvm> {
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+    type Variant = Deserializer<'a, 'jvm>;
+
+    fn variant_seed<V>(self, seed: V) -> LocalResult<'jvm, (V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(Deserializer::new(self.jvm, self.variant))?;
+        Ok((variant, Deserializer::new(self.jvm, self.value)))
+    }
+}
+
+impl<'de, 'jvm> de::VariantAccess<'de> for Deserializer<'_, 'jvm> {
+    type Error = Error<Local<'jvm, java::lang::Throwable>>;
+
+    fn unit_variant(self) -> LocalResult<'jvm, ()> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> LocalResult<'jvm, T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> LocalResult<'jvm, V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> LocalResult<'jvm, V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,174 @@
+//! Structured snapshots of Java stack traces, see [`crate::Error::stack_trace`].
+
+use std::fmt::{self, Display};
+
+use crate::{
+    array::JavaArrayExt,
+    java::{self, lang::Throwable},
+    jvm::JavaObjectExt,
+    prelude::*,
+    Jvm, Local, LocalResult,
+};
+
+/// How many causes (and suppressed exceptions) deep a stack trace is captured,
+/// which also guards against cycles in the cause chain.
+const MAX_DEPTH: usize = 16;
+
+/// A snapshot of a Java exception: its class, message and stack frames,
+/// along with the exceptions that caused it and those it suppressed.
+///
+/// `{}` formats it like Java's `Throwable.toString()`, and `{:#}` like `printStackTrace()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JavaStackTrace {
+    /// Fully qualified name of the exception class, e.g. `java.lang.RuntimeException`
+    pub class_name: String,
+    /// Result of `getMessage()`
+    pub message: Option<String>,
+    /// Stack frames, innermost call first
+    pub frames: Vec<JavaStackFrame>,
+    /// Result of `getCause()`
+    pub cause: Option<Box<JavaStackTrace>>,
+    /// Result of `getSuppressed()`
+    pub suppressed: Vec<JavaStackTrace>,
+}
+
+/// One frame of a [`JavaStackTrace`], read from a `java.lang.StackTraceElement`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JavaStackFrame {
+    pub class_name: String,
+    pub method_name: String,
+    /// `None` if the source file is unknown
+    pub file_name: Option<String>,
+    /// `None` if the line is unknown
+    pub line_number: Option<u32>,
+    pub is_native: bool,
+}
+
+impl JavaStackTrace {
+    /// Reads the stack trace of `exception`, its cause chain and suppressed exceptions.
+    pub fn capture<'jvm>(jvm: &mut Jvm<'jvm>, exception: &Throwable) -> LocalResult<'jvm, Self> {
+        capture(jvm, exception, MAX_DEPTH)
+    }
+
+    /// Iterates over this exception and its causes, outermost first.
+    pub fn chain(&self) -> impl Iterator<Item = &JavaStackTrace> {
+        std::iter::successors(Some(self), |trace| trace.cause.as_deref())
+    }
+
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>, prefix: &str, indent: &str) -> fmt::Result {
+        write!(f, "{indent}{prefix}{self}")?;
+        for frame in &self.frames {
+            write!(f, "\n{indent}\tat {frame}")?;
+        }
+        for suppressed in &self.suppressed {
+            f.write_str("\n")?;
+            suppressed.fmt_nested(f, "Suppressed: ", &format!("{indent}\t"))?;
+        }
+        if let Some(cause) = &self.cause {
+            f.write_str("\n")?;
+            cause.fmt_nested(f, "Caused by: ", indent)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for JavaStackTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.fmt_nested(f, "", "");
+        }
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.class_name, message),
+            None => write!(f, "{}", self.class_name),
+        }
+    }
+}
+
+impl std::error::Error for JavaStackTrace {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause.as_deref().map(|cause| cause as _)
+    }
+}
+
+impl Display for JavaStackFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.class_name, self.method_name)?;
+        match (&self.file_name, self.line_number) {
+            _ if self.is_native => write!(f, "(Native Method)"),
+            (Some(file), Some(line)) => write!(f, "({file}:{line})"),
+            (Some(file), None) => write!(f, "({file})"),
+            (None, _) => write!(f, "(Unknown Source)"),
+        }
+    }
+}
+
+fn capture<'jvm>(
+    jvm: &mut Jvm<'jvm>,
+    exception: &Throwable,
+    depth: usize,
+) -> LocalResult<'jvm, JavaStackTrace> {
+    jvm.with_local_frame(16, |jvm| {
+        let class_name = class_name(jvm, exception)?;
+        let message: Option<String> = exception.get_message().execute_with(jvm)?;
+
+        let elements = exception.get_stack_trace().assert_not_null().do_jni(jvm)?;
+        let mut frames = vec![];
+        for index in 0..elements.length().do_jni(jvm)? as usize {
+            let frame = jvm.with_local_frame(8, |jvm| {
+                let Some(element) = elements.get(index).do_jni(jvm)? else {
+                    return Ok(None);
+                };
+                Ok(Some(JavaStackFrame {
+                    class_name: element
+                        .get_class_name()
+                        .assert_not_null()
+                        .execute_with(jvm)?,
+                    method_name: element
+                        .get_method_name()
+                        .assert_not_null()
+                        .execute_with(jvm)?,
+                    file_name: element.get_file_name().execute_with(jvm)?,
+                    line_number: u32::try_from(element.get_line_number().do_jni(jvm)?).ok(),
+                    is_native: element.is_native_method().do_jni(jvm)?,
+                }))
+            })?;
+            frames.extend(frame);
+        }
+
+        let mut cause = None;
+        let mut suppressed = vec![];
+        if let Some(depth) = depth.checked_sub(1) {
+            if let Some(inner) = exception.get_cause().do_jni(jvm)? {
+                cause = Some(Box::new(capture(jvm, &inner, depth)?));
+            }
+            let others = exception.get_suppressed().assert_not_null().do_jni(jvm)?;
+            for index in 0..others.length().do_jni(jvm)? as usize {
+                if let Some(other) = others.get(index).do_jni(jvm)? {
+                    suppressed.push(capture(jvm, &other, depth)?);
+                }
+            }
+        }
+
+        Ok(JavaStackTrace {
+            class_name,
+            message,
+            frames,
+            cause,
+            suppressed,
+        })
+    })
+}
+
+/// Returns the name of the runtime class of `exception`.
+fn class_name<'jvm>(jvm: &mut Jvm<'jvm>, exception: &Throwable) -> LocalResult<'jvm, String> {
+    let exception = exception.as_raw();
+    let class: Option<Local<java::lang::Class>> = unsafe {
+        // SAFETY: exception is a valid, non-null object pointer
+        jvm.env().invoke(
+            |env| env.GetObjectClass,
+            |env, f| f(env, exception.as_ptr()),
+        )
+    }?;
+    let class = class.ok_or(crate::Error::NullDeref)?;
+    class.get_name().assert_not_null().execute_with(jvm)
+}
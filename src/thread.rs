@@ -1,48 +1,93 @@
-use std::cell::Cell;
+use std::cell::RefCell;
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::{
     raw::{EnvPtr, JvmPtr},
     Error, Result,
 };
 
-// XX: The current thread-local state will prevent duchess => java => duchess call stacks. We may want to relax this in
-// the future!
+/// Number of local references that each duchess frame reserves up front.
+/// The JVM grows the frame on demand, so this is only a hint.
+const LOCAL_FRAME_CAPACITY: i32 = 16;
+
+// Each thread tracks a stack of the frames that are currently active on it. This permits
+// duchess => java => duchess call stacks of any depth: every frame gets its own scope for
+// local references, and the thread is only detached once the outermost frame that attached
+// it has been popped.
 thread_local! {
-    static STATE: Cell<State> = Cell::new(State::Detached);
+    static STATE: RefCell<ThreadState> = RefCell::new(ThreadState::default());
+}
+
+/// Number of threads that are permanently attached (see [`attach_permanently`]),
+/// which keep [`crate::Jvm::shutdown`] from destroying the JVM.
+static PERMANENT_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Default)]
+struct ThreadState {
+    /// Set when the JVM has been permanently attached to the current thread.
+    permanent: Option<EnvPtr<'static>>,
+
+    /// Frames that are currently live on this thread, innermost last.
+    frames: Vec<Frame>,
+
+    /// Number of critical regions (pinned primitive arrays or borrowed direct buffers)
+    /// currently held on this thread. No JNI call may be made while this is non-zero.
+    /// Only [`attach`] checks it: the calls that bypass it are listed on
+    /// `JavaArrayCriticalExt`, which `JavaByteBufferExt` refers to; keep that list in sync.
+    critical: usize,
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub enum State {
-    /// The JVM is attached to the current thread, but we're already inside a duchess frame.
-    InUse,
-    /// The JVM is permanently attached to the current thread, but we're not inside a duchess frame.
-    AttachedPermanently(EnvPtr<'static>),
-    /// Duchess thinks the JVM is detached, though JNI calls through other means could change this.
-    Detached,
-}
-
-fn attached_or(jvm: JvmPtr, f: impl FnOnce() -> Result<AttachGuard>) -> Result<AttachGuard> {
-    STATE.with(|state| match state.replace(State::InUse) {
-        State::AttachedPermanently(env) => Ok(AttachGuard {
-            jvm,
-            env,
-            permanent: true,
-        }),
-        State::InUse => Err(Error::NestedUsage),
-        State::Detached => {
-            let result = f();
-            if result.is_err() {
-                state.set(State::Detached);
+enum Frame {
+    /// A call to `Jvm::with` (e.g., from `JvmOp::execute`).
+    Duchess(EnvPtr<'static>),
+
+    /// A native function that was invoked by the JVM.
+    JniCallback(EnvPtr<'static>),
+}
+
+impl Frame {
+    fn env(&self) -> EnvPtr<'static> {
+        match *self {
+            Frame::Duchess(env) | Frame::JniCallback(env) => env,
+        }
+    }
+}
+
+impl ThreadState {
+    /// The env pointer for the current thread, if duchess knows the thread to be attached.
+    fn env(&self) -> Option<EnvPtr<'static>> {
+        self.frames.last().map(Frame::env).or(self.permanent)
+    }
+}
+
+impl Drop for ThreadState {
+    /// Detaches the thread as it exits if it was permanently attached.
+    fn drop(&mut self) {
+        if self.permanent.take().is_none() {
+            return;
+        }
+        if let Ok(_lock) = crate::jvm::lock_jvm() {
+            let jvm = crate::jvm::unwrap_global_jvm();
+            // SAFETY: the thread is exiting, so no local refs are accessible
+            if let Err(err) = unsafe { jvm.detach_thread() } {
+                tracing::warn!(?err, "couldn't detach thread from JVM");
             }
-            result
         }
-    })
+        PERMANENT_THREADS.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
-/// Marks the current thread as attached until `detach_from_jni_callback` is called.
+/// Returns the number of duchess and JNI callback frames currently live on this thread.
+#[cfg(test)]
+pub(crate) fn depth() -> usize {
+    STATE.with(|state| state.borrow().frames.len())
+}
+
+/// Marks the current thread as attached until the returned guard is dropped.
 /// Intended for use within JNI calls of native functions.
-/// Returns the previous thread state, which should be given to `detach_from_jni_callback`
-/// as a parameter.
 ///
 /// # Safety condition
 ///
@@ -50,83 +95,152 @@ fn attached_or(jvm: JvmPtr, f: impl FnOnce() -> Result<AttachGuard>) -> Result<A
 /// which guarantees that the current thread is attached and will stay that way.
 ///
 /// Caller must drop the guard object that is returned before returning control to the JVM.
-#[must_use = "remember to call `detach_from_jni_callback`"]
+#[must_use = "the thread is only marked as attached while the guard is live"]
 pub unsafe fn attach_from_jni_callback(env: EnvPtr<'_>) -> JniCallbackGuard<'_> {
-    let old_state = STATE.with(|state| {
-        // Unsafe condition: `env` pointer returned from transmute will not
-        // live past the drop of the guard object that we return,
-        // and that guard object is contained in in its original lifetime.
-        let env: EnvPtr<'static> = unsafe { std::mem::transmute(env) };
-        state.replace(State::AttachedPermanently(env))
+    // Unsafe condition: `env` pointer returned from transmute will not
+    // live past the drop of the guard object that we return,
+    // and that guard object is contained in in its original lifetime.
+    let static_env: EnvPtr<'static> = unsafe { std::mem::transmute(env) };
+    STATE.with(|state| {
+        state
+            .borrow_mut()
+            .frames
+            .push(Frame::JniCallback(static_env))
     });
-    JniCallbackGuard { env, old_state }
+    JniCallbackGuard { env }
 }
 
-/// A guard object whose destructor restores the thread attachment state
-/// to whatever it was before the JNI invocation began.
-/// See [`attach_from_jni_callback`][] for more details.
+/// A guard object whose destructor pops the frame pushed by [`attach_from_jni_callback`][].
 pub struct JniCallbackGuard<'env> {
     env: EnvPtr<'env>,
-    old_state: State,
 }
 
 impl Drop for JniCallbackGuard<'_> {
     fn drop(&mut self) {
-        STATE.with(|state| {
-            let old_state = std::mem::replace(&mut self.old_state, State::InUse);
-            let jni_state = state.replace(old_state);
-
-            // Unsafe condition: this pointer will not actually live past end of this block
-            // so it remains inside its original lifetime.
-            let env: EnvPtr<'static> = unsafe { std::mem::transmute(self.env) };
-            assert!(
-                jni_state == State::AttachedPermanently(env),
-                "invalid prior state `{jni_state:?}`"
-            );
-        });
+        // Unsafe condition: this pointer will not actually live past end of this block
+        // so it remains inside its original lifetime.
+        let env: EnvPtr<'static> = unsafe { std::mem::transmute(self.env) };
+        let frame = STATE.with(|state| state.borrow_mut().frames.pop());
+        assert!(
+            frame == Some(Frame::JniCallback(env)),
+            "invalid innermost frame `{frame:?}`"
+        );
+    }
+}
+
+/// Attaches the current thread to the JVM until the thread exits.
+/// Calling this while some frame has already attached the thread
+/// prevents that frame from detaching it.
+pub fn attach_permanently(jvm: JvmPtr) -> Result<()> {
+    let env = STATE.with(|state| state.borrow().env());
+    let env = match env {
+        Some(env) => env,
+        // no-op if already attached outside of duchess
+        None => unsafe { jvm.attach_thread()? },
+    };
+    let previous = STATE.with(|state| state.borrow_mut().permanent.replace(env));
+    if previous.is_none() {
+        PERMANENT_THREADS.fetch_add(1, Ordering::SeqCst);
     }
+    Ok(())
 }
 
-pub fn attach_permanently(jvm: JvmPtr) -> Result<AttachGuard> {
-    attached_or(jvm, || {
-        Ok(AttachGuard {
-            jvm,
-            // no-op if already attached outside of duchess
-            env: unsafe { jvm.attach_thread()? },
-            permanent: true,
-        })
+/// Fails if the current thread is in the middle of using the JVM, and so cannot shut it down.
+pub(crate) fn check_can_shut_down() -> Result<()> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        if !state.frames.is_empty() || state.critical > 0 {
+            return Err(Error::JvmInternal(
+                "cannot shut down the JVM from within a JVM operation".to_string(),
+            ));
+        }
+        Ok(())
     })
 }
 
-pub unsafe fn attach<'jvm>(jvm: JvmPtr) -> Result<AttachGuard> {
-    attached_or(jvm, || {
-        Ok(AttachGuard {
-            jvm,
-            // no-op if already attached outside of duchess
-            env: unsafe { jvm.attach_thread()? },
-            permanent: false,
-        })
+/// Detaches the current thread if it was permanently attached, in preparation for destroying the JVM.
+/// Fails if other threads are permanently attached. Must be called while no other thread
+/// can attach permanently, i.e. with the JVM locked for shutdown.
+pub(crate) fn detach_for_shutdown(jvm: JvmPtr) -> Result<()> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let this_thread = usize::from(state.permanent.is_some());
+        let others = PERMANENT_THREADS.load(Ordering::SeqCst) - this_thread;
+        if others > 0 {
+            return Err(Error::JvmInternal(format!(
+                "cannot shut down the JVM while {others} other thread(s) are permanently attached"
+            )));
+        }
+        if state.permanent.take().is_some() {
+            PERMANENT_THREADS.fetch_sub(1, Ordering::SeqCst);
+            // SAFETY: there are no frames, so no local refs are accessible
+            unsafe { jvm.detach_thread()? };
+        }
+        Ok(())
     })
 }
 
-/// When dropped, will detach the current thread from the JVM unless it was permanently attached.
+/// Pushes a new duchess frame, attaching the current thread first if needed.
+/// The frame is popped (and the thread detached, if this frame attached it)
+/// when the returned guard is dropped.
+///
+/// # Safety
+///
+/// The caller must not let the JVM be deinitialized while the guard is live,
+/// for example by holding a `JvmLock`.
+pub unsafe fn attach(jvm: JvmPtr) -> Result<AttachGuard> {
+    if STATE.with(|state| state.borrow().critical > 0) {
+        return Err(Error::JvmInternal(
+            "cannot call into the JVM while a critical region is held".to_string(),
+        ));
+    }
+
+    let (env, detach) = match STATE.with(|state| state.borrow().env()) {
+        Some(env) => (env, false),
+        None => match unsafe { jvm.env()? } {
+            // Attached by someone other than duchess, so it's not ours to detach.
+            Some(env) => (env, false),
+            None => (unsafe { jvm.attach_thread()? }, true),
+        },
+    };
+
+    if let Err(err) = unsafe { env.push_local_frame(LOCAL_FRAME_CAPACITY) } {
+        if detach {
+            let _ = unsafe { jvm.detach_thread() };
+        }
+        return Err(err);
+    }
+
+    STATE.with(|state| state.borrow_mut().frames.push(Frame::Duchess(env)));
+    Ok(AttachGuard { jvm, env, detach })
+}
+
+/// When dropped, pops the local reference frame and, if this was the frame that attached
+/// the current thread, detaches it from the JVM unless it has since been permanently attached.
 pub struct AttachGuard {
     jvm: JvmPtr,
     env: EnvPtr<'static>, // not send!
-    permanent: bool,
+    detach: bool,
 }
 
 impl Drop for AttachGuard {
     fn drop(&mut self) {
-        if self.permanent {
-            STATE.with(|state| {
-                let old_state = state.replace(State::AttachedPermanently(self.env));
-                debug_assert!(matches!(old_state, State::InUse))
-            });
-        } else {
-            match unsafe { self.jvm.detach_thread() } {
-                Ok(()) => STATE.with(|state| state.set(State::Detached)),
-                Err(err) => tracing::warn!(?err, "couldn't detach thread from JVM"),
+        // SAFETY: all locals created in this frame were tied to the lifetime of the guard's env
+        unsafe { self.env.pop_local_frame() };
+
+        let permanent = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            let frame = state.frames.pop();
+            debug_assert!(
+                frame == Some(Frame::Duchess(self.env)),
+                "invalid innermost frame `{frame:?}`"
+            );
+            state.permanent.is_some()
+        });
+
+        if self.detach && !permanent {
+            if let Err(err) = unsafe { self.jvm.detach_thread() } {
+                tracing::warn!(?err, "couldn't detach thread from JVM");
             }
         }
     }
@@ -137,3 +251,24 @@ impl AttachGuard {
         self.env
     }
 }
+
+/// Marks the current thread as holding a critical region until the
+/// returned guard is dropped. While it is live, [`attach`][] refuses to push new frames,
+/// so that operations executed from within the region fail instead of calling into the JVM.
+pub(crate) fn enter_critical() -> CriticalGuard {
+    STATE.with(|state| state.borrow_mut().critical += 1);
+    CriticalGuard {
+        not_send: PhantomData,
+    }
+}
+
+/// A guard object whose destructor leaves the region entered by [`enter_critical`][].
+pub(crate) struct CriticalGuard {
+    not_send: PhantomData<*const ()>,
+}
+
+impl Drop for CriticalGuard {
+    fn drop(&mut self) {
+        STATE.with(|state| state.borrow_mut().critical -= 1);
+    }
+}
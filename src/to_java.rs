@@ -250,12 +250,14 @@ where
                 Error::Thrown(t) => Err(Error::Thrown(jvm.local(t))),
                 Error::SliceTooLong(t) => Err(Error::SliceTooLong(*t)),
                 Error::NullDeref => Err(Error::NullDeref),
-                Error::NestedUsage => Err(Error::NestedUsage),
                 Error::JvmAlreadyExists => Err(Error::JvmAlreadyExists),
+                Error::JvmShutDown => Err(Error::JvmShutDown),
                 Error::UnableToLoadLibjvm(t) => Err(Error::UnableToLoadLibjvm(
                     format!("UnableToLoadLibjvm({t:?})").as_str().into(), // FIXME: should to_java_impl be `self` ?
                 )),
                 Error::JvmInternal(t) => Err(Error::JvmInternal(t.clone())),
+                Error::Serde(t) => Err(Error::Serde(t.clone())),
+                Error::InvalidJvmConfig(t) => Err(Error::InvalidJvmConfig(t.clone())),
             },
         }
     }
@@ -276,12 +278,14 @@ where
                 Error::Thrown(t) => Err(Error::Thrown(jvm.local(t))),
                 Error::SliceTooLong(t) => Err(Error::SliceTooLong(*t)),
                 Error::NullDeref => Err(Error::NullDeref),
-                Error::NestedUsage => Err(Error::NestedUsage),
                 Error::JvmAlreadyExists => Err(Error::JvmAlreadyExists),
+                Error::JvmShutDown => Err(Error::JvmShutDown),
                 Error::UnableToLoadLibjvm(t) => Err(Error::UnableToLoadLibjvm(
                     format!("UnableToLoadLibjvm({t:?})").as_str().into(), // FIXME: should to_java_impl be `self` ?
                 )),
                 Error::JvmInternal(t) => Err(Error::JvmInternal(t.clone())),
+                Error::Serde(t) => Err(Error::Serde(t.clone())),
+                Error::InvalidJvmConfig(t) => Err(Error::InvalidJvmConfig(t.clone())),
             },
         }
     }
@@ -306,12 +310,14 @@ where
                 Error::Thrown(t) => Err(Error::Thrown(jvm.local(t))),
                 Error::SliceTooLong(t) => Err(Error::SliceTooLong(*t)),
                 Error::NullDeref => Err(Error::NullDeref),
-                Error::NestedUsage => Err(Error::NestedUsage),
                 Error::JvmAlreadyExists => Err(Error::JvmAlreadyExists),
+                Error::JvmShutDown => Err(Error::JvmShutDown),
                 Error::UnableToLoadLibjvm(t) => Err(Error::UnableToLoadLibjvm(
                     format!("UnableToLoadLibjvm({t:?})").as_str().into(), // FIXME: should to_java_scalar be `self` ?
                 )),
                 Error::JvmInternal(t) => Err(Error::JvmInternal(t.clone())),
+                Error::Serde(t) => Err(Error::Serde(t.clone())),
+                Error::InvalidJvmConfig(t) => Err(Error::InvalidJvmConfig(t.clone())),
             },
         }
     }
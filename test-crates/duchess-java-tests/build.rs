@@ -3,7 +3,7 @@ use std::{
     process::Command,
 };
 
-use duchess_build_rs::Configuration;
+use duchess_build_rs::{Configuration, Reflection};
 use walkdir::WalkDir;
 
 // This controls where this script writes built files
@@ -42,12 +42,21 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
-    // This has to run after the compilations above
+    // Package the test classes into a jar, so that reflection on jars is exercised as well.
+    let configuration = Configuration::default();
+    let jar = target_dir.join("java.jar");
+    build_jar(&configuration, &jar, &target_dir.join("java"))?;
+
+    // This has to run after the compilations above.
+    // The test classes are reflected from their bytecode, while the JDK is still reflected via
+    // `javap` (the default), so that both reflectors are covered.
     duchess_build_rs::DuchessBuildRs::new()
-        .with_configuration(
-            Configuration::default()
-                .with_classpath("../target/java:../target/tests/java-to-rust/java"),
-        )
+        .with_configuration(configuration.with_classpath(format!(
+            "{}:../target/tests/java-to-rust/java",
+            jar.display()
+        )))
+        .with_reflection(Reflection::Bytecode)
+        .with_package_reflection("java", Reflection::Javap)
         .execute()?;
 
     Ok(())
@@ -90,3 +99,25 @@ fn build_java(input: &BuildFile, source_path: &str) -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// Packages the class files in `classes_dir` into `jar`
+fn build_jar(configuration: &Configuration, jar: &Path, classes_dir: &Path) -> std::io::Result<()> {
+    let output = Command::new(configuration.bin_path("jar"))
+        .arg("--create")
+        .arg("--file")
+        .arg(jar)
+        .arg("-C")
+        .arg(classes_dir)
+        .arg(".")
+        .output()?;
+
+    if !output.status.success() {
+        panic!(
+            "Failed to build {:?}: {}",
+            jar,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(())
+}
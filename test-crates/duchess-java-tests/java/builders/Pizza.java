@@ -0,0 +1,54 @@
+package builders;
+
+public class Pizza {
+    private final int size;
+    private final String topping;
+    private final String crust;
+
+    private Pizza(int size, String topping, String crust) {
+        this.size = size;
+        this.topping = topping;
+        this.crust = crust;
+    }
+
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    public int getSize() {
+        return size;
+    }
+
+    public String getTopping() {
+        return topping;
+    }
+
+    public String getCrust() {
+        return crust;
+    }
+
+    public static class Builder {
+        private int size;
+        private String topping;
+        private String crust = "thin";
+
+        public Builder withSize(int size) {
+            this.size = size;
+            return this;
+        }
+
+        public Builder withTopping(String topping) {
+            this.topping = topping;
+            return this;
+        }
+
+        public Builder setCrust(String crust) {
+            this.crust = crust;
+            return this;
+        }
+
+        public Pizza bake() {
+            return new Pizza(size, topping, crust);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+package derives;
+
+public class Meters {
+    private final double value;
+
+    public Meters(double value) {
+        this.value = value;
+    }
+
+    public double getValue() {
+        return value;
+    }
+}
@@ -0,0 +1,43 @@
+package derives;
+
+public class User {
+    private final String userId;
+    private final String displayName;
+    private final boolean active;
+    private final int loginCount;
+    private final String nickname;
+    private final long createdMillis;
+
+    public User(String userId, String displayName, boolean active, int loginCount, String nickname, long createdMillis) {
+        this.userId = userId;
+        this.displayName = displayName;
+        this.active = active;
+        this.loginCount = loginCount;
+        this.nickname = nickname;
+        this.createdMillis = createdMillis;
+    }
+
+    public String getUserId() {
+        return userId;
+    }
+
+    public String fetchDisplayName() {
+        return displayName;
+    }
+
+    public boolean isActive() {
+        return active;
+    }
+
+    public int getLoginCount() {
+        return loginCount;
+    }
+
+    public String getNickname() {
+        return nickname;
+    }
+
+    public long getCreatedMillis() {
+        return createdMillis;
+    }
+}
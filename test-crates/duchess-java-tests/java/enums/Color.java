@@ -0,0 +1,21 @@
+package enums;
+
+public enum Color {
+    RED("#ff0000"),
+    GREEN("#00ff00"),
+    DARK_BLUE("#00008b");
+
+    private final String hex;
+
+    Color(String hex) {
+        this.hex = hex;
+    }
+
+    public String hex() {
+        return hex;
+    }
+
+    public static String describe(Color color) {
+        return color.name() + " is " + color.hex;
+    }
+}
@@ -0,0 +1,18 @@
+package exceptions;
+
+public class Config {
+    public String get(String key) {
+        switch (key) {
+            case "missing":
+                throw new MissingKeyException(key);
+            case "invalid":
+                throw new ConfigException("invalid value for " + key);
+            case "state":
+                throw new IllegalStateException("not loaded yet");
+            case "null":
+                return null;
+            default:
+                return key + "-value";
+        }
+    }
+}
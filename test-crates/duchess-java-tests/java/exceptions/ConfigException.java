@@ -0,0 +1,7 @@
+package exceptions;
+
+public class ConfigException extends RuntimeException {
+    public ConfigException(String message) {
+        super(message);
+    }
+}
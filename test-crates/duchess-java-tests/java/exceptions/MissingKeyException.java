@@ -0,0 +1,14 @@
+package exceptions;
+
+public class MissingKeyException extends ConfigException {
+    private final String key;
+
+    public MissingKeyException(String key) {
+        super("missing key " + key);
+        this.key = key;
+    }
+
+    public String getKey() {
+        return key;
+    }
+}
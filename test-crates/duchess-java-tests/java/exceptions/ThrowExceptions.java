@@ -8,6 +8,12 @@ public class ThrowExceptions {
         throw new RuntimeException("something has gone horribly wrong");
     }
 
+    public void throwWithCause() {
+        RuntimeException exception = new RuntimeException("outer problem", new IllegalStateException("inner problem"));
+        exception.addSuppressed(new IllegalArgumentException("cleanup failed"));
+        throw exception;
+    }
+
     public Object nullObject() {
         Object a = null;
         return a;
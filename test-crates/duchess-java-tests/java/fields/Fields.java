@@ -0,0 +1,35 @@
+package fields;
+
+public class Fields<T> {
+    public int count;
+    public long total;
+    public boolean flag;
+    public double ratio;
+    public char letter;
+    public String name;
+    public T value;
+    public int[] numbers;
+    public volatile int generation;
+    public final String id;
+
+    public static String greeting = "hello";
+    public static volatile long counter;
+    public static final int MAX = 10;
+
+    // Shadowed by the method of the same name
+    public int size = 22;
+
+    int packagePrivate;
+
+    public Fields(String id) {
+        this.id = id;
+    }
+
+    public int size() {
+        return size * 2;
+    }
+
+    public String describe() {
+        return id + ": " + name + " " + count + " " + value;
+    }
+}
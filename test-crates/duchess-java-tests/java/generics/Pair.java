@@ -0,0 +1,28 @@
+package generics;
+
+public class Pair<A, B> {
+    private final A first;
+    private final B second;
+
+    public Pair(A first, B second) {
+        this.first = first;
+        this.second = second;
+    }
+
+    public A getFirst() {
+        return first;
+    }
+
+    public B getSecond() {
+        return second;
+    }
+
+    public static <T> Pair<T, T> twin(T value) {
+        return new Pair<>(value, value);
+    }
+
+    // The method's `B` is unrelated to the `B` of the class
+    public static <B, A> Pair<B, A> swapped(Pair<A, B> pair) {
+        return new Pair<>(pair.second, pair.first);
+    }
+}
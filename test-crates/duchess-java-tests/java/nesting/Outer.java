@@ -0,0 +1,59 @@
+package nesting;
+
+import java.util.List;
+
+public class Outer {
+    private final String name;
+
+    public Outer(String name) {
+        this.name = name;
+    }
+
+    public static class Nested {
+        private final int value;
+
+        public Nested(int value) {
+            this.value = value;
+        }
+
+        public int getValue() {
+            return value;
+        }
+
+        public static class Deeper {
+            public Deeper() {}
+
+            public String hello() {
+                return "deeper";
+            }
+        }
+    }
+
+    public class Inner {
+        private final String suffix;
+
+        public Inner(String suffix) {
+            this.suffix = suffix;
+        }
+
+        public String describe() {
+            return name + suffix;
+        }
+    }
+
+    public class Holder<T> {
+        private final List<T> items;
+
+        public Holder(List<T> items) {
+            this.items = items;
+        }
+
+        public String describe() {
+            return name + items.size();
+        }
+    }
+
+    public Nested makeNested(int value) {
+        return new Nested(value);
+    }
+}
@@ -0,0 +1,8 @@
+package records;
+
+public record Person(String firstName, int age, String nickname) {
+    public static String describe(Person person) {
+        String name = person.nickname() != null ? person.nickname() : person.firstName;
+        return name + " (" + person.age + ")";
+    }
+}
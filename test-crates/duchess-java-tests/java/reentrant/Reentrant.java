@@ -0,0 +1,15 @@
+package reentrant;
+
+public class Reentrant {
+    public String outer(String name) {
+        return "outer(" + rustMiddle(name) + ")";
+    }
+
+    native String rustMiddle(String name);
+
+    public String inner(String name) {
+        return "inner(" + rustLeaf(name) + ")";
+    }
+
+    native String rustLeaf(String name);
+}
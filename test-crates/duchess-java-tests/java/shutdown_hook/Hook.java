@@ -0,0 +1,13 @@
+package shutdown_hook;
+
+public class Hook implements Runnable {
+    public static void register() {
+        Runtime.getRuntime().addShutdownHook(new Thread(new Hook()));
+    }
+
+    public void run() {
+        callback();
+    }
+
+    native int callback();
+}
@@ -0,0 +1,41 @@
+package varargs;
+
+public class Varargs {
+    private final String[] names;
+
+    public Varargs(String... names) {
+        this.names = names;
+    }
+
+    public int count() {
+        return names.length;
+    }
+
+    public String names(String separator) {
+        return String.join(separator, names);
+    }
+
+    public String prefixed(String prefix, String... parts) {
+        StringBuilder builder = new StringBuilder();
+        for (String part : parts) {
+            builder.append(prefix).append(part);
+        }
+        return builder.toString();
+    }
+
+    public static int sum(int... values) {
+        int sum = 0;
+        for (int value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static String describe(Object... values) {
+        StringBuilder builder = new StringBuilder();
+        for (Object value : values) {
+            builder.append(value == null ? "null" : value.toString()).append(";");
+        }
+        return builder.toString();
+    }
+}
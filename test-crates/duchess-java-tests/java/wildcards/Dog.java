@@ -0,0 +1,7 @@
+package wildcards;
+
+public class Dog extends Animal {
+    public Dog(String name) {
+        super(name);
+    }
+}
@@ -0,0 +1,38 @@
+package wildcards;
+
+import java.util.ArrayList;
+import java.util.List;
+
+public class Wildcards {
+    public List<? extends Animal> animals = new ArrayList<>();
+
+    public static String names(List<? extends Animal> animals) {
+        StringBuilder builder = new StringBuilder();
+        for (Animal animal : animals) {
+            builder.append(animal.getName()).append(";");
+        }
+        return builder.toString();
+    }
+
+    public static void addDog(List<? super Dog> sink, Dog dog) {
+        sink.add(dog);
+    }
+
+    public static int count(List<?> list) {
+        return list.size();
+    }
+
+    public static List<? extends Animal> pack() {
+        return List.of(new Dog("rex"), new Animal("generic"));
+    }
+
+    public static List<?> anything() {
+        return List.of("a", "b");
+    }
+
+    public static List<? super Dog> kennel() {
+        List<Animal> kennel = new ArrayList<>();
+        kennel.add(new Dog("fido"));
+        return kennel;
+    }
+}
@@ -0,0 +1,36 @@
+//@run
+use duchess::java::{ArrayCriticalExt, ArrayExt};
+use duchess::{java, prelude::*};
+
+fn main() -> duchess::Result<()> {
+    let bytes: Java<java::Array<i8>> = vec![1i8, 2, 3, 4].to_java().assert_not_null().execute()?;
+
+    // Read the elements in place
+    let sum: i64 = bytes
+        .with_critical(|bytes| bytes.iter().map(|&b| b as i64).sum::<i64>())
+        .execute()?;
+    assert_eq!(sum, 10);
+
+    // Changes made through the mutable slice are visible from Java
+    // SAFETY: `bytes` is not shared with Java or other threads
+    let () = unsafe { bytes.with_critical_mut(|bytes| bytes.iter_mut().for_each(|b| *b *= 2)) }
+        .execute()?;
+    let doubled: Vec<i8> = bytes.execute()?;
+    assert_eq!(doubled, vec![2, 4, 6, 8]);
+
+    let doubles: Java<java::Array<f64>> = vec![0.5f64; 3].to_java().assert_not_null().execute()?;
+    // SAFETY: `doubles` is not shared with Java or other threads
+    let () = unsafe { doubles.with_critical_mut(|doubles| doubles[1] = 1.5) }.execute()?;
+    let doubles: Vec<f64> = doubles.execute()?;
+    assert_eq!(doubles, vec![0.5, 1.5, 0.5]);
+
+    // Calling into the JVM while the elements are pinned fails instead of deadlocking
+    let inner = &bytes;
+    let nested_call_failed: bool = bytes
+        .with_critical(move |_| inner.length().execute().is_err())
+        .execute()?;
+    assert!(nested_call_failed);
+    assert_eq!(bytes.length().execute()?, 4);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//@run
+use duchess::java::ArrayExt;
+use duchess::{java, prelude::*};
+
+fn main() -> duchess::Result<()> {
+    // Arrays of scalars
+    let ints: Java<java::Array<i32>> = vec![10i32, 20, 30, 40]
+        .to_java()
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(ints.get(1).execute::<i32>()?, 20);
+    assert_eq!(ints.get_region(1..3).execute::<Vec<i32>>()?, vec![20, 30]);
+    assert_eq!(ints.get_region(2..2).execute::<Vec<i32>>()?, vec![]);
+    ints.set(0, 11).execute()?;
+    assert_eq!(ints.execute::<Vec<i32>>()?, vec![11, 20, 30, 40]);
+
+    // Out of bounds accesses throw
+    assert!(ints.get(4).execute::<i32>().is_err());
+    assert!(ints.get_region(2..5).execute::<Vec<i32>>().is_err());
+    assert!(ints.set(usize::MAX, 0).execute().is_err());
+
+    // Arrays of objects can be created from Rust collections...
+    let strings: Java<java::Array<java::lang::String>> = vec!["a", "b", "c"]
+        .to_java::<java::Array<java::lang::String>>()
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(strings.length().execute()?, 3);
+    let b: Option<String> = strings.get(1).execute()?;
+    assert_eq!(b.as_deref(), Some("b"));
+
+    // ...modified, including with nulls...
+    strings.set(0, "z").execute()?;
+    strings
+        .set(2, &None::<Java<java::lang::String>>)
+        .execute()?;
+    let tail: Vec<Option<String>> = strings.get_region(1..3).execute()?;
+    assert_eq!(tail, vec![Some("b".to_string()), None]);
+
+    // ...and converted back as a whole
+    let all: Vec<Option<String>> = strings.execute()?;
+    assert_eq!(
+        all,
+        vec![Some("z".to_string()), Some("b".to_string()), None]
+    );
+
+    let nullable: Java<java::Array<java::lang::String>> = vec![Some("x"), None]
+        .to_java::<java::Array<java::lang::String>>()
+        .assert_not_null()
+        .execute()?;
+    let nullable: Vec<Option<String>> = nullable.execute()?;
+    assert_eq!(nullable, vec![Some("x".to_string()), None]);
+
+    Ok(())
+}
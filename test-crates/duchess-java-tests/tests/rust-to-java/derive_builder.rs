@@ -0,0 +1,68 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package builders;
+    class builders.Pizza { * }
+    class builders.Pizza$Builder { * }
+
+    package log;
+    class Event { * }
+    interface NameStep { * }
+    interface TimeStep { * }
+    interface BuildStep { * }
+}
+
+// A builder that returns itself from each setter
+#[derive(duchess::ToJava, duchess::ToRust)]
+#[java(builder = builders.Pizza::builder, build = bake)]
+#[java(builders.Pizza)]
+struct Pizza {
+    size: i32,
+    topping: String,
+    // `None` skips `setCrust`, keeping the default of the builder
+    crust: Option<String>,
+    #[java(skip)]
+    price: u32,
+}
+
+// A step builder, whose setters must be called in order
+#[derive(duchess::ToJava)]
+#[java(builder = log.Event::builder)]
+struct Event {
+    time: Java<java::util::Date>,
+    #[java(setter = "withName")]
+    title: String,
+}
+
+pub fn main() -> duchess::Result<()> {
+    let pizza = Pizza {
+        size: 12,
+        topping: "basil".to_string(),
+        crust: None,
+        price: 10,
+    };
+    let java: Java<builders::Pizza> = pizza.to_java().assert_not_null().execute()?;
+    assert_eq!(java.get_size().execute()?, 12);
+    let crust: String = java.get_crust().assert_not_null().execute()?;
+    assert_eq!(crust, "thin");
+
+    let pizza = Pizza {
+        crust: Some("thick".to_string()),
+        ..pizza
+    };
+    let and_back: Pizza = pizza.to_java().assert_not_null().execute()?;
+    assert_eq!(and_back.topping, "basil");
+    assert_eq!(and_back.crust.as_deref(), Some("thick"));
+    assert_eq!(and_back.price, 0);
+
+    let event = Event {
+        time: java::util::Date::new().execute()?,
+        title: "launch".to_string(),
+    };
+    let java: Java<log::Event> = event.to_java().assert_not_null().execute()?;
+    let name: String = java.name().assert_not_null().execute()?;
+    assert_eq!(name, "launch");
+
+    Ok(())
+}
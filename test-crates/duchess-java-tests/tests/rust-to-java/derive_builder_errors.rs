@@ -0,0 +1,30 @@
+duchess::java_package! {
+    package log;
+    class Event { * }
+    interface NameStep { * }
+    interface TimeStep { * }
+    interface BuildStep { * }
+}
+
+#[derive(duchess::ToJava)]
+#[java(builder = log.Event::builder)]
+struct OptionalStep {
+    time: Option<Java<java::util::Date>>, //~ ERROR: setter `withTime` for optional field must return the builder `log.TimeStep<log.NameStep>`, not `log.NameStep`
+    name: String,
+}
+
+#[derive(duchess::ToJava)]
+#[java(builder = log.Event::builder)]
+struct OutOfOrder {
+    name: String, //~ ERROR: no setter for field `name` found on builder `log.TimeStep<log.NameStep>`
+    time: Java<java::util::Date>,
+}
+
+#[derive(duchess::ToJava)]
+#[java(builder = log.Event::builder, build = finish)] //~ ERROR: no method `finish` without arguments found on builder `log.BuildStep`
+struct WrongBuild {
+    time: Java<java::util::Date>,
+    name: String,
+}
+
+fn main() {}
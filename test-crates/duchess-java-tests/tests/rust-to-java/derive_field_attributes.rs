@@ -0,0 +1,95 @@
+//@run
+use std::time::Duration;
+
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package derives;
+    class derives.User { * }
+}
+
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(derives.User)]
+struct User {
+    // bean-style `getUserId`
+    user_id: String,
+    #[java(getter = "fetchDisplayName")]
+    display_name: String,
+    // bean-style `isActive`
+    active: bool,
+    login_count: i32,
+    #[java(default)]
+    nickname: String,
+    #[java(getter = "getCreatedMillis", with = millis)]
+    created: Duration,
+    #[java(skip)]
+    cached: Vec<u8>,
+}
+
+mod millis {
+    use std::time::Duration;
+
+    pub fn to_rust(millis: i64) -> Duration {
+        Duration::from_millis(millis as u64)
+    }
+
+    pub fn to_java(duration: &Duration) -> i64 {
+        duration.as_millis() as i64
+    }
+}
+
+#[derive(Debug, PartialEq, duchess::ToRust)]
+#[java(rename_all = "camelCase")]
+#[java(derives.User)]
+struct UserSummary {
+    user_id: String,
+    login_count: i32,
+}
+
+pub fn main() -> duchess::Result<()> {
+    let user = User {
+        user_id: "u-1".to_string(),
+        display_name: "Ferris".to_string(),
+        active: true,
+        login_count: 3,
+        nickname: "".to_string(),
+        created: Duration::from_millis(1234),
+        cached: vec![1, 2, 3],
+    };
+    let java: Java<derives::User> = user.to_java().assert_not_null().execute()?;
+    assert_eq!(
+        java.fetch_display_name()
+            .assert_not_null()
+            .execute::<String>()?,
+        "Ferris"
+    );
+    assert_eq!(java.get_created_millis().execute()?, 1234);
+
+    let and_back: User = java.execute()?;
+    assert_eq!(
+        and_back,
+        User {
+            cached: vec![],
+            ..user
+        }
+    );
+
+    // `default` fields are defaulted when the getter returns null
+    let java: Java<derives::User> =
+        derives::User::new("u-2", "Crab", false, 0, duchess::Null, 0i64)
+            .assert_not_null()
+            .execute()?;
+    let and_back: User = java.execute()?;
+    assert_eq!(and_back.nickname, "");
+    assert!(!and_back.active);
+
+    let summary: UserSummary = java.execute()?;
+    assert_eq!(
+        summary,
+        UserSummary {
+            user_id: "u-2".to_string(),
+            login_count: 0,
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+duchess::java_package! {
+    package derives;
+    class derives.User { * }
+}
+
+#[derive(duchess::ToRust)]
+#[java(rename_all = "kebab-case")] //~ ERROR: unknown rename rule `kebab-case`
+#[java(derives.User)]
+struct UnknownRenameRule {
+    user_id: String,
+}
+
+#[derive(duchess::ToRust)]
+#[java(derives.User)]
+struct UnknownFieldAttribute {
+    #[java(rename = "userId")] //~ ERROR: unsupported field attribute
+    id: String,
+}
+
+#[derive(duchess::ToJava)]
+#[java(derives.User)]
+struct SkippedWithGetter {
+    user_id: String,
+    display_name: String,
+    active: bool,
+    login_count: i32,
+    nickname: String,
+    created_millis: i64,
+    #[java(skip, getter = "getNickname")] //~ ERROR: skipped fields cannot have a `getter`, `setter` or `with` attribute
+    cached: String,
+}
+
+#[derive(duchess::ToJava)]
+#[java(derives.User)] //~ ERROR: selected method or constructor has 6 arguments, but there are 5 fields
+struct SkippedConstructorArgument {
+    user_id: String,
+    display_name: String,
+    active: bool,
+    login_count: i32,
+    #[java(skip)]
+    nickname: String,
+    created_millis: i64,
+}
+
+fn main() {}
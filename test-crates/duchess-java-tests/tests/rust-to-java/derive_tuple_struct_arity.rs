@@ -0,0 +1,39 @@
+duchess::java_package! {
+    package derives;
+    class derives.Point { * }
+
+    package records;
+    public record Person { * }
+}
+
+#[derive(duchess::ToJava)]
+#[java(derives.Point)] //~ ERROR: selected method or constructor has 2 arguments, but there are 3 fields
+struct TooManyFields(i32, i32, i32);
+
+#[derive(duchess::ToJava)]
+#[java(derives.Point::diagonal)] //~ ERROR: selected method or constructor has 1 arguments, but there are 2 fields
+struct TooManyFactoryFields(i32, i32);
+
+#[derive(duchess::ToJava)]
+#[java(records.Person)]
+struct TooFewComponents(String, i32); //~ ERROR: no field for component `nickname` of record `records.Person`
+
+#[derive(duchess::ToRust)]
+#[java(records.Person)]
+struct TooManyComponents(String, i32, Option<String>, bool); //~ ERROR: record `records.Person` has only 3 components
+
+#[derive(duchess::ToRust)]
+#[java(derives.Point)]
+struct MissingGetter(i32, i32); //~ ERROR: fields of tuple structs need a `#[java(getter = "...")]` to be converted to Rust
+
+#[derive(duchess::ToJava)]
+#[java(derives.Point)]
+#[java(transparent)] //~ ERROR: `transparent` requires a tuple struct or variant with a single field
+struct TransparentPair(i32, i32);
+
+#[derive(duchess::ToJava)]
+#[java(derives.Point::diagonal)] //~ ERROR: `transparent` requires a class name, not a method
+#[java(transparent)]
+struct TransparentFactory(i32);
+
+fn main() {}
@@ -0,0 +1,69 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package derives;
+    class derives.Point { * }
+    class derives.Meters { * }
+
+    package records;
+    public record Person { * }
+}
+
+// A newtype converts through the Java class of its field
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(java.lang.String)]
+#[java(transparent)]
+struct UserId(String);
+
+// Without `transparent`, a single field is passed to the constructor
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(derives.Meters)]
+struct Meters(#[java(getter = "getValue")] f64);
+
+// Tuple structs map positionally onto the constructor...
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(derives.Point)]
+struct Point(#[java(getter = "getX")] i32, #[java(getter = "getY")] i32);
+
+// ...or onto a static factory method
+#[derive(Debug, PartialEq, duchess::ToJava)]
+#[java(derives.Point::diagonal)]
+struct Diagonal(i32);
+
+// ...or onto the components of a record
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(records.Person)]
+struct Person(String, i32, Option<String>);
+
+pub fn main() -> duchess::Result<()> {
+    let id = UserId("u-1".to_string());
+    let java: Java<java::lang::String> = id.to_java().assert_not_null().execute()?;
+    assert_eq!(java.length().execute()?, 3);
+    let and_back: UserId = java.execute()?;
+    assert_eq!(and_back, id);
+
+    let meters = Meters(1.5);
+    let java: Java<derives::Meters> = meters.to_java().assert_not_null().execute()?;
+    assert_eq!(java.get_value().execute()?, 1.5);
+    let and_back: Meters = java.execute()?;
+    assert_eq!(and_back, meters);
+
+    let point = Point(3, 4);
+    let java: Java<derives::Point> = point.to_java().assert_not_null().execute()?;
+    assert_eq!(java.get_y().execute()?, 4);
+    let and_back: Point = java.execute()?;
+    assert_eq!(and_back, point);
+
+    let diagonal: Point = Diagonal(7).to_java().assert_not_null().execute()?;
+    assert_eq!(diagonal, Point(7, 7));
+
+    let person = Person("Ferris".to_string(), 8, None);
+    let java: Java<records::Person> = person.to_java().assert_not_null().execute()?;
+    let first_name: String = java.first_name().assert_not_null().execute()?;
+    assert_eq!(first_name, "Ferris");
+    let and_back: Person = java.execute()?;
+    assert_eq!(and_back, person);
+
+    Ok(())
+}
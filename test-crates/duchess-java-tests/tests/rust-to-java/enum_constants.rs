@@ -0,0 +1,47 @@
+//@run
+use duchess::{java, prelude::*};
+use java::ArrayExt;
+
+duchess::java_package! {
+    package enums;
+
+    public class Color { * }
+}
+
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(enums.Color)]
+enum RustColor {
+    Red,
+    Green,
+    DarkBlue,
+}
+
+fn main() -> duchess::Result<()> {
+    // Constants have accessors named after them, and are never null
+    let name: String = enums::Color::RED().name().assert_not_null().execute()?;
+    assert_eq!(name, "RED");
+    assert_eq!(enums::Color::DARK_BLUE().ordinal().execute()?, 2);
+    let hex: String = enums::Color::GREEN().hex().assert_not_null().execute()?;
+    assert_eq!(hex, "#00ff00");
+
+    // The static methods of enums are available too
+    let length = enums::Color::values()
+        .assert_not_null()
+        .length()
+        .execute()?;
+    assert_eq!(length, 3);
+    let green: Option<duchess::Java<enums::Color>> = enums::Color::value_of("GREEN").execute()?;
+    assert_eq!(green.unwrap().ordinal().execute()?, 1);
+
+    // Derived conversions map variants to constants by name
+    let color: RustColor = enums::Color::DARK_BLUE().execute()?;
+    assert_eq!(color, RustColor::DarkBlue);
+    let description: String = enums::Color::describe(&RustColor::Red)
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(description, "RED is #ff0000");
+    let color: RustColor = RustColor::Green.to_java().assert_not_null().execute()?;
+    assert_eq!(color, RustColor::Green);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+duchess::java_package! {
+    package enums;
+
+    public class Color { * }
+}
+
+#[derive(duchess::ToRust)]
+#[java(enums.Color)]
+enum MissingVariant { //~ ERROR: no variant for constant `DARK_BLUE` of Java enum `enums.Color`
+    Red,
+    Green,
+}
+
+#[derive(duchess::ToJava)]
+#[java(enums.Color)]
+enum UnknownVariant {
+    Red,
+    Purple, //~ ERROR: no constant named `PURPLE` in Java enum `enums.Color`
+}
+
+#[derive(duchess::ToJava)]
+#[java(enums.Color)]
+enum VariantWithFields {
+    Red,
+    Green(u8), //~ ERROR: variants of an enum mirroring Java enum `enums.Color` cannot have fields
+}
+
+fn main() {}
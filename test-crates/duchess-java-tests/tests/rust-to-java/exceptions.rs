@@ -54,9 +54,16 @@ fn catch_exceptions() -> duchess::Result<()> {
         .execute();
     assert!(matches!(caught_exception, Err(duchess::Error::Thrown(_))));
 
-    assert_eq!(
-        format!("{:?}", caught_exception),
-        "Err(Java invocation threw: java.lang.RuntimeException: something has gone horribly wrong)"
+    let debug = format!("{:?}", caught_exception);
+    assert!(
+        debug.starts_with(
+            "Err(Java invocation threw: java.lang.RuntimeException: something has gone horribly wrong\n"
+        ),
+        "{debug}"
+    );
+    assert!(
+        debug.contains("\tat exceptions.ThrowExceptions.throwRuntime(ThrowExceptions.java:"),
+        "{debug}"
     );
     Ok(())
 }
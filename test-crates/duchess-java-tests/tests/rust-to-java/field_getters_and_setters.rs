@@ -0,0 +1,82 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package fields;
+
+    public class Fields { * }
+}
+
+fn main() -> duchess::Result<()> {
+    let fields: Java<fields::Fields<java::lang::String>> = fields::Fields::new("f1").execute()?;
+
+    // Scalar fields
+    fields.set_count(42).execute()?;
+    fields.set_total(1_i64 << 40).execute()?;
+    fields.set_flag(true).execute()?;
+    fields.set_ratio(0.5).execute()?;
+    fields.set_letter('x' as u16).execute()?;
+    assert_eq!(fields.count().execute()?, 42);
+    assert_eq!(fields.total().execute()?, 1_i64 << 40);
+    assert!(fields.flag().execute()?);
+    assert_eq!(fields.ratio().execute()?, 0.5);
+    assert_eq!(fields.letter().execute()?, 'x' as u16);
+
+    // Volatile fields
+    fields.set_generation(7).execute()?;
+    assert_eq!(fields.generation().execute()?, 7);
+
+    // Reference fields, including generic ones, can be set to null
+    let name: Option<String> = fields.name().execute()?;
+    assert_eq!(name, None);
+    fields.set_name("Ferris").execute()?;
+    fields.set_value("crab").execute()?;
+    let name: Option<String> = fields.name().execute()?;
+    assert_eq!(name.as_deref(), Some("Ferris"));
+    let described: String = fields.describe().assert_not_null().execute()?;
+    assert_eq!(described, "f1: Ferris 42 crab");
+    fields.set_name(duchess::Null).execute()?;
+    let name: Option<String> = fields.name().execute()?;
+    assert_eq!(name, None);
+
+    // Getters are operations, so they can be passed to other operations
+    let value: String = fields.value().assert_not_null().execute()?;
+    assert_eq!(value, "crab");
+    fields.set_name(fields.id()).execute()?;
+    let name: Option<String> = fields.name().execute()?;
+    assert_eq!(name.as_deref(), Some("f1"));
+
+    // Final fields only have getters
+    let id: String = fields.id().assert_not_null().execute()?;
+    assert_eq!(id, "f1");
+
+    // Getters and setters are also available on the class, like instance methods
+    fields::Fields::set_count(&fields, 43).execute()?;
+    assert_eq!(fields::Fields::count(&fields).execute()?, 43);
+
+    // A method of the same name takes precedence over the field
+    assert_eq!(fields.size().execute()?, 44);
+
+    // Static fields
+    let greeting: String = fields::Fields::<java::lang::Object>::get_greeting()
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(greeting, "hello");
+    fields::Fields::<java::lang::Object>::set_greeting("goodbye").execute()?;
+    let greeting: String = fields::Fields::<java::lang::Object>::get_greeting()
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(greeting, "goodbye");
+
+    fields::Fields::<java::lang::Object>::set_counter(99_i64).execute()?;
+    assert_eq!(
+        fields::Fields::<java::lang::Object>::get_counter().execute()?,
+        99_i64
+    );
+    assert_eq!(
+        fields::Fields::<java::lang::Object>::get_max().execute()?,
+        10
+    );
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package fields;
+
+    public class Fields { * }
+}
+
+fn main() -> duchess::Result<()> {
+    let fields: Java<fields::Fields<java::lang::String>> = fields::Fields::new("f1").execute()?;
+    fields.set_id("f2").execute()?; //~ ERROR: no method named `set_id` found
+    fields::Fields::<java::lang::Object>::set_max(11).execute()?; //~ ERROR: no function or associated item named `set_max` found
+    // `size` has no getter since it is shadowed by the `size()` method, so it has no setter either
+    fields.set_size(3).execute()?; //~ ERROR: no method named `set_size` found
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//@run
+use duchess::{java, prelude::*, Java};
+
+duchess::java_package! {
+    package exceptions;
+
+    public class Config { * }
+    public class ConfigException { * }
+    public class MissingKeyException { * }
+}
+
+// Variants are listed least specific first: the derive still tries the most specific class first
+#[derive(Debug, duchess::JavaException)]
+enum ConfigError {
+    #[java(java.lang.RuntimeException)]
+    #[java(transparent)]
+    Runtime(Java<java::lang::RuntimeException>),
+
+    #[java(exceptions.ConfigException)]
+    Invalid { get_message: String },
+
+    #[java(exceptions.MissingKeyException)]
+    Missing { key: String },
+
+    Other(duchess::Error<Java<java::lang::Throwable>>),
+}
+
+fn get(config: &Java<exceptions::Config>, key: &str) -> Result<String, ConfigError> {
+    Ok(config.get(key).assert_not_null().execute()?)
+}
+
+fn main() -> duchess::Result<()> {
+    let config = exceptions::Config::new().execute()?;
+
+    assert_eq!(get(&config, "name").unwrap(), "name-value");
+
+    let error = get(&config, "missing").unwrap_err();
+    assert!(matches!(&error, ConfigError::Missing { key } if key == "missing"), "{error:?}");
+
+    let error = get(&config, "invalid").unwrap_err();
+    assert!(
+        matches!(&error, ConfigError::Invalid { get_message } if get_message == "invalid value for invalid"),
+        "{error:?}"
+    );
+
+    // `IllegalStateException` has no variant of its own
+    let ConfigError::Runtime(exception) = get(&config, "state").unwrap_err() else {
+        panic!("expected a runtime exception");
+    };
+    let message: String = exception.get_message().assert_not_null().execute()?;
+    assert_eq!(message, "not loaded yet");
+
+    // Errors that are not Java exceptions go to the variant without a class
+    let error = get(&config, "null").unwrap_err();
+    assert!(
+        matches!(error, ConfigError::Other(duchess::Error::NullDeref)),
+        "{error:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use duchess::{java, Java};
+
+duchess::java_package! {
+    package exceptions;
+
+    public class ConfigException { * }
+}
+
+#[derive(duchess::JavaException)]
+enum NoFallback { //~ ERROR: must have one variant without a `#[java(...)]` class
+    #[java(exceptions.ConfigException)]
+    Invalid,
+}
+
+#[derive(duchess::JavaException)]
+enum TwoFallbacks {
+    #[java(exceptions.ConfigException)]
+    Invalid,
+    Other(duchess::Error<Java<java::lang::Throwable>>),
+    Again(duchess::Error<Java<java::lang::Throwable>>), //~ ERROR: only one variant can be without a `#[java(...)]` class
+}
+
+#[derive(duchess::JavaException)]
+enum NamedFallback {
+    #[java(exceptions.ConfigException)]
+    Invalid,
+    Other { error: duchess::Error<Java<java::lang::Throwable>> }, //~ ERROR: must have a single unnamed field
+}
+
+#[derive(duchess::JavaException)]
+#[java(exceptions.ConfigException)]
+struct NotAnEnum; //~ ERROR: `JavaException` can only be derived for enums
+
+fn main() {}
@@ -0,0 +1,42 @@
+//@ run
+
+use std::sync::Mutex;
+
+use duchess::{java, prelude::*, Error, Java};
+
+duchess::java_package! {
+    package shutdown_hook;
+
+    public class shutdown_hook.Hook {
+        public static void register();
+        native int callback();
+    }
+}
+
+/// What the shutdown hook observed when it called back into Rust.
+static OBSERVED: Mutex<Option<String>> = Mutex::new(None);
+
+#[duchess::java_function(shutdown_hook.Hook::callback)]
+fn callback(_this: &shutdown_hook::Hook) -> i32 {
+    // The JVM is being destroyed, so this must fail rather than wait for the shutdown
+    let result = java::lang::Object::new().execute::<Java<java::lang::Object>>();
+    *OBSERVED.lock().unwrap() = Some(match result {
+        Err(Error::JvmShutDown) => "shut down".to_string(),
+        Err(err) => format!("unexpected error: {err}"),
+        Ok(_) => "executed".to_string(),
+    });
+    0
+}
+
+fn main() -> duchess::Result<()> {
+    duchess::Jvm::builder()
+        .link(callback::java_fn())
+        .try_launch()?;
+
+    shutdown_hook::Hook::register().execute()?;
+    duchess::Jvm::shutdown()?;
+
+    assert_eq!(OBSERVED.lock().unwrap().as_deref(), Some("shut down"));
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package nesting;
+
+    public class Outer { * }
+    public class Outer$Nested { * }
+    public class Outer$Nested$Deeper { * }
+    public class Outer$Inner { * }
+    public class Outer$Holder<T> {
+        public nesting.Outer$Holder(nesting.Outer, java.util.List<T>);
+        public java.lang.String describe();
+    }
+}
+
+fn main() -> duchess::Result<()> {
+    use nesting::{outer, Outer};
+
+    // Static nested classes are found in a module named after the enclosing class
+    let nested: Java<outer::Nested> = outer::Nested::new(42).execute()?;
+    assert_eq!(nested.get_value().execute()?, 42);
+    let deeper: Java<outer::nested::Deeper> = outer::nested::Deeper::new().execute()?;
+    let hello: String = deeper.hello().assert_not_null().execute()?;
+    assert_eq!(hello, "deeper");
+
+    // ...and can appear in signatures
+    let outer: Java<Outer> = Outer::new("outer").execute()?;
+    let nested: Java<outer::Nested> = outer.make_nested(7).assert_not_null().execute()?;
+    assert_eq!(nested.get_value().execute()?, 7);
+
+    // Inner classes take the instance of the enclosing class in their constructor
+    let inner: Java<outer::Inner> = outer::Inner::new(&outer, "-inner").execute()?;
+    let described: String = inner.describe().assert_not_null().execute()?;
+    assert_eq!(described, "outer-inner");
+
+    // ...including generic ones
+    let items: Java<java::util::List<java::lang::String>> =
+        java::util::List::of::<java::lang::String>(["a", "b"])
+            .assert_not_null()
+            .execute()?;
+    let holder: Java<outer::Holder<java::lang::String>> =
+        outer::Holder::new(&outer, &items).execute()?;
+    let described: String = holder.describe().assert_not_null().execute()?;
+    assert_eq!(described, "outer2");
+
+    // Nested classes in the JDK
+    let map: Java<java::util::HashMap<java::lang::String, java::lang::String>> =
+        java::util::HashMap::new().execute()?;
+    let _previous: Option<String> = map.put("key", "value").execute()?;
+    let entries: Java<
+        java::util::Set<java::util::map::Entry<java::lang::String, java::lang::String>>,
+    > = map.entry_set().assert_not_null().execute()?;
+    assert_eq!(entries.size().execute()?, 1);
+
+    Ok(())
+}
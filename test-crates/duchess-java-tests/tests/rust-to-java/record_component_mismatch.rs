@@ -0,0 +1,13 @@
+duchess::java_package! {
+    package records;
+
+    public record Person { //~ ERROR: record component `firstName` has type `java.lang.String`, not `int`
+    //~^ ERROR: no component named `nick` in the reflected record
+    //~^^ ERROR: record component `nickname` is not declared
+        private final int firstName;
+        private final int age;
+        private final java.lang.String nick;
+    }
+}
+
+fn main() {}
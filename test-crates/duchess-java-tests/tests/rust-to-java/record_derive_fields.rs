@@ -0,0 +1,21 @@
+duchess::java_package! {
+    package records;
+
+    public record Person { * }
+}
+
+#[derive(duchess::ToRust)]
+#[java(records.Person)]
+struct UnknownField {
+    first_name: String,
+    last_name: String, //~ ERROR: no component named `last_name` in record `records.Person`
+}
+
+#[derive(duchess::ToJava)]
+#[java(records.Person)]
+struct MissingComponent { //~ ERROR: no field for component `nickname` of record `records.Person`
+    first_name: String,
+    age: i32,
+}
+
+fn main() {}
@@ -0,0 +1,67 @@
+//@run
+use duchess::prelude::*;
+
+duchess::java_package! {
+    package records;
+
+    public record Person { * }
+}
+
+// Fields are matched to record components by name, in any order,
+// either exactly or converted to snake case.
+#[derive(Debug, PartialEq, duchess::ToRust, duchess::ToJava)]
+#[java(records.Person)]
+struct Person {
+    age: i32,
+    first_name: String,
+    nickname: Option<String>,
+}
+
+// Conversion to Rust can skip components.
+#[allow(non_snake_case)]
+#[derive(Debug, PartialEq, duchess::ToRust)]
+#[java(records.Person)]
+struct Name {
+    firstName: String,
+}
+
+fn main() -> duchess::Result<()> {
+    // Records have accessors for their components
+    let java: Java<records::Person> = records::Person::new("Ferris", 8, duchess::Null).execute()?;
+    let first_name: String = java.first_name().assert_not_null().execute()?;
+    assert_eq!(first_name, "Ferris");
+    assert_eq!(java.age().execute()?, 8);
+
+    let rust: Person = java.execute()?;
+    assert_eq!(
+        rust,
+        Person {
+            age: 8,
+            first_name: "Ferris".to_string(),
+            nickname: None,
+        }
+    );
+
+    let crab = Person {
+        age: 9,
+        first_name: "Ferris".to_string(),
+        nickname: Some("crab".to_string()),
+    };
+    let description: String = records::Person::describe(&crab)
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(description, "crab (9)");
+
+    let round_trip: Person = crab.to_java().assert_not_null().execute()?;
+    assert_eq!(round_trip, crab);
+
+    let name: Name = crab.to_java().assert_not_null().execute()?;
+    assert_eq!(
+        name,
+        Name {
+            firstName: "Ferris".to_string()
+        }
+    );
+
+    Ok(())
+}
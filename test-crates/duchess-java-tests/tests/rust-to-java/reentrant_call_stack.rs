@@ -0,0 +1,49 @@
+//@ run
+
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package reentrant;
+
+    public class reentrant.Reentrant {
+        public reentrant.Reentrant();
+        public java.lang.String outer(java.lang.String);
+        native java.lang.String rustMiddle(java.lang.String);
+        public java.lang.String inner(java.lang.String);
+        native java.lang.String rustLeaf(java.lang.String);
+    }
+}
+
+// Rust -> Java -> Rust (here) -> Java
+#[duchess::java_function(reentrant.Reentrant::rustMiddle)]
+fn rust_middle(
+    this: &reentrant::Reentrant,
+    name: Option<&java::lang::String>,
+) -> duchess::Result<String> {
+    let inner: String = this.inner(&name).assert_not_null().execute()?;
+    Ok(format!("middle({inner})"))
+}
+
+// Rust -> Java -> Rust -> Java -> Rust (here)
+#[duchess::java_function(reentrant.Reentrant::rustLeaf)]
+fn rust_leaf(
+    _this: &reentrant::Reentrant,
+    name: Option<&java::lang::String>,
+) -> duchess::Result<String> {
+    let name: String = name.assert_not_null().execute()?;
+    Ok(format!("leaf({name})"))
+}
+
+fn main() -> duchess::Result<()> {
+    duchess::Jvm::builder()
+        .link(vec![rust_middle::java_fn(), rust_leaf::java_fn()])
+        .try_launch()?;
+
+    let reentrant: Java<reentrant::Reentrant> = reentrant::Reentrant::new().execute()?;
+    for _ in 0..100 {
+        let result: String = reentrant.outer("Ferris").assert_not_null().execute()?;
+        assert_eq!(result, "outer(middle(inner(leaf(Ferris))))");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+//@run
+use std::collections::BTreeMap;
+
+use duchess::{java, prelude::*, serde::Serde};
+
+type Settings = BTreeMap<String, (Vec<u32>, Option<f64>, bool, char)>;
+
+pub fn main() -> duchess::Result<()> {
+    let settings: Settings = [
+        ("ferris".to_string(), (vec![1, 2, 3], None, true, 'f')),
+        ("duke".to_string(), (vec![], Some(1.5), false, 'd')),
+    ]
+    .into_iter()
+    .collect();
+
+    // Maps become `java.util.HashMap`s...
+    let java: Java<java::lang::Object> = Serde(&settings).to_java().assert_not_null().execute()?;
+    let map: Java<java::util::Map<java::lang::Object, java::lang::Object>> = java
+        .try_downcast::<java::util::Map<java::lang::Object, java::lang::Object>>()
+        .execute()?
+        .unwrap_or_else(|_| panic!("expected a map"));
+    assert_eq!(map.size().execute()?, 2);
+
+    // ...holding lists and boxed primitives
+    let ferris: Option<Java<java::lang::Object>> = map.get("ferris").execute()?;
+    let ferris = ferris
+        .unwrap()
+        .try_downcast::<java::util::List<java::lang::Object>>()
+        .execute()?
+        .unwrap_or_else(|_| panic!("expected a list"));
+    assert_eq!(ferris.size().execute()?, 4);
+    let retries = ferris
+        .get(0)
+        .try_downcast::<java::util::List<java::lang::Object>>()
+        .execute()?
+        .unwrap_or_else(|_| panic!("expected a list"));
+    let first = retries
+        .get(0)
+        .try_downcast::<java::lang::Long>()
+        .execute()?
+        .unwrap_or_else(|_| panic!("expected a long"));
+    assert_eq!(first.long_value().execute()?, 1);
+    let timeout: Option<Java<java::lang::Object>> = ferris.get(1).execute()?;
+    assert!(timeout.is_none());
+
+    // Everything converts back, including to other shapes of the same data
+    let Serde(and_back): Serde<Settings> = Serde::from_java(&java).execute()?;
+    assert_eq!(and_back, settings);
+    let Serde(prefix): Serde<BTreeMap<String, (Vec<u8>, Option<f32>)>> =
+        Serde::from_java(&java).execute()?;
+    assert_eq!(prefix["ferris"], (vec![1, 2, 3], None));
+    assert_eq!(prefix["duke"], (vec![], Some(1.5)));
+
+    // Numbers boxed on the Java side convert too
+    let parsed: Java<java::lang::Long> = java::lang::Long::value_of("42")
+        .assert_not_null()
+        .execute()?;
+    let Serde(parsed): Serde<i64> = Serde::from_java(&parsed).execute()?;
+    assert_eq!(parsed, 42);
+
+    // Enums other than unit variants become a map with a single entry
+    let ok: Result<i16, String> = Ok(7);
+    let java: Java<java::lang::Object> = Serde(&ok).to_java().assert_not_null().execute()?;
+    let map: Java<java::util::Map<java::lang::Object, java::lang::Object>> = java
+        .try_downcast::<java::util::Map<java::lang::Object, java::lang::Object>>()
+        .execute()?
+        .unwrap_or_else(|_| panic!("expected a map"));
+    let value: Option<Java<java::lang::Object>> = map.get("Ok").execute()?;
+    assert!(value.is_some());
+    let Serde(and_back): Serde<Result<i16, String>> = Serde::from_java(&java).execute()?;
+    assert_eq!(and_back, ok);
+
+    // Mismatched shapes and values that do not fit are reported as errors
+    let result: duchess::Result<Serde<BTreeMap<String, (Vec<i8>, bool)>>> =
+        Serde::from_java(&java).execute();
+    assert!(result.is_err());
+    let too_big: duchess::Result<Java<java::lang::Object>> =
+        Serde(&u64::MAX).to_java().assert_not_null().execute();
+    assert!(too_big.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//@run
+use std::error::Error as _;
+
+use duchess::prelude::*;
+
+duchess::java_package! {
+    package exceptions;
+
+    public class ThrowExceptions { * }
+}
+
+pub fn main() -> duchess::Result<()> {
+    let thrower = exceptions::ThrowExceptions::new().execute()?;
+    let error = thrower
+        .throw_with_cause()
+        .execute()
+        .expect_err("method throws an exception");
+
+    let trace = error.stack_trace().expect("thrown exceptions have a stack trace");
+    assert_eq!(trace.class_name, "java.lang.RuntimeException");
+    assert_eq!(trace.message.as_deref(), Some("outer problem"));
+    let frame = &trace.frames[0];
+    assert_eq!(frame.class_name, "exceptions.ThrowExceptions");
+    assert_eq!(frame.method_name, "throwWithCause");
+    assert_eq!(frame.file_name.as_deref(), Some("ThrowExceptions.java"));
+    assert!(frame.line_number.is_some());
+    assert!(!frame.is_native);
+
+    assert_eq!(trace.suppressed.len(), 1);
+    assert_eq!(
+        trace.suppressed[0].to_string(),
+        "java.lang.IllegalArgumentException: cleanup failed"
+    );
+    let cause = trace.cause.as_deref().expect("exception has a cause");
+    assert_eq!(cause.class_name, "java.lang.IllegalStateException");
+    assert!(cause.cause.is_none());
+    assert_eq!(trace.chain().count(), 2);
+
+    // The causes are reported through the trace, not the error
+    assert!(error.source().is_none());
+    let source = trace.source().expect("the cause is the source");
+    assert_eq!(
+        source.to_string(),
+        "java.lang.IllegalStateException: inner problem"
+    );
+    assert!(source.source().is_none());
+
+    assert_eq!(
+        error.to_string(),
+        "Java invocation threw: java.lang.RuntimeException: outer problem"
+    );
+    let debug = format!("{error:?}");
+    for expected in [
+        "Java invocation threw: java.lang.RuntimeException: outer problem\n",
+        "\n\tat exceptions.ThrowExceptions.throwWithCause(ThrowExceptions.java:",
+        "\n\tSuppressed: java.lang.IllegalArgumentException: cleanup failed\n\t\tat ",
+        "\nCaused by: java.lang.IllegalStateException: inner problem\n\tat ",
+    ] {
+        assert!(debug.contains(expected), "{debug}");
+    }
+
+    let error = thrower
+        .null_object()
+        .to_string()
+        .execute::<Option<String>>()
+        .expect_err("returns a null pointer");
+    assert!(error.stack_trace().is_none());
+    assert!(error.source().is_none());
+
+    Ok(())
+}
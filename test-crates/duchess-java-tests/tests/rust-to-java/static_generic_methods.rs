@@ -0,0 +1,76 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package generics;
+
+    public class generics.Pair { * }
+}
+
+fn main() -> duchess::Result<()> {
+    use generics::Pair;
+
+    // Static methods with their own generics are called on the erased class
+    let pair: Java<Pair<java::lang::String, java::lang::String>> =
+        Pair::twin::<java::lang::String>("twin")
+            .assert_not_null()
+            .execute()?;
+    let first: String = pair.get_first().assert_not_null().execute()?;
+    let second: String = pair.get_second().assert_not_null().execute()?;
+    assert_eq!((first.as_str(), second.as_str()), ("twin", "twin"));
+
+    // ...even if their generics shadow the generics of the class
+    let pair: Java<Pair<java::lang::String, java::lang::Object>> =
+        Pair::new("left", java::lang::Object::new())
+            .assert_not_null()
+            .execute()?;
+    let swapped: Java<Pair<java::lang::Object, java::lang::String>> =
+        Pair::swapped::<java::lang::Object, java::lang::String>(&pair)
+            .assert_not_null()
+            .execute()?;
+    let second: String = swapped.get_second().assert_not_null().execute()?;
+    assert_eq!(second, "left");
+
+    // Factory methods in the JDK
+    let list: Java<java::util::List<java::lang::String>> =
+        java::util::List::of::<java::lang::String>(["a", "b", "c"])
+            .assert_not_null()
+            .execute()?;
+    assert_eq!(list.size().execute()?, 3);
+    let element: String = list.get(1).assert_not_null().execute()?;
+    assert_eq!(element, "b");
+
+    let map: Java<java::util::Map<java::lang::String, java::lang::String>> =
+        java::util::Map::of::<java::lang::String, java::lang::String>("key", "value")
+            .assert_not_null()
+            .execute()?;
+    assert_eq!(map.size().execute()?, 1);
+
+    let empty: Java<java::util::List<java::lang::String>> =
+        java::util::Collections::empty_list::<java::lang::String>()
+            .assert_not_null()
+            .execute()?;
+    assert!(empty.is_empty().execute()?);
+
+    let optional: Java<java::util::Optional<java::lang::String>> =
+        java::util::Optional::of::<java::lang::String>("present")
+            .assert_not_null()
+            .execute()?;
+    assert!(optional.is_present().execute()?);
+    let value: String = optional.get().assert_not_null().execute()?;
+    assert_eq!(value, "present");
+    let absent: Java<java::util::Optional<java::lang::String>> =
+        java::util::Optional::empty::<java::lang::String>()
+            .assert_not_null()
+            .execute()?;
+    assert!(absent.is_empty().execute()?);
+
+    let future: Java<java::util::concurrent::CompletableFuture<java::lang::String>> =
+        java::util::concurrent::CompletableFuture::completed_future::<java::lang::String>("done")
+            .assert_not_null()
+            .execute()?;
+    let done: String = future.join().assert_not_null().execute()?;
+    assert_eq!(done, "done");
+
+    Ok(())
+}
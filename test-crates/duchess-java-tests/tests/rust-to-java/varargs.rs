@@ -0,0 +1,67 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package varargs;
+
+    public class Varargs { * }
+}
+
+#[derive(duchess::ToJava)]
+#[java(varargs.Varargs)]
+struct Names {
+    names: Vec<String>,
+}
+
+fn main() -> duchess::Result<()> {
+    // Varargs accept Rust arrays, slices, `Vec`s, and iterators
+    assert_eq!(varargs::Varargs::sum([1, 2, 3]).execute()?, 6);
+    assert_eq!(varargs::Varargs::sum(&[4, 5][..]).execute()?, 9);
+    assert_eq!(varargs::Varargs::sum(vec![6; 3]).execute()?, 18);
+    assert_eq!(varargs::Varargs::sum((1..=4).map(|i| i * 10)).execute()?, 100);
+    assert_eq!(varargs::Varargs::sum(Vec::<i32>::new()).execute()?, 0);
+
+    // Arguments to object varargs can be anything convertible to Java
+    let described: String = varargs::Varargs::describe(["a", "b"])
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(described, "a;b;");
+    let present: Option<Java<java::lang::String>> = "c".to_java().execute()?;
+    let absent: Option<Java<java::lang::String>> = None;
+    let described: String = varargs::Varargs::describe([&present, &absent])
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(described, "c;null;");
+
+    // Varargs can come after other parameters, including on constructors and instance methods
+    let v: Java<varargs::Varargs> = varargs::Varargs::new(vec!["x", "y", "z"]).execute()?;
+    assert_eq!(v.count().execute()?, 3);
+    let names: String = v.names(", ").assert_not_null().execute()?;
+    assert_eq!(names, "x, y, z");
+    let parts = vec!["p".to_string(), "q".to_string()];
+    let prefixed: String = v.prefixed("-", &parts[..]).assert_not_null().execute()?;
+    assert_eq!(prefixed, "-p-q");
+
+    // Existing Java arrays can also be passed
+    let values: Java<java::Array<i32>> = vec![7, 8]
+        .to_java::<java::Array<i32>>()
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(varargs::Varargs::sum(&values).execute()?, 15);
+
+    // Including for methods in the JDK
+    let formatted: String = java::lang::String::format("%s is %s", ["Ferris", "a crab"])
+        .assert_not_null()
+        .execute()?;
+    assert_eq!(formatted, "Ferris is a crab");
+
+    // Derived conversions pass a collection field as the varargs
+    let names = Names {
+        names: vec!["m".to_string(), "n".to_string()],
+    };
+    let v: Java<varargs::Varargs> = names.to_java().assert_not_null().execute()?;
+    let names: String = v.names("+").assert_not_null().execute()?;
+    assert_eq!(names, "m+n");
+
+    Ok(())
+}
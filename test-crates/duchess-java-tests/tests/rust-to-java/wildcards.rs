@@ -0,0 +1,76 @@
+//@run
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package wildcards;
+
+    public class Animal { * }
+    public class Dog { * }
+    public class Wildcards { * }
+}
+
+fn main() -> duchess::Result<()> {
+    use wildcards::{Animal, Dog, Wildcards};
+
+    let rex: Java<Dog> = Dog::new("rex").execute()?;
+    let fido: Java<Dog> = Dog::new("fido").execute()?;
+    let dogs: Java<java::util::List<Dog>> = java::util::List::of::<Dog>([&rex, &fido])
+        .assert_not_null()
+        .execute()?;
+
+    // `? extends Animal` in argument position accepts a list of any subclass
+    let names: String = Wildcards::names(&dogs).assert_not_null().execute()?;
+    assert_eq!(names, "rex;fido;");
+
+    // `? super Dog` in argument position accepts a list of any superclass
+    let animals: Java<java::util::ArrayList<Animal>> =
+        java::util::ArrayList::<Animal>::new().execute()?;
+    Wildcards::add_dog(&animals, &rex).execute()?;
+    let objects: Java<java::util::ArrayList<java::lang::Object>> =
+        java::util::ArrayList::<java::lang::Object>::new().execute()?;
+    Wildcards::add_dog(&objects, &fido).execute()?;
+    assert_eq!(animals.size().execute()?, 1);
+    assert_eq!(objects.size().execute()?, 1);
+
+    // `?` in argument position accepts any list
+    assert_eq!(Wildcards::count(&dogs).execute()?, 2);
+    assert_eq!(Wildcards::count(&animals).execute()?, 1);
+
+    // `? extends Animal` in return position is erased to `Animal`
+    let pack: Java<java::util::List<Animal>> = Wildcards::pack().assert_not_null().execute()?;
+    let animal: Java<Animal> = pack.get(1).assert_not_null().execute()?;
+    let name: String = animal.get_name().assert_not_null().execute()?;
+    assert_eq!(name, "generic");
+
+    // `?` and `? super Dog` in return position are erased to `Object`
+    let anything: Java<java::util::List<java::lang::Object>> =
+        Wildcards::anything().assert_not_null().execute()?;
+    assert_eq!(anything.size().execute()?, 2);
+    let kennel: Java<java::util::List<java::lang::Object>> =
+        Wildcards::kennel().assert_not_null().execute()?;
+    assert_eq!(kennel.size().execute()?, 1);
+
+    // Fields with wildcards are erased when read and accept any matching list when written
+    let wildcards: Java<Wildcards> = Wildcards::new().execute()?;
+    wildcards.set_animals(&dogs).execute()?;
+    let animals: Java<java::util::List<Animal>> =
+        wildcards.animals().assert_not_null().execute()?;
+    assert_eq!(animals.size().execute()?, 2);
+
+    // JDK methods taking wildcards
+    let map: Java<java::util::HashMap<java::lang::String, java::lang::String>> =
+        java::util::HashMap::new().execute()?;
+    let _previous: Option<String> = map.put("k", "v").execute()?;
+    let keys: Java<java::util::Set<java::lang::String>> =
+        map.key_set().assert_not_null().execute()?;
+    assert!(keys.contains("k").execute()?);
+    let values: Java<java::util::Collection<java::lang::String>> =
+        map.values().assert_not_null().execute()?;
+    assert_eq!(values.size().execute()?, 1);
+    let copy: Java<java::util::HashMap<java::lang::String, java::lang::Object>> =
+        java::util::HashMap::new().execute()?;
+    copy.put_all(&map).execute()?;
+    assert_eq!(copy.size().execute()?, 1);
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use duchess::{java, prelude::*};
+
+duchess::java_package! {
+    package wildcards;
+
+    public class Animal { * }
+    public class Dog { * }
+    public class Wildcards { * }
+}
+
+fn main() -> duchess::Result<()> {
+    use wildcards::{Dog, Wildcards};
+
+    let strings: Java<java::util::ArrayList<java::lang::String>> =
+        java::util::ArrayList::new().execute()?;
+    let rex: Java<Dog> = Dog::new("rex").execute()?;
+
+    // `String` does not extend `Animal`
+    Wildcards::names(&strings).execute()?; //~ ERROR: the trait bound
+
+    // `String` is not a superclass of `Dog`
+    Wildcards::add_dog(&strings, &rex).execute()?; //~ ERROR: the trait bound
+
+    Ok(())
+}
@@ -34,6 +34,9 @@ fn run_rust_tests(
 
     config.out_dir = Path::new("..").join("target");
 
+    // Diagnostics pointing into duchess itself should not depend on where it is checked out
+    config.path_stderr_filter(&Path::new("../../Cargo.toml"), "$DUCHESS");
+
     // Make sure we can depend on duchess itself in our tests
     config
         .comment_defaults
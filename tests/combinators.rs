@@ -0,0 +1,110 @@
+use std::cell::Cell;
+
+use duchess::{java, Error, Java, JvmOp, JvmOpExt, Null};
+
+fn list_of(elements: &[Option<&str>]) -> Java<java::util::ArrayList<java::lang::String>> {
+    let list: Java<java::util::ArrayList<java::lang::String>> =
+        java::util::ArrayList::new().execute().unwrap();
+    for element in elements {
+        match element {
+            Some(element) => list.add(*element).execute().unwrap(),
+            None => list.add(Null).execute().unwrap(),
+        };
+    }
+    list
+}
+
+#[test]
+fn map_and_and_then() {
+    let list = list_of(&[Some("a"), Some("b")]);
+    let doubled: i32 = list.size().map(|_jvm, size| size * 2).execute().unwrap();
+    assert_eq!(doubled, 4);
+
+    // Build and query a list within a single crossing
+    let size: i32 = java::util::ArrayList::<java::lang::String>::new()
+        .and_then(|jvm, list| {
+            list.add("x").do_jni(jvm)?;
+            list.add("y").do_jni(jvm)?;
+            list.size().do_jni(jvm)
+        })
+        .execute()
+        .unwrap();
+    assert_eq!(size, 2);
+}
+
+#[test]
+fn inspect_sees_output() {
+    let list = list_of(&[Some("a")]);
+    let seen = Cell::new(0);
+    let size: i32 = list
+        .size()
+        .inspect(|size| seen.set(*size))
+        .execute()
+        .unwrap();
+    assert_eq!((size, seen.get()), (1, 1));
+}
+
+#[test]
+fn if_null_and_or_else() {
+    let list = list_of(&[None, Some("b")]);
+
+    let first: Option<String> = list.get(0).if_null("default").execute().unwrap();
+    assert_eq!(first.as_deref(), Some("default"));
+    let second: Option<String> = list.get(1).if_null("default").execute().unwrap();
+    assert_eq!(second.as_deref(), Some("b"));
+
+    // Out of bounds accesses throw, which `or_else` recovers from
+    let missing: Option<String> = list
+        .get(5)
+        .or_else(|_jvm, error| match error {
+            Error::Thrown(_) => Ok(None),
+            error => Err(error),
+        })
+        .execute()
+        .unwrap();
+    assert_eq!(missing, None);
+}
+
+#[test]
+fn for_each_visits_elements() {
+    let list = list_of(&[Some("one"), None, Some("three")]);
+    let lengths = Cell::new(0);
+    let nulls = Cell::new(0);
+    let () = list
+        .for_each(|jvm, element: Option<duchess::Local<java::lang::String>>| {
+            match element {
+                Some(element) => lengths.set(lengths.get() + element.length().do_jni(jvm)?),
+                None => nulls.set(nulls.get() + 1),
+            }
+            Ok(())
+        })
+        .execute()
+        .unwrap();
+    assert_eq!((lengths.get(), nulls.get()), (8, 1));
+}
+
+#[test]
+fn join_runs_all_ops() {
+    let list = list_of(&[Some("a"), Some("b"), Some("c")]);
+    let (size, empty, first): (i32, bool, Option<String>) =
+        duchess::join((list.size(), list.is_empty(), list.get(0)))
+            .execute()
+            .unwrap();
+    assert_eq!((size, empty, first.as_deref()), (3, false, Some("a")));
+
+    let elements: [Option<String>; 3] = duchess::join([list.get(2), list.get(1), list.get(0)])
+        .execute()
+        .unwrap();
+    assert_eq!(
+        elements,
+        [
+            Some("c".to_string()),
+            Some("b".to_string()),
+            Some("a".to_string())
+        ]
+    );
+
+    assert!(duchess::join((list.size(), list.get(3)))
+        .execute::<(i32, Option<String>)>()
+        .is_err());
+}
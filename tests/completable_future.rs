@@ -0,0 +1,146 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+use duchess::{
+    java::{self, util::concurrent::CompletableFuture},
+    Error, Java, JvmOp, JvmOpFutureExt, RustFuture, ToJava,
+};
+
+/// A minimal executor: polls `future` on the current thread, parking until woken.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn new_future() -> Java<CompletableFuture<java::lang::String>> {
+    CompletableFuture::new().execute().unwrap()
+}
+
+#[test]
+fn await_completed_from_another_thread() {
+    let future = new_future();
+
+    let completer = {
+        let future: Java<_> = future.execute().unwrap();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            let completed: bool = future.complete("hello").execute().unwrap();
+            assert!(completed);
+        })
+    };
+
+    let value: Option<String> = block_on(future.into_future()).unwrap();
+    assert_eq!(value.as_deref(), Some("hello"));
+    completer.join().unwrap();
+}
+
+#[test]
+fn await_already_completed() {
+    let future = new_future();
+    let _: bool = future.complete("done").execute().unwrap();
+
+    let value: Option<String> = block_on(future.into_future()).unwrap();
+    assert_eq!(value.as_deref(), Some("done"));
+}
+
+#[test]
+fn await_completed_with_null() {
+    let future = new_future();
+    let _: bool = future.complete(duchess::Null).execute().unwrap();
+
+    let value: Option<String> = block_on(future.into_future()).unwrap();
+    assert_eq!(value, None);
+}
+
+#[test]
+fn await_completed_exceptionally() {
+    let future = new_future();
+    let exception = java::util::concurrent::CompletionException::new("boom")
+        .execute()
+        .unwrap();
+    let _: bool = future.complete_exceptionally(&exception).execute().unwrap();
+
+    let result: duchess::Result<Option<String>> = block_on(future.into_future());
+    match result {
+        Err(Error::Thrown(_)) => {}
+        other => panic!("expected a thrown exception, got {other:?}"),
+    }
+}
+
+#[test]
+fn rust_future_to_java() {
+    let future = RustFuture::new(async { "from rust".to_string() });
+    let java: Java<CompletableFuture<java::lang::String>> = future
+        .to_java::<CompletableFuture<java::lang::String>>()
+        .assert_not_null()
+        .execute()
+        .unwrap();
+
+    let value: Option<String> = java.join().execute().unwrap();
+    assert_eq!(value.as_deref(), Some("from rust"));
+}
+
+#[test]
+fn rust_future_awaiting_java_future() {
+    let pending = new_future();
+    let future = RustFuture::new({
+        let pending: Java<CompletableFuture<java::lang::String>> = pending.execute().unwrap();
+        async move {
+            let value: Option<String> = pending.into_future().await.unwrap();
+            format!("{}!", value.unwrap())
+        }
+    });
+    let java: Java<CompletableFuture<java::lang::String>> = future
+        .to_java::<CompletableFuture<java::lang::String>>()
+        .assert_not_null()
+        .execute()
+        .unwrap();
+
+    let done: bool = java.is_done().execute().unwrap();
+    assert!(!done);
+
+    let _: bool = pending.complete("hello").execute().unwrap();
+    let value: Option<String> = java.join().execute().unwrap();
+    assert_eq!(value.as_deref(), Some("hello!"));
+}
+
+#[test]
+fn rust_future_panics() {
+    let future = RustFuture::new(async {
+        if true {
+            panic!("oh no");
+        }
+        String::new()
+    });
+    let java: Java<CompletableFuture<java::lang::String>> = future
+        .to_java::<CompletableFuture<java::lang::String>>()
+        .assert_not_null()
+        .execute()
+        .unwrap();
+
+    let result: duchess::Result<Option<String>> = java.join().execute();
+    assert!(matches!(result, Err(Error::Thrown(_))));
+    let exceptionally: bool = java.is_completed_exceptionally().execute().unwrap();
+    assert!(exceptionally);
+}
@@ -0,0 +1,148 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use duchess::{
+    java::{self, lang::management::ManagementFactory, nio::ByteBuffer, ByteBufferExt},
+    BufferMemory, DirectByteBuffer, Java, JvmOp, ToJava,
+};
+
+fn to_java<B: BufferMemory>(memory: B) -> Java<ByteBuffer> {
+    DirectByteBuffer::new(memory)
+        .to_java::<ByteBuffer>()
+        .assert_not_null()
+        .execute()
+        .unwrap()
+}
+
+#[test]
+fn rust_memory_is_shared_with_java() {
+    let buffer = to_java(vec![1u8, 2, 3].into_boxed_slice());
+    assert!(buffer.is_direct().execute().unwrap());
+    assert!(!buffer.is_read_only().execute().unwrap());
+    assert_eq!(buffer.capacity().execute().unwrap(), 3);
+    assert_eq!(buffer.get(1).execute().unwrap(), 2);
+
+    // Writes from Java are visible from Rust...
+    buffer.put(0, 42i8).execute().unwrap();
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let bytes: i32 = unsafe {
+        buffer.with_direct_bytes(|bytes| {
+            assert_eq!(bytes, [42, 2, 3]);
+            bytes.len() as i32
+        })
+    }
+    .execute()
+    .unwrap();
+    assert_eq!(bytes, 3);
+
+    // ...and the other way around
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let () = unsafe { buffer.with_direct_bytes_mut(|bytes| bytes[2] = 7) }
+        .execute()
+        .unwrap();
+    assert_eq!(buffer.get(2).execute().unwrap(), 7);
+}
+
+#[test]
+fn read_only_memory() {
+    let buffer = to_java(&b"hello"[..]);
+    assert!(buffer.is_read_only().execute().unwrap());
+    assert!(buffer.is_direct().execute().unwrap());
+    assert_eq!(buffer.get(4).execute().unwrap(), b'o' as i8);
+
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let first: i32 = unsafe { buffer.with_direct_bytes(|bytes| bytes[0] as i32) }
+        .execute()
+        .unwrap();
+    assert_eq!(first, b'h' as i32);
+
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let result = unsafe { buffer.with_direct_bytes_mut(|bytes| bytes[0] = 0) }.execute();
+    assert!(result.is_err());
+}
+
+#[test]
+fn heap_buffers_cannot_be_borrowed() {
+    let buffer: Java<ByteBuffer> = ByteBuffer::allocate(4).assert_not_null().execute().unwrap();
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let result = unsafe { buffer.with_direct_bytes(|_| ()) }.execute();
+    assert!(result.is_err());
+
+    let buffer: Java<ByteBuffer> = ByteBuffer::allocate_direct(4)
+        .assert_not_null()
+        .execute()
+        .unwrap();
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let () = unsafe { buffer.with_direct_bytes_mut(|bytes| bytes.fill(1)) }
+        .execute()
+        .unwrap();
+    assert_eq!(buffer.get(3).execute().unwrap(), 1);
+}
+
+#[test]
+fn java_calls_fail_while_borrowed() {
+    let buffer = to_java(vec![0u8; 8]);
+    let inner = &buffer;
+    // SAFETY: Java code does not use `buffer` in the meantime
+    let failed: bool =
+        unsafe { buffer.with_direct_bytes(move |_| inner.capacity().execute().is_err()) }
+            .execute()
+            .unwrap();
+    assert!(failed);
+}
+
+#[test]
+fn conversion_happens_once() {
+    let memory = DirectByteBuffer::new(vec![0u8; 8]);
+    let _buffer: Java<ByteBuffer> = memory.to_java().assert_not_null().execute().unwrap();
+    let again: duchess::Result<Option<Java<ByteBuffer>>> = memory.to_java::<ByteBuffer>().execute();
+    assert!(again.is_err());
+}
+
+#[test]
+fn memory_is_released_after_collection() {
+    struct Tracked(Vec<u8>, Arc<AtomicBool>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.store(true, Ordering::SeqCst);
+        }
+    }
+
+    unsafe impl BufferMemory for Tracked {
+        const READ_ONLY: bool = false;
+
+        fn as_mut_ptr(&mut self) -> *mut u8 {
+            self.0.as_mut_ptr()
+        }
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let buffer = to_java(Tracked(vec![0; 1024], dropped.clone()));
+    assert!(!dropped.load(Ordering::SeqCst));
+    drop(buffer);
+
+    let memory: Java<java::lang::management::MemoryMXBean> =
+        ManagementFactory::get_memory_mx_bean()
+            .assert_not_null()
+            .execute()
+            .unwrap();
+    for _ in 0..100 {
+        if dropped.load(Ordering::SeqCst) {
+            return;
+        }
+        memory.gc().execute().unwrap();
+        thread::sleep(Duration::from_millis(50));
+    }
+    panic!("memory was not released");
+}
@@ -0,0 +1,80 @@
+use std::{thread, time::Duration};
+
+use duchess::{
+    java::{self, lang::management::ManagementFactory},
+    Error, Java, JvmOp, Null,
+};
+
+fn new_object() -> Java<java::lang::Object> {
+    java::lang::Object::new().execute().unwrap()
+}
+
+#[test]
+fn weak_refs_can_be_upgraded() {
+    let object = new_object();
+    let weak = object.downgrade().unwrap();
+    assert!(!weak.is_collected().unwrap());
+
+    let upgraded: Option<Java<java::lang::Object>> = weak.upgrade().execute().unwrap();
+    assert!(weak.refers_to(&upgraded.unwrap()).unwrap());
+
+    // Methods can be called on the object through the weak ref
+    let hash: i32 = weak.upgrade().hash_code().execute().unwrap();
+    assert_eq!(hash, object.hash_code().execute().unwrap());
+}
+
+#[test]
+fn weak_refs_compare_by_object() {
+    let object = new_object();
+    let other = new_object();
+    let weak = object.downgrade().unwrap();
+    let same = object.downgrade().unwrap();
+    let different = other.downgrade().unwrap();
+
+    assert!(weak.is_same(&same).unwrap());
+    assert!(!weak.is_same(&different).unwrap());
+    assert!(weak.refers_to(&object).unwrap());
+    assert!(!weak.refers_to(&other).unwrap());
+    assert!(!weak.refers_to(&Null).unwrap());
+}
+
+#[test]
+fn weak_refs_are_shared_across_threads() {
+    let object = new_object();
+    let weak = object.downgrade().unwrap();
+    let hash: i32 = thread::scope(|s| {
+        s.spawn(|| weak.upgrade().hash_code().execute().unwrap())
+            .join()
+            .unwrap()
+    });
+    assert_eq!(hash, object.hash_code().execute().unwrap());
+}
+
+#[test]
+fn weak_refs_do_not_keep_objects_alive() {
+    let object = new_object();
+    let weak = object.downgrade().unwrap();
+    drop(object);
+
+    let memory: Java<java::lang::management::MemoryMXBean> =
+        ManagementFactory::get_memory_mx_bean()
+            .assert_not_null()
+            .execute()
+            .unwrap();
+    for _ in 0..100 {
+        if weak.is_collected().unwrap() {
+            break;
+        }
+        memory.gc().execute().unwrap();
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert!(weak.is_collected().unwrap());
+
+    let upgraded: Option<Java<java::lang::Object>> = weak.upgrade().execute().unwrap();
+    assert!(upgraded.is_none());
+    assert!(weak.refers_to(&Null).unwrap());
+    assert!(matches!(
+        weak.upgrade().hash_code().execute::<i32>(),
+        Err(Error::NullDeref)
+    ));
+}
@@ -0,0 +1,31 @@
+use duchess::{java, Jvm, JvmOp};
+
+#[test]
+fn test_structured_jvm_options() {
+    Jvm::builder()
+        .add_classpath("first-entry")
+        .add_classpath("second-entry")
+        .system_property("duchess.test", "overridden")
+        .system_property("duchess.test", "value")
+        .initial_heap_size("16m")
+        .max_heap_size("256m")
+        .thread_stack_size("1m")
+        // Would prevent the JVM from launching if it weren't ignored
+        .custom("-Xduchess-unrecognized")
+        .ignore_unrecognized(true)
+        .try_launch()
+        .unwrap();
+
+    let property: Option<String> = java::lang::System::get_property("duchess.test")
+        .execute()
+        .unwrap();
+    assert_eq!(property.as_deref(), Some("value"));
+
+    // Both classpath entries end up in the same option
+    let classpath: Option<String> = java::lang::System::get_property("java.class.path")
+        .execute()
+        .unwrap();
+    let classpath = classpath.unwrap();
+    assert!(classpath.contains("first-entry"), "{classpath}");
+    assert!(classpath.contains("second-entry"), "{classpath}");
+}
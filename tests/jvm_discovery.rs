@@ -0,0 +1,22 @@
+#![cfg(feature = "dylibjvm")]
+
+use duchess::{Error, Jvm};
+
+#[test]
+fn test_jvm_discovery() {
+    // No JVM is that new, so every candidate is rejected
+    let Err(Error::UnableToLoadLibjvm(error)) = Jvm::builder().min_java_version(1000).try_launch()
+    else {
+        panic!("expected no suitable JVM to be found");
+    };
+    assert!(error
+        .to_string()
+        .starts_with("unable to find a JVM for Java 1000 or newer"));
+
+    assert_eq!(Jvm::jni_version(), None);
+    Jvm::builder().min_java_version(8).try_launch().unwrap();
+
+    // Java 8 supports JNI 1.8, newer versions support newer JNI versions
+    let version = Jvm::jni_version().expect("negotiated a JNI version");
+    assert!(version >= 0x0001_0008);
+}
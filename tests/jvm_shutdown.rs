@@ -0,0 +1,52 @@
+use std::sync::mpsc;
+
+use duchess::{java, Error, Java, Jvm, JvmOp, JvmOpExt};
+
+#[test]
+fn test_jvm_shutdown() {
+    let guard = Jvm::builder().try_launch_guarded().unwrap();
+
+    let object: Java<java::lang::Object> = java::lang::Object::new().execute().unwrap();
+    object.hash_code().execute().unwrap();
+
+    // Shutting down from within an operation is refused
+    let refused: bool = java::lang::Object::new()
+        .map(|_jvm, _object| Jvm::shutdown().is_err())
+        .execute()
+        .unwrap();
+    assert!(refused);
+    object.hash_code().execute().unwrap();
+
+    // Shutting down while another thread is permanently attached is refused
+    let (attached_tx, attached_rx) = mpsc::channel();
+    let (exit_tx, exit_rx) = mpsc::channel::<()>();
+    let thread = std::thread::spawn(move || {
+        Jvm::attach_thread_permanently().unwrap();
+        attached_tx.send(()).unwrap();
+        exit_rx.recv().unwrap();
+    });
+    attached_rx.recv().unwrap();
+    assert!(matches!(Jvm::shutdown(), Err(Error::JvmInternal(_))));
+    object.hash_code().execute().unwrap();
+    exit_tx.send(()).unwrap();
+    thread.join().unwrap();
+
+    drop(guard);
+
+    assert!(matches!(
+        java::lang::Object::new().execute::<Java<java::lang::Object>>(),
+        Err(Error::JvmShutDown)
+    ));
+    assert!(matches!(
+        object.hash_code().execute(),
+        Err(Error::JvmShutDown)
+    ));
+    assert!(matches!(Jvm::shutdown(), Err(Error::JvmShutDown)));
+    assert!(matches!(
+        Jvm::builder().launch_or_use_existing(),
+        Err(Error::JvmShutDown)
+    ));
+
+    // References that outlive the JVM can still be dropped
+    drop(object);
+}
@@ -0,0 +1,27 @@
+//! The combinators of `JvmOpExt` are not in the prelude, so they do not hide
+//! Java methods of the same name.
+
+use duchess::{java, prelude::*, Null};
+
+#[test]
+fn optional_or_else() {
+    let optional: Java<java::util::Optional<java::lang::String>> = java::util::Optional::empty()
+        .assert_not_null()
+        .execute()
+        .unwrap();
+    let fallback: Java<java::lang::String> =
+        "fallback".to_java().assert_not_null().execute().unwrap();
+    let value: Option<String> = optional.or_else(&fallback).execute().unwrap();
+    assert_eq!(value.as_deref(), Some("fallback"));
+}
+
+#[test]
+fn map_for_each() {
+    let map: Java<java::util::HashMap<java::lang::String, java::lang::String>> =
+        java::util::HashMap::new().execute().unwrap();
+    // `Map.forEach` rejects a null action
+    let result = map
+        .for_each::<java::lang::String, java::lang::String>(Null)
+        .execute();
+    assert!(result.is_err());
+}
@@ -0,0 +1,44 @@
+use duchess::{java, Java, JvmOp, JvmOpExt, ToJava};
+
+fn string(value: &str) -> Java<java::lang::String> {
+    value.to_java().assert_not_null().execute().unwrap()
+}
+
+#[test]
+fn upcast_global_keeps_its_reference() {
+    let object: Java<java::lang::Object> = string("hello").upcast();
+
+    // A global ref deleted by the upcast would be reused by the ones created here
+    let others: Vec<_> = (0..16).map(|i| string(&format!("other {i}"))).collect();
+
+    let value: String = object.to_string().assert_not_null().execute().unwrap();
+    assert_eq!(value, "hello");
+    drop(object);
+    drop(others);
+}
+
+#[test]
+fn upcast_local_keeps_its_reference() {
+    let expected: i32 = string("hello").hash_code().execute().unwrap();
+    let hash: i32 = "hello"
+        .to_java::<java::lang::String>()
+        .assert_not_null()
+        .and_then(|jvm, string| {
+            let object = string.upcast::<java::lang::Object>();
+
+            // A local ref deleted by the upcast would be reused by the ones created here
+            let mut others = vec![];
+            for i in 0..16 {
+                let other = format!("other {i}")
+                    .to_java::<java::lang::String>()
+                    .assert_not_null()
+                    .do_jni(jvm)?;
+                others.push(other);
+            }
+
+            object.hash_code().do_jni(jvm)
+        })
+        .execute()
+        .unwrap();
+    assert_eq!(hash, expected);
+}